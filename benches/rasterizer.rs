@@ -0,0 +1,64 @@
+//! Rasterizer benchmark
+//! Draws the same batches of triangles one at a time and through the tiled rasterizer, checks they match and prints how long each took.
+//! cargo bench --bench rasterizer
+
+use std::time::{Duration, Instant};
+
+use test_engine::components::{nodes::node::Camera2D, shapes::Triangle};
+use test_engine::graphics::{tiles::{TileRasterizer, TriangleBatch}, window_minifb::Framebuffer};
+use test_engine::util::{linear::{Pos2D, Vec2D}, random::Rng};
+
+const WIDTH: usize = 1280;
+const HEIGHT: usize = 720;
+const RUNS: u32 = 10;
+
+// count triangles up to reach pixels from their middles, scattered over the screen
+fn scene(count: usize, reach: f32) -> TriangleBatch {
+    let mut rng = Rng::new(count as u64);
+    let mut batch = TriangleBatch::new();
+    let (half_width, half_height) = (WIDTH as f32 / 2.0, HEIGHT as f32 / 2.0);
+    for _ in 0..count {
+        let center = Pos2D::new(rng.range(-half_width..half_width), rng.range(-half_height..half_height));
+        let mut corner = || center + Vec2D::new(rng.range(-reach..reach), rng.range(-reach..reach));
+        let triangle = Triangle::new(corner(), corner(), corner());
+        batch.push(triangle, rng.next_u32() & 0xFFFFFF);
+    }
+    batch
+}
+
+// the average time of draw over RUNS frames, cleared between them like a game loop would, and the last frame drawn
+fn time(mut draw: impl FnMut(&mut Framebuffer)) -> (Duration, Framebuffer) {
+    let mut total = Duration::ZERO;
+    let mut framebuffer = Framebuffer::new(WIDTH, HEIGHT);
+    for _ in 0..RUNS {
+        framebuffer.clear(0);
+        let start = Instant::now();
+        draw(&mut framebuffer);
+        total += start.elapsed();
+    }
+    (total / RUNS, framebuffer)
+}
+
+fn same(a: &Framebuffer, b: &Framebuffer) -> bool {
+    (0..HEIGHT).all(|y| (0..WIDTH).all(|x| a.get_pixel(x, y) == b.get_pixel(x, y)))
+}
+
+fn main() {
+    let cam = Camera2D::new(Pos2D::ZERO);
+    let rasterizer = TileRasterizer::new();
+    println!("{}x{}, {} pixel tiles on {} threads, average of {} runs", WIDTH, HEIGHT, rasterizer.tile_size(), rasterizer.threads(), RUNS);
+    for (count, reach) in [(1_000, 80.0), (10_000, 25.0), (100_000, 8.0)] {
+        let batch = scene(count, reach);
+        let (single, expected) = time(|fb| batch.draw_abs(fb, &cam));
+        let (tiled, drawn) = time(|fb| batch.draw_tiled_abs(fb, &rasterizer, &cam));
+        assert!(same(&expected, &drawn), "tiled output on {} threads differs for {} triangles", rasterizer.threads(), count);
+        println!(
+            "{:>7} triangles: single {:>9.3?}  tiled on {} threads {:>9.3?}  {:.2}x",
+            count,
+            single,
+            rasterizer.threads(),
+            tiled,
+            single.as_secs_f64() / tiled.as_secs_f64()
+        );
+    }
+}
@@ -1,5 +1,5 @@
 #[allow(unused)]
-use crate::{graphics::texture::Material, util::linear::{Pos2D, Pos3D, Vec3D}};
+use crate::{graphics::texture::Material, util::linear::{Aabb2D, Mat4, Normalized, Pos2D, Pos3D, Quat, Screen, Vec2D, Vec3D, World}};
 
 // node needs to be converted to a trait with predetermined member functions. this way we can make a tree
 #[allow(unused)]
@@ -39,10 +39,11 @@ pub struct Camera3D {
     children: Vec<Box<dyn Node>>,
     parent: Option<Box<dyn Node>>,
     pos: Pos3D,
-    direction: Direction3D
+    orientation: Quat
 }
 
 #[allow(unused)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Direction3D {
     x: f32,
     y: f32,
@@ -108,15 +109,91 @@ impl Camera2D {
         return self.pos
     }
 
-    pub fn viewport(&self, width: f32, height: f32) -> (Pos2D, Pos2D) {
-        let (mut bl, mut tr) = (self.pos.new_from_self(), self.pos.new_from_self());
-        bl.translate(-width / 2.0, -height / 2.0);
-        tr.translate(width / 2.0, height / 2.0);
-        (bl , tr)
+    /// the area of the world the camera sees on a framebuffer of width x height
+    pub fn viewport(&self, width: f32, height: f32) -> Aabb2D<World> {
+        Aabb2D::from_center(self.pos, Vec2D::new(width / 2.0, height / 2.0))
+    }
+
+    /// converts a world position into framebuffer pixels for a framebuffer of width x height
+    ///
+    /// the camera sits in the middle of the framebuffer and y gets flipped since pixel rows count downwards
+    pub fn world_to_screen(&self, p: Pos2D<World>, width: f32, height: f32) -> Pos2D<Screen> {
+        Pos2D::new(
+            p.x() - self.pos.x() + width / 2.0,
+            self.pos.y() - p.y() + height / 2.0,
+        )
+    }
+
+    /// converts framebuffer pixels back into a world position, the inverse of world_to_screen
+    pub fn screen_to_world(&self, p: Pos2D<Screen>, width: f32, height: f32) -> Pos2D<World> {
+        Pos2D::new(
+            p.x() + self.pos.x() - width / 2.0,
+            self.pos.y() - p.y() + height / 2.0,
+        )
+    }
+
+    /// converts a world box into a pixel box, y flips so the corners swap but the result is still min to max
+    pub fn aabb_to_screen(&self, b: &Aabb2D<World>, width: f32, height: f32) -> Aabb2D<Screen> {
+        Aabb2D::new(self.world_to_screen(b.min(), width, height), self.world_to_screen(b.max(), width, height))
+    }
+
+    pub fn world_to_normalized(&self, p: Pos2D<World>, width: f32, height: f32) -> Pos2D<Normalized> {
+        self.world_to_screen(p, width, height).to_normalized(width, height)
+    }
+
+    pub fn normalized_to_world(&self, p: Pos2D<Normalized>, width: f32, height: f32) -> Pos2D<World> {
+        self.screen_to_world(p.to_screen(width, height), width, height)
     }
 }
 
 impl Camera3D {
+    /// the camera looks down -z (Direction3D::WEST) when its orientation is the identity
+    pub const FORWARD: Direction3D = Direction3D::WEST;
+
+    pub fn new(pos: Pos3D, direction: Direction3D) -> Camera3D {
+        let orientation = Quat::from_rotation_arc(Camera3D::FORWARD.to_vec3d(), direction.to_vec3d());
+        Camera3D { children: Vec::new(), parent: None, pos, orientation }
+    }
+
+    pub fn pos(&self) -> Pos3D {
+        self.pos
+    }
+
+    pub fn orientation(&self) -> Quat {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, orientation: Quat) {
+        self.orientation = orientation.normalize()
+    }
+
+    /// the way the camera is facing
+    pub fn direction(&self) -> Direction3D {
+        Direction3D::from_vec3d(self.orientation * Camera3D::FORWARD.to_vec3d())
+    }
+
+    /// turns the camera to face direction by the shortest rotation from where it is facing now
+    pub fn set_direction(&mut self, direction: Direction3D) {
+        let turn = Quat::from_rotation_arc(self.direction().to_vec3d(), direction.to_vec3d());
+        self.orientation = (turn * self.orientation).normalize();
+    }
+
+    /// rotates the camera by rotation on top of its current orientation
+    pub fn rotate(&mut self, rotation: Quat) {
+        self.orientation = (rotation * self.orientation).normalize();
+    }
+
+    /// moves the orientation t% of the way towards target
+    pub fn slerp_orientation(&mut self, target: Quat, t: f32) {
+        self.orientation = Quat::slerp(self.orientation, target, t);
+    }
+
+    /// world to camera space transform for the camera's current position and orientation
+    pub fn view_matrix(&self) -> Mat4 {
+        // undo the camera's rotation after moving the world so the camera sits at the origin
+        self.orientation.conjugate().to_mat4() * Mat4::translation(-self.pos.x(), -self.pos.y(), -self.pos.z())
+    }
+
     pub fn translate_x(&mut self, tx: f32) {
         self.pos.translate_x(tx);
     }
@@ -151,4 +228,26 @@ impl Direction3D {
             z: z / mag,
         }
     }
+
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    /// normalizes a vector into a direction
+    pub fn from_vec3d(v: Vec3D) -> Self {
+        Direction3D::new(v.dx(), v.dy(), v.dz())
+    }
+
+    /// the direction as a unit vector
+    pub fn to_vec3d(self) -> Vec3D {
+        Vec3D::new(self.x, self.y, self.z)
+    }
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
-use crate::{graphics::window_minifb::Framebuffer, util::{color, linear::Pos2D}};
+use std::f32::consts::{FRAC_PI_2, PI, SQRT_2, TAU};
+use std::ops::{Range, RangeInclusive};
+
+use crate::{graphics::{shading::{Fragment, Varying, Varyings}, texture::Texture, window_minifb::Framebuffer}, util::{color::Color, easing::Interpolate, geometry::{self, FillRule}, linear::{impl_approx_eq_by_parts, Aabb2D, ApproxEq, Mat3, Normalized, Pos2D, Radians, Ray2D, RayHit2D, Screen, Space, Vec2D, World}}};
 
 use super::nodes::node::Camera2D;
 
@@ -6,21 +9,23 @@ use super::nodes::node::Camera2D;
 /// 
 /// in the context of the game engine this will aide in UI construction
 /// 
-/// Shape trait with functions required of every shape (i.e. draw, inside_eh)
+/// Shape trait with functions required of every shape (i.e. inside_eh, translate)
 /// This is to separate functions specific to the shape.
 /// We will need access functions inside the individual shape's impl blocks for type safety purposes
+///
+/// Shapes are tagged with the coordinate space their points are in. Drawing lives on the shape's impl for the space
+/// it makes sense in: draw for Normalized shapes and draw_abs (with a camera) for World shapes.
 
 /// Shape Trait
 /// Core functions related to drawing a scene
 trait Shape {
     type ReturnType;
-    /// draw
-    /// sets the pixels the appropriate pixels to the defined color and eventually texture
-    fn draw(&self, framebuffer: &mut Framebuffer, color: u32);
+    /// the coordinate space the shape's points live in
+    type Coords: Space;
 
     /// inside_eh / is_inside
     /// checks if a point(Pos2D) is inside the given shape.
-    fn inside_eh(&self, p: &Pos2D) -> bool;
+    fn inside_eh(&self, p: &Pos2D<Self::Coords>) -> bool;
 
     /// translate
     /// moves the shape immediately a given x and y
@@ -39,105 +44,138 @@ trait Shape {
     /// mutates the shape immediately to the given points
     /// 
     /// will eventually have an elegant way of changing shapes
-    fn transform(&mut self, points: Vec<Pos2D>);
+    fn transform(&mut self, points: Vec<Pos2D<Self::Coords>>);
 
     /// lerp_transform
     /// mutates the shape gradually to the given points with t% completion
     ///
     /// will eventually have an elegant way of changing shapes
-    fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D>);
+    fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D<Self::Coords>>);
 
     /// lerp_steps_transform
     /// mutates the shape gradually to the given points with t steps
-    fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D>);
+    fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D<Self::Coords>>);
 }
 
-pub struct Triangle {
-    a: Pos2D,
-    b: Pos2D,
-    c: Pos2D,
-}
+// TRAIT ACCESS FUNCTIONS
+// Shape is private so every shape re-exposes it through inherent methods of the same name
+macro_rules! shape_access {
+    ($($shape:ident),*) => {$(
+        impl<S: Space> $shape<S> {
+            pub fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+                <Self as Shape>::inside_eh(self, p)
+            }
 
-impl Triangle {
-    pub fn new(a: Pos2D, b: Pos2D, c: Pos2D) -> Triangle {
-        Triangle{ a, b, c }
-    }
+            pub fn translate(&mut self, tx: f32, ty: f32) {
+                <Self as Shape>::translate(self, tx, ty)
+            }
 
-    /// if edge_function returns positive we are inside the triangle, if not we are outside
-    pub fn edge_function(&self, a: &Pos2D, c: &Pos2D, b: &Pos2D) -> f32 {
-        // we force passing in references to minimize copying things we don't need to. 
-        (c.x() - a.x()) * (b.y()- a.y()) - (c.y() - a.y()) * (b.x() - a.x())
-    }
-    
-    // TRAIT ACCESS FUNCTIONS
-    pub fn inside_eh(&self, p: &Pos2D) -> bool {
-        <Self as Shape>::inside_eh(self, p)
-    }
+            pub fn lerp_translate(&mut self, tx: f32, ty: f32, t: f32) {
+                <Self as Shape>::lerp_translate(self, tx, ty, t)
+            }
 
-    // access function for drawing relative to framebuffer
-    pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
-        <Self as Shape>::draw(self, framebuffer, color)
-    }
+            pub fn lerp_steps_translate(&mut self, tx: f32, ty: f32, t: u32) {
+                <Self as Shape>::lerp_steps_translate(self, tx, ty, t)
+            }
 
-    // we are using draw_abs to pilot drawing the scene with the camera in mind
-    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, camera: &Camera2D) {
-        let width = framebuffer.width() as f32;
-        let height = framebuffer.height() as f32;
-        // don't do draw calculations if not in viewport
-        // print!("in draw_abs");
-        if !self.render_guard(&camera, width, height) {
-            return;
+            pub fn transform(&mut self, points: Vec<Pos2D<S>>) {
+                <Self as Shape>::transform(self, points)
+            }
+
+            pub fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D<S>>) {
+                <Self as Shape>::lerp_transform(self, t, points)
+            }
+
+            pub fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D<S>>) {
+                <Self as Shape>::lerp_steps_transform(self, t, points)
+            }
         }
-        // get the bottom left and top right of our viewport
-        let (bl, tr) = camera.viewport(width, height);
+    )*};
+}
 
-        // find our offset
-        let offset = Pos2D::new(bl.x(), bl.y());
-        // print!("our offset is {}", offset);
+// the lerp half of Shape is the same for every shape once it has translate, transform and points()
+macro_rules! shape_lerps {
+    () => {
+        fn lerp_translate(&mut self, tx: f32, ty: f32, t: f32) {
+            <Self as Shape>::translate(self, tx * t, ty * t);
+        }
 
-        // get the bounding box and and adjust to not care about anything outside of the viewport
-        let mut bounds = self.bounds();
-        bounds.0 = bounds.0.max(bl);
-        bounds.1 = bounds.1.min(tr);
+        // one of t equal steps, 0 steps moves the whole way at once
+        fn lerp_steps_translate(&mut self, tx: f32, ty: f32, t: u32) {
+            let steps = t.max(1) as f32;
+            <Self as Shape>::translate(self, tx / steps, ty / steps);
+        }
 
-        // we can now start drawing the triangle
-        // double for loop uses the bounds as the range and we'll adjust pixels relative to the fb
-        for x in (bounds.0.x() as usize)..(bounds.1.x() as usize) {
-            for y in (bounds.0.y() as usize)..(bounds.1.y() as usize) {
-                // current point we're evaluating
-                let p = Pos2D::new(x as f32, y as f32);
-                if self.inside_eh(&p) {
-                    framebuffer.set_pixel(x + (offset.x() as usize), y + (offset.y() as usize), color)
-                }
-            }
+        fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D<Self::Coords>>) {
+            let targets = self.points().iter().zip(&points).map(|(&from, &to)| Pos2D::lerp(from, to, t)).collect();
+            <Self as Shape>::transform(self, targets);
+        }
+
+        // one of t equal steps towards the points, same as lerp_transform with 1 / t
+        fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D<Self::Coords>>) {
+            <Self as Shape>::lerp_transform(self, 1.0 / t.max(1.0), points);
         }
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Triangle<S: Space = World> {
+    a: Pos2D<S>,
+    b: Pos2D<S>,
+    c: Pos2D<S>,
+}
+
+impl<S: Space> Triangle<S> {
+    pub fn new(a: Pos2D<S>, b: Pos2D<S>, c: Pos2D<S>) -> Triangle<S> {
+        Triangle{ a, b, c }
+    }
 
+    pub fn a(&self) -> Pos2D<S> {
+        self.a
     }
 
-    // returns a Pos2D bounding box tuple with first being min and second being max ignoring the viewport
-    pub fn bounds(&self) -> (Pos2D, Pos2D) {
-        let p_min: Pos2D = self.a.min(self.b.min(self.c));
-        let p_max: Pos2D = self.a.max(self.b.max(self.c));
-        (p_min, p_max)
+    pub fn b(&self) -> Pos2D<S> {
+        self.b
     }
 
-    // if true we can draw the triangle
-    pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
-        // get our mins and maxes for the box formed by the triangle
-        let (p_min, p_max) = self.bounds();
-        // adjust width and height to be half for less messy conditionals
-        let w = width / 2.0; // 2.0 and not 2 bc vscode type inference was scary looking
-        let h = height / 2.0;
-        let cx = cam.pos().x();
-        let cy = cam.pos().y();
-        // check our x vals
-        if p_min.x() < (cx + width) || p_max.x() > (cx - width) {
-            if p_min.y() < (cy + height) || p_max.y() > (cy - height) {
-                return true;
-            }
+    pub fn c(&self) -> Pos2D<S> {
+        self.c
+    }
+
+    /// if edge_function returns positive we are inside the triangle, if not we are outside
+    pub fn edge_function(&self, a: &Pos2D<S>, c: &Pos2D<S>, b: &Pos2D<S>) -> f32 {
+        // we force passing in references to minimize copying things we don't need to. 
+        (c.x() - a.x()) * (b.y()- a.y()) - (c.y() - a.y()) * (b.x() - a.x())
+    }
+    
+    /// how much of a, b and c make up p, adding up to 1. all three are positive inside the triangle
+    ///
+    /// None for a triangle with no area
+    pub fn barycentric(&self, p: &Pos2D<S>) -> Option<[f32; 3]> {
+        let area = self.edge_function(&self.a, &self.b, &self.c);
+        if area == 0.0 || !area.is_finite() {
+            return None;
         }
+        Some([
+            self.edge_function(&self.b, &self.c, p) / area,
+            self.edge_function(&self.c, &self.a, p) / area,
+            self.edge_function(&self.a, &self.b, p) / area,
+        ])
+    }
 
-        return false;
+    // TRAIT ACCESS FUNCTIONS
+    pub fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        <Self as Shape>::inside_eh(self, p)
+    }
+
+    // returns the bounding box of the triangle ignoring the viewport
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::new(self.a, self.b).expand_to(self.c)
+    }
+
+    /// the three edges as a closed polyline, for drawing the triangle unfilled
+    pub fn outline(&self) -> Polyline<S> {
+        Polyline::new_closed(vec![self.a, self.b, self.c])
     }
 
     pub fn translate_a(&mut self, tx: f32, ty: f32) {
@@ -151,48 +189,107 @@ impl Triangle {
     pub fn translate_c(&mut self, tx: f32, ty: f32) {
         self.c.translate(tx, ty)
     }
-}
 
-impl Shape for Triangle {
-    type ReturnType = Triangle;
+    /// applies an affine transform (rotation, scale, etc.) to every point of the triangle
+    pub fn apply_transform(&mut self, m: &Mat3) {
+        self.a = *m * self.a;
+        self.b = *m * self.b;
+        self.c = *m * self.c;
+    }
+
+    /// first edge the ray crosses, a ray starting inside hits the edge it leaves through
+    ///
+    /// the hit normal always faces out of the triangle
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        let ab = self.edge_hit(ray, self.a, self.b, self.c);
+        let bc = self.edge_hit(ray, self.b, self.c, self.a);
+        let ca = self.edge_hit(ray, self.c, self.a, self.b);
+        RayHit2D::closest(RayHit2D::closest(ab, bc), ca)
+    }
+
+    // hit against the edge p -> q with the normal flipped away from the opposite corner
+    fn edge_hit(&self, ray: &Ray2D<S>, p: Pos2D<S>, q: Pos2D<S>, opposite: Pos2D<S>) -> Option<RayHit2D<S>> {
+        let hit = ray.intersect_segment(p, q)?;
+        // edge_function is positive when opposite is to the left of p -> q so outward is to the right
+        let left = (q - p).perp().normalize();
+        let normal = if self.edge_function(&p, &q, &opposite) > 0.0 { -left } else { left };
+        Some(RayHit2D::new(hit.distance(), hit.point(), normal))
+    }
+}
 
+impl Triangle<Normalized> {
     /// Draw the triangle on the screen on top of all previous pixels relative to the height/width of the fb
-    /// 
+    ///
+    /// either winding works, and triangles sharing an edge split the pixels along it so a mesh has no gaps or overlaps
     /// Later we will have an alpha channel to deal with transparency coloring
-    fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
-        let width = framebuffer.width();
-        let height = framebuffer.height();
+    pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
+        // skip anything fully off screen before converting it to pixels
+        if self.bounds().overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)) {
+            fill_triangle(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+        }
+    }
+
+    /// draws the same pixels as draw, with shader picking each one's color from the corner values blended there
+    ///
+    /// a shader returning None leaves that pixel as it was
+    pub fn draw_shaded<V: Varying>(&self, framebuffer: &mut Framebuffer, varyings: &Varyings<V>, shader: impl FnMut(&Fragment<V>) -> Option<u32>) {
+        if self.bounds().overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)) {
+            shade_triangle(self, framebuffer, varyings, &PixelMap::normalized(framebuffer), shader);
+        }
+    }
 
-        // create a bounding box over the triangle so we don't perform checks on every coord
-        // find the smallest value and if < 0 change it. find largest value and if > 1 change it. Then we convert them to pixels.
-        // something to consider later adjusting positions and conditional rendering
-        let min = self.a.min(self.b.min(self.c)).max(Pos2D::ZERO) * Pos2D::new(width as f32, height as f32);
-        let max = self.a.max(self.b.max(self.c)).min(Pos2D::ONE) * Pos2D::new(width as f32, height as f32);
+    /// paints the texture across the triangle, uvs go with a, b and c. the texture's alpha blends over what's there
+    pub fn draw_textured(&self, framebuffer: &mut Framebuffer, texture: &Texture, uvs: [Vec2D; 3]) {
+        let [a, b, c] = uvs;
+        self.draw_shaded(framebuffer, &Varyings::new(a, b, c), |f| Some(texture.sample(f.value()).over(f.under())));
+    }
+}
 
-        for x in (min.x() as usize)..(max.x() as usize) {
-            for y in (min.y() as usize)..(max.y() as usize) {
-                // create a point for each pixel we are attempting to modify within 0 - 1
-                let p = Pos2D::new(x as f32 / width as f32, y as f32 / height as f32);
+impl Triangle<World> {
+    // we are using draw_abs to pilot drawing the scene with the camera in mind
+    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, camera: &Camera2D) {
+        // nothing to do if the triangle is outside of the viewport
+        if self.render_guard(camera, framebuffer.width() as f32, framebuffer.height() as f32) {
+            fill_triangle(self, framebuffer, color, &PixelMap::camera(camera, framebuffer));
+        }
+    }
 
-                if self.inside_eh(&p) {
-                    framebuffer.set_pixel(x, y, color);
-                }
-            }
+    /// draw_shaded through the camera
+    pub fn draw_shaded_abs<V: Varying>(
+        &self,
+        framebuffer: &mut Framebuffer,
+        varyings: &Varyings<V>,
+        camera: &Camera2D,
+        shader: impl FnMut(&Fragment<V>) -> Option<u32>,
+    ) {
+        if self.render_guard(camera, framebuffer.width() as f32, framebuffer.height() as f32) {
+            shade_triangle(self, framebuffer, varyings, &PixelMap::camera(camera, framebuffer), shader);
         }
     }
-    // works with CLOCKWISE winding order
-    // checks if a point is inside the triangle or not via edge functions
-    fn inside_eh(&self, p: &Pos2D) -> bool {
-        let a0 = self.edge_function(&self.b, &self.c, p);
-        let a1 = self.edge_function(&self.c, &self.a, p);
-        let a2 = self.edge_function(&self.a, &self.b, p);
-        
-        let mut overlaps = true;
-        overlaps &= a0 > 0.0;
-        overlaps &= a1 > 0.0;
-        overlaps &= a2 > 0.0;
 
-        overlaps
+    /// draw_textured through the camera
+    pub fn draw_textured_abs(&self, framebuffer: &mut Framebuffer, texture: &Texture, uvs: [Vec2D; 3], camera: &Camera2D) {
+        let [a, b, c] = uvs;
+        self.draw_shaded_abs(framebuffer, &Varyings::new(a, b, c), camera, |f| Some(texture.sample(f.value()).over(f.under())));
+    }
+
+    // if true we can draw the triangle
+    pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
+        self.bounds().overlaps(&cam.viewport(width, height))
+    }
+}
+
+impl<S: Space> Shape for Triangle<S> {
+    type ReturnType = Triangle<S>;
+    type Coords = S;
+
+    // works with either winding order
+    // points exactly on an edge follow the same top-left rule as drawing, as the edge would look on screen,
+    // so a point on the edge between two triangles is inside exactly one of them
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        // y up spaces get flipped so top and left mean the same thing they do on screen
+        let on_screen = |p: Pos2D<S>| Pos2D::<Screen>::new(p.x(), if S::Y_UP { -p.y() } else { p.y() });
+        covers([on_screen(self.a), on_screen(self.b), on_screen(self.c)], on_screen(*p))
     }
 
     fn translate(&mut self, tx: f32, ty: f32) {
@@ -209,37 +306,38 @@ impl Shape for Triangle {
         
     }
 
-    fn transform(&mut self, points: Vec<Pos2D>) {
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
         self.a = Pos2D::new_from_other(*points.get(0).expect("missing point"));
         self.b = Pos2D::new_from_other(*points.get(1).expect("missing point"));
         self.c = Pos2D::new_from_other(*points.get(2).expect("missing point"));
     }
 
-    fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D>) {
+    fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D<S>>) {
         
     }
 
-    fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D>) {
+    fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D<S>>) {
         
     }
 }
 
-pub struct Rect {
-    a: Pos2D,
-    b: Pos2D,
-    c: Pos2D,
-    d: Pos2D,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rect<S: Space = World> {
+    a: Pos2D<S>,
+    b: Pos2D<S>,
+    c: Pos2D<S>,
+    d: Pos2D<S>,
     length: f32,
     height: f32
 }
 
-impl Rect {
+impl<S: Space> Rect<S> {
     // pub fn new(a: Pos2D, b: Pos2D, c: Pos2D, d: Pos2D) -> Rect {
     //     Rect { a, b, c, d }
     // }
     /// create rect based on initial point(top left), length, & height *without* rotation
     /// we use height to reduce confusion on which is x and y
-    pub fn new(point: Pos2D, length: f32, height: f32) -> Rect {
+    pub fn new(point: Pos2D<S>, length: f32, height: f32) -> Rect<S> {
         Rect { 
             a: point, 
             b: Pos2D::new(point.x() + length, point.y()), 
@@ -250,94 +348,2152 @@ impl Rect {
         }
     }
 
-    pub fn a(&self) -> Pos2D {
+    /// same as new but turned theta radians counter-clockwise about pivot
+    pub fn new_rotated(point: Pos2D<S>, length: f32, height: f32, theta: f32, pivot: Pos2D<S>) -> Rect<S> {
+        let mut rect = Rect::new(point, length, height);
+        rect.rotate_about(pivot, theta);
+        rect
+    }
+
+    pub fn a(&self) -> Pos2D<S> {
         self.a
     }
 
-    pub fn b(&self) -> Pos2D {
+    pub fn b(&self) -> Pos2D<S> {
         self.b
     }
 
-    pub fn c(&self) -> Pos2D {
+    pub fn c(&self) -> Pos2D<S> {
         self.c
     }
 
-    pub fn d(&self) -> Pos2D {
+    pub fn d(&self) -> Pos2D<S> {
         self.d
     }
 
-    // TRAIT ACCESS FUNCTIONS
-    pub fn inside_eh(&self, p: &Pos2D) -> bool {
-        <Self as Shape>::inside_eh(self, p)
+    pub fn length(&self) -> f32 {
+        self.length
     }
 
-    pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
-        true
+    pub fn height(&self) -> f32 {
+        self.height
     }
 
-    pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
-        <Self as Shape>::draw(self, framebuffer, color)
+    pub fn center(&self) -> Pos2D<S> {
+        Pos2D::lerp(self.a, self.c, 0.5)
     }
 
-    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
-        let width = framebuffer.width();
-        let height = framebuffer.height();
+    /// how far the a -> b edge is turned counter-clockwise from +x in -PI..PI, 0 for a rect made with new
+    pub fn angle(&self) -> f32 {
+        let ab = self.b - self.a;
+        ab.dy().atan2(ab.dx())
+    }
+
+    /// true when the edges line up with the axes so the rect fills its bounding box
+    pub fn is_axis_aligned(&self) -> bool {
+        let ab = self.b - self.a;
+        ab.dx() == 0.0 || ab.dy() == 0.0
+    }
+
+    // returns the bounding box of the rect ignoring the viewport, rotated rects get the box around all four corners
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::new(self.a, self.c).expand_to(self.b).expand_to(self.d)
+    }
+
+    /// turns the rect theta radians counter-clockwise about pivot
+    pub fn rotate_about(&mut self, pivot: Pos2D<S>, theta: f32) {
+        self.apply_transform(&Mat3::rotation_about(pivot, theta));
+    }
+
+    /// turns the rect theta radians counter-clockwise about its center
+    pub fn rotate(&mut self, theta: f32) {
+        self.rotate_about(self.center(), theta);
+    }
+
+    /// turns the rect about pivot until angle() is theta
+    pub fn set_angle_about(&mut self, pivot: Pos2D<S>, theta: f32) {
+        self.rotate_about(pivot, theta - self.angle());
+    }
+
+    /// applies an affine transform (rotation, scale, etc.) to every point of the rect
+    ///
+    /// shears and uneven scales after a rotation turn it into a parallelogram, length and height follow the new edges
+    pub fn apply_transform(&mut self, m: &Mat3) {
+        self.a = *m * self.a;
+        self.b = *m * self.b;
+        self.c = *m * self.c;
+        self.d = *m * self.d;
+        self.update_sides();
+    }
+
+    // length and height from the corners after they've been moved
+    fn update_sides(&mut self) {
+        self.length = (self.b - self.a).length();
+        self.height = (self.d - self.a).length();
+    }
+
+    /// first edge the ray crosses, a ray starting inside hits the edge it leaves through
+    ///
+    /// the hit normal always faces out of the rect
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        let center = self.center();
+        [(self.a, self.b), (self.b, self.c), (self.c, self.d), (self.d, self.a)]
+            .iter()
+            .filter_map(|&(p, q)| {
+                let hit = ray.intersect_segment(p, q)?;
+                let mut normal = (q - p).perp().normalize();
+                if normal.dot(p - center) < 0.0 {
+                    normal = -normal;
+                }
+                Some(RayHit2D::new(hit.distance(), hit.point(), normal))
+            })
+            .fold(None, |closest, hit| RayHit2D::closest(closest, Some(hit)))
+    }
 
-        if !self.render_guard(cam, width as f32, height as f32) {
+    /// the corners in the order transform takes them
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        vec![self.a, self.b, self.c, self.d]
+    }
+
+    /// the four edges as a closed polyline, for drawing the rect unfilled
+    pub fn outline(&self) -> Polyline<S> {
+        Polyline::new_closed(self.points())
+    }
+
+    // the two halves either side of the a -> c diagonal with their corners' uvs, textured rects are drawn as these
+    fn halves(&self, uvs: [Vec2D; 4]) -> [(Triangle<S>, [Vec2D; 3]); 2] {
+        [
+            (Triangle::new(self.a, self.b, self.c), [uvs[0], uvs[1], uvs[2]]),
+            (Triangle::new(self.a, self.c, self.d), [uvs[0], uvs[2], uvs[3]]),
+        ]
+    }
+}
+
+impl Rect<Normalized> {
+    /// Draw the rect on the screen on top of all previous pixels relative to the height/width of the fb
+    pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
+        let width = framebuffer.width() as f32;
+        let height = framebuffer.height() as f32;
+
+        // same as triangles, only look at the pixels under the part of the bounding box that's on screen
+        let Some(bounds) = self.bounds().clamp_to(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)) else {
             return;
+        };
+        let (xs, ys) = pixel_bounds(&bounds.to_screen(width, height), framebuffer);
+        for x in xs {
+            for y in ys.clone() {
+                // sample the middle of the pixel so edges on whole pixels don't depend on which edge is half open
+                let p = Pos2D::<Screen>::new(x as f32 + 0.5, y as f32 + 0.5).to_normalized(width, height);
+                if self.inside_eh(&p) {
+                    framebuffer.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// paints the texture across the rect, uvs go with a, b, c and d. Texture::uvs for the whole image or
+    /// Texture::region for one frame of a sprite sheet. the texture's alpha blends over what's there
+    pub fn draw_textured(&self, framebuffer: &mut Framebuffer, texture: &Texture, uvs: [Vec2D; 4]) {
+        for (half, uvs) in self.halves(uvs) {
+            half.draw_textured(framebuffer, texture, uvs);
         }
+    }
+}
 
-        let (mut bl, mut tr) = cam.viewport(width as f32, height as f32);
+impl Rect<World> {
+    // if true we can draw the rect
+    pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
+        self.bounds().overlaps(&cam.viewport(width, height))
+    }
+
+    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+        let width = framebuffer.width() as f32;
+        let height = framebuffer.height() as f32;
 
-        let offset = Pos2D::new(bl.x(), bl.y());
-        // in a rect, d is min and b is max so we'll adjust bl and tr to be the max of d/br and min of b/tr
-        bl = bl.max(self.d);
-        tr = tr.min(self.b);
+        // the part of the rect's bounding box inside the viewport
+        let Some(bounds) = self.bounds().clamp_to(&cam.viewport(width, height)) else {
+            return;
+        };
 
-        // we can draw now
-        for x in (bl.x() as usize)..(tr.x() as usize) {
-            for y in (bl.y() as usize)..(tr.y() as usize) {
-                framebuffer.set_pixel(x + offset.x() as usize, y + offset.y() as usize, color)
+        // we can draw now, a rotated rect doesn't fill its box so every pixel gets checked
+        let (xs, ys) = pixel_bounds(&cam.aabb_to_screen(&bounds, width, height), framebuffer);
+        for x in xs {
+            for y in ys.clone() {
+                let p = cam.screen_to_world(Pos2D::new(x as f32 + 0.5, y as f32 + 0.5), width, height);
+                if self.inside_eh(&p) {
+                    framebuffer.set_pixel(x, y, color)
+                }
             }
         }
     }
+
+    /// draw_textured through the camera, for sprites
+    pub fn draw_textured_abs(&self, framebuffer: &mut Framebuffer, texture: &Texture, uvs: [Vec2D; 4], cam: &Camera2D) {
+        for (half, uvs) in self.halves(uvs) {
+            half.draw_textured_abs(framebuffer, texture, uvs, cam);
+        }
+    }
 }
 
-impl Shape for Rect {
-    type ReturnType = Rect;
-    
-    fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
-        let width = framebuffer.width();
-        let height = framebuffer.height();
+impl<S: Space> Shape for Rect<S> {
+    type ReturnType = Rect<S>;
+    type Coords = S;
 
-        // correct the min and max
+    // works with any winding and rotation
+    // projects the point onto the two edges leaving a, half open so rects sharing an edge don't both claim it
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        let ab = self.b - self.a;
+        let ad = self.d - self.a;
+        let ap = *p - self.a;
+        (0.0..ab.length_squared()).contains(&ap.dot(ab)) && (0.0..ad.length_squared()).contains(&ap.dot(ad))
     }
 
-    fn inside_eh(&self, p: &Pos2D) -> bool {
-        true
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.a.translate(tx, ty);
+        self.b.translate(tx, ty);
+        self.c.translate(tx, ty);
+        self.d.translate(tx, ty);
     }
 
-    fn translate(&mut self, tx: f32, ty: f32) {
-        
+    // points go a, b, c, d like the corners and should still make a rectangle
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        self.a = *points.first().expect("missing point");
+        self.b = *points.get(1).expect("missing point");
+        self.c = *points.get(2).expect("missing point");
+        self.d = *points.get(3).expect("missing point");
+        self.update_sides();
     }
 
-    fn lerp_translate(&mut self, tx: f32, ty: f32, t: f32) {
-        
+    shape_lerps!();
+}
+
+/// ## Circle
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Circle<S: Space = World> {
+    center: Pos2D<S>,
+    radius: f32,
+}
+
+/// ## Ellipse
+/// axis aligned, radius_x across and radius_y up and down
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ellipse<S: Space = World> {
+    center: Pos2D<S>,
+    radius_x: f32,
+    radius_y: f32,
+}
+
+/// ## Arc
+/// the part of a circle from start sweeping sweep radians, counter-clockwise for positive sweeps
+///
+/// filled it's the circular segment between the curve and its chord, outlined it's only the curve
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Arc<S: Space = World> {
+    center: Pos2D<S>,
+    radius: f32,
+    start: f32,
+    sweep: f32,
+}
+
+/// ## Pie
+/// a slice of a circle from start sweeping sweep radians, counter-clockwise for positive sweeps
+///
+/// filled it's the whole sector, outlined it's the curve and the two straight sides back to the center
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Pie<S: Space = World> {
+    center: Pos2D<S>,
+    radius: f32,
+    start: f32,
+    sweep: f32,
+}
+
+impl<S: Space> Circle<S> {
+    pub fn new(center: Pos2D<S>, radius: f32) -> Circle<S> {
+        Circle { center, radius }
     }
 
-    fn lerp_steps_translate(&mut self, tx: f32, ty: f32, t: u32) {
-        
+    pub fn center(&self) -> Pos2D<S> {
+        self.center
     }
 
-    fn transform(&mut self, points: Vec<Pos2D>) {
-        
+    pub fn radius(&self) -> f32 {
+        self.radius
     }
 
-    fn lerp_transform(&mut self, t: f32, points: Vec<Pos2D>) {
-        
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
     }
 
-    fn lerp_steps_transform(&mut self, t: f32, points: Vec<Pos2D>) {
-        
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::from_center(self.center, Vec2D::new(self.radius, self.radius))
+    }
+
+    /// the center and a point on the rim, what transform takes
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        vec![self.center, self.center + Vec2D::new(self.radius, 0.0)]
+    }
+
+    /// first hit on the rim, a ray starting inside hits the rim on its way out
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        ray.intersect_circle(self.center, self.radius)
+    }
+}
+
+impl<S: Space> Ellipse<S> {
+    pub fn new(center: Pos2D<S>, radius_x: f32, radius_y: f32) -> Ellipse<S> {
+        Ellipse { center, radius_x, radius_y }
     }
-}
\ No newline at end of file
+
+    pub fn center(&self) -> Pos2D<S> {
+        self.center
+    }
+
+    pub fn radius_x(&self) -> f32 {
+        self.radius_x
+    }
+
+    pub fn radius_y(&self) -> f32 {
+        self.radius_y
+    }
+
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::from_center(self.center, Vec2D::new(self.radius_x, self.radius_y))
+    }
+
+    /// the center and a corner of the bounding box, what transform takes
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        vec![self.center, self.center + Vec2D::new(self.radius_x, self.radius_y)]
+    }
+}
+
+// Arc and Pie are the same circle slice filled differently so they share everything but inside_eh and drawing
+macro_rules! impl_circle_slice {
+    ($($shape:ident),*) => {$(
+        impl<S: Space> $shape<S> {
+            /// angles in radians from +x, a sweep of 2 * PI or more is the whole circle
+            pub fn new(center: Pos2D<S>, radius: f32, start: f32, sweep: f32) -> $shape<S> {
+                $shape { center, radius, start, sweep }
+            }
+
+            pub fn center(&self) -> Pos2D<S> {
+                self.center
+            }
+
+            pub fn radius(&self) -> f32 {
+                self.radius
+            }
+
+            pub fn start(&self) -> f32 {
+                self.start
+            }
+
+            pub fn sweep(&self) -> f32 {
+                self.sweep
+            }
+
+            /// the angle the sweep stops at
+            pub fn end(&self) -> f32 {
+                self.start + self.sweep
+            }
+
+            pub fn start_point(&self) -> Pos2D<S> {
+                self.center + Vec2D::from_angle(Radians::new(self.start)) * self.radius
+            }
+
+            pub fn end_point(&self) -> Pos2D<S> {
+                self.center + Vec2D::from_angle(Radians::new(self.end())) * self.radius
+            }
+
+            /// true if the direction theta from the center is inside the sweep
+            pub fn contains_angle(&self, theta: f32) -> bool {
+                in_sweep(self.start, self.sweep, theta)
+            }
+
+            /// the center, start point and end point, what transform takes
+            pub fn points(&self) -> Vec<Pos2D<S>> {
+                vec![self.center, self.start_point(), self.end_point()]
+            }
+
+            // the direction from the center to p is in the sweep
+            fn faces(&self, p: &Pos2D<S>) -> bool {
+                self.contains_angle((*p - self.center).angle().value())
+            }
+
+            // the ends and whichever of the four axis extremes the sweep passes through
+            fn rim_points(&self) -> Vec<Pos2D<S>> {
+                let mut points = vec![self.start_point(), self.end_point()];
+                let r = self.radius;
+                for (theta, offset) in [(0.0, Vec2D::new(r, 0.0)), (FRAC_PI_2, Vec2D::new(0.0, r)), (PI, Vec2D::new(-r, 0.0)), (-FRAC_PI_2, Vec2D::new(0.0, -r))] {
+                    if self.contains_angle(theta) {
+                        points.push(self.center + offset);
+                    }
+                }
+                points
+            }
+        }
+
+        impl<S: Space> Shape for $shape<S> {
+            type ReturnType = $shape<S>;
+            type Coords = S;
+
+            fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+                (*p - self.center).length_squared() <= self.radius * self.radius && self.fill_keep(p)
+            }
+
+            fn translate(&mut self, tx: f32, ty: f32) {
+                self.center.translate(tx, ty);
+            }
+
+            // center, start point and end point. the sweep keeps its direction and the radius comes from the start point.
+            // a whole circle has its start and end on the same spot so it keeps its sweep
+            fn transform(&mut self, points: Vec<Pos2D<S>>) {
+                let center = *points.first().expect("missing point");
+                let start = *points.get(1).expect("missing point") - center;
+                let end = *points.get(2).expect("missing point") - center;
+                let turn = Radians::new(end.angle().value() - start.angle().value());
+                self.center = center;
+                self.radius = start.length();
+                self.start = start.angle().value();
+                self.sweep = if self.sweep.abs() >= TAU {
+                    self.sweep
+                } else if self.sweep < 0.0 {
+                    -(-turn).wrap_positive().value()
+                } else {
+                    turn.wrap_positive().value()
+                };
+            }
+
+            shape_lerps!();
+        }
+    )*};
+}
+
+impl_circle_slice!(Arc, Pie);
+
+impl<S: Space> Arc<S> {
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::from_points(&self.rim_points()).expect("always has its end points")
+    }
+}
+
+impl<S: Space> Pie<S> {
+    pub fn bounds(&self) -> Aabb2D<S> {
+        let mut points = self.rim_points();
+        points.push(self.center);
+        Aabb2D::from_points(&points).expect("always has its end points")
+    }
+}
+
+impl<S: Space> Shape for Circle<S> {
+    type ReturnType = Circle<S>;
+    type Coords = S;
+
+    // the rim counts as inside
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        (*p - self.center).length_squared() <= self.radius * self.radius
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.center.translate(tx, ty);
+    }
+
+    // center then any point on the rim
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        self.center = *points.first().expect("missing point");
+        self.radius = (*points.get(1).expect("missing point") - self.center).length();
+    }
+
+    shape_lerps!();
+}
+
+impl<S: Space> Shape for Ellipse<S> {
+    type ReturnType = Ellipse<S>;
+    type Coords = S;
+
+    // (x / rx)^2 + (y / ry)^2 <= 1, a flat ellipse has nothing inside it
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        if self.radius_x <= 0.0 || self.radius_y <= 0.0 {
+            return false;
+        }
+        let offset = (*p - self.center).div_elem(Vec2D::new(self.radius_x, self.radius_y));
+        offset.length_squared() <= 1.0
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.center.translate(tx, ty);
+    }
+
+    // center then a corner of the bounding box
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        self.center = *points.first().expect("missing point");
+        let corner = (*points.get(1).expect("missing point") - self.center).abs();
+        self.radius_x = corner.dx();
+        self.radius_y = corner.dy();
+    }
+
+    shape_lerps!();
+}
+
+impl<S: Space> RoundShape for Circle<S> {
+    fn center(&self) -> Pos2D<S> {
+        self.center
+    }
+
+    fn radii(&self) -> Vec2D {
+        Vec2D::new(self.radius, self.radius)
+    }
+}
+
+impl<S: Space> RoundShape for Ellipse<S> {
+    fn center(&self) -> Pos2D<S> {
+        self.center
+    }
+
+    fn radii(&self) -> Vec2D {
+        Vec2D::new(self.radius_x, self.radius_y)
+    }
+}
+
+impl<S: Space> RoundShape for Arc<S> {
+    fn center(&self) -> Pos2D<S> {
+        self.center
+    }
+
+    fn radii(&self) -> Vec2D {
+        Vec2D::new(self.radius, self.radius)
+    }
+
+    // on the same side of the chord as the curve
+    fn fill_keep(&self, p: &Pos2D<S>) -> bool {
+        if self.sweep.abs() >= TAU {
+            return true;
+        }
+        if self.sweep == 0.0 {
+            return false;
+        }
+        let (start, end) = (self.start_point(), self.end_point());
+        let middle = self.center + Vec2D::from_angle(Radians::new(self.start + self.sweep / 2.0)) * self.radius;
+        let chord = end - start;
+        let side = chord.perp_dot(*p - start);
+        side == 0.0 || side.signum() == chord.perp_dot(middle - start).signum()
+    }
+
+    fn outline_keep(&self, p: &Pos2D<S>) -> bool {
+        self.faces(p)
+    }
+}
+
+impl<S: Space> RoundShape for Pie<S> {
+    fn center(&self) -> Pos2D<S> {
+        self.center
+    }
+
+    fn radii(&self) -> Vec2D {
+        Vec2D::new(self.radius, self.radius)
+    }
+
+    fn fill_keep(&self, p: &Pos2D<S>) -> bool {
+        *p == self.center || self.faces(p)
+    }
+
+    fn outline_keep(&self, p: &Pos2D<S>) -> bool {
+        self.faces(p)
+    }
+
+    fn outline_edges(&self) -> Vec<Segment<S>> {
+        if self.sweep.abs() >= TAU {
+            return Vec::new();
+        }
+        vec![(self.center, self.start_point()), (self.center, self.end_point())]
+    }
+}
+
+// drawing is the same for all the round shapes, only which pixels of the ellipse they keep changes
+macro_rules! impl_round_draw {
+    ($($shape:ident),*) => {$(
+        impl $shape<Normalized> {
+            /// filled with a midpoint ellipse relative to the height/width of the fb, a circle on a wide fb comes out wide
+            pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
+                if self.bounds().overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)) {
+                    fill_round(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+                }
+            }
+
+            /// one pixel wide outline relative to the height/width of the fb
+            pub fn draw_outline(&self, framebuffer: &mut Framebuffer, color: u32) {
+                if self.bounds().overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)) {
+                    outline_round(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+                }
+            }
+        }
+
+        impl $shape<World> {
+            // if true we can draw the shape
+            pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
+                self.bounds().overlaps(&cam.viewport(width, height))
+            }
+
+            pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+                if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+                    fill_round(self, framebuffer, color, &PixelMap::camera(cam, framebuffer));
+                }
+            }
+
+            pub fn draw_outline_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+                if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+                    outline_round(self, framebuffer, color, &PixelMap::camera(cam, framebuffer));
+                }
+            }
+        }
+    )*};
+}
+
+impl_round_draw!(Circle, Ellipse, Arc, Pie);
+
+/// ## Antialias
+/// how many points of each pixel get tested for smooth edges, the pixel is blended by how many of them land inside
+///
+/// the points follow the standard MSAA patterns, spread so no two share a row or column
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Antialias {
+    /// hard edges, the same pixels as a plain draw
+    Off,
+    X2,
+    X4,
+    X8,
+}
+
+impl Antialias {
+    /// where in a pixel each sample is, 0 to 1 across and down from the top left corner
+    pub fn samples(self) -> &'static [(f32, f32)] {
+        // 16ths of a pixel out from the middle
+        const fn at(x: i8, y: i8) -> (f32, f32) {
+            (0.5 + x as f32 / 16.0, 0.5 + y as f32 / 16.0)
+        }
+        const OFF: &[(f32, f32)] = &[at(0, 0)];
+        const X2: &[(f32, f32)] = &[at(4, 4), at(-4, -4)];
+        const X4: &[(f32, f32)] = &[at(-2, -6), at(6, -2), at(-6, 2), at(2, 6)];
+        const X8: &[(f32, f32)] = &[at(1, -3), at(-1, 3), at(5, 1), at(-3, -5), at(-5, 5), at(-7, -1), at(3, 7), at(7, -7)];
+        match self {
+            Antialias::Off => OFF,
+            Antialias::X2 => X2,
+            Antialias::X4 => X4,
+            Antialias::X8 => X8,
+        }
+    }
+}
+
+// antialiased filling is the same for every shape with an inside, it only needs inside_eh and a box around it
+macro_rules! impl_coverage_draw {
+    ($($shape:ident),*) => {$(
+        impl AreaShape for $shape<Normalized> {
+            fn area_bounds(&self) -> Option<Aabb2D<Normalized>> {
+                self.bounds().into()
+            }
+        }
+
+        impl AreaShape for $shape<World> {
+            fn area_bounds(&self) -> Option<Aabb2D<World>> {
+                self.bounds().into()
+            }
+        }
+
+        impl $shape<Normalized> {
+            /// filled with smooth edges, each pixel is blended over what's there by how much of it the shape covers.
+            /// Off is the same as draw
+            ///
+            /// shapes sharing an edge each blend their half of the pixels along it so a faint seam of the background shows through
+            pub fn draw_antialiased(&self, framebuffer: &mut Framebuffer, color: u32, antialias: Antialias) {
+                if antialias == Antialias::Off {
+                    self.draw(framebuffer, color);
+                } else if self.area_bounds().is_some_and(|bounds| bounds.overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE))) {
+                    fill_coverage(self, framebuffer, color, antialias, &PixelMap::normalized(framebuffer));
+                }
+            }
+        }
+
+        impl $shape<World> {
+            /// draw_antialiased through the camera
+            pub fn draw_antialiased_abs(&self, framebuffer: &mut Framebuffer, color: u32, antialias: Antialias, cam: &Camera2D) {
+                if antialias == Antialias::Off {
+                    self.draw_abs(framebuffer, color, cam);
+                } else if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+                    fill_coverage(self, framebuffer, color, antialias, &PixelMap::camera(cam, framebuffer));
+                }
+            }
+        }
+    )*};
+}
+
+impl_coverage_draw!(Triangle, Rect, Circle, Ellipse, Arc, Pie, Polygon);
+
+/// ## LineCap
+/// how the open ends of a stroke are finished
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineCap {
+    /// stops flat at the end point
+    Butt,
+    /// a half circle around the end point
+    Round,
+    /// flat, half the width past the end point
+    Square,
+}
+
+/// ## LineJoin
+/// how a stroke turns the corner where two segments meet
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineJoin {
+    /// the outer edges carried on to a point, bevelled instead when the point is longer than the miter limit
+    Miter,
+    /// rounded off with a circle around the corner
+    Round,
+    /// the outer corners cut straight across
+    Bevel,
+}
+
+/// ## Stroke
+/// how wide a line is drawn and what its ends, corners and dashes look like, all sizes are in pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f32,
+    dashes: Vec<f32>,
+    dash_offset: f32,
+    antialias: bool,
+}
+
+impl Stroke {
+    /// butt caps, miter joins with a limit of 4, solid and antialiased
+    pub fn new(width: f32) -> Stroke {
+        Stroke {
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+            antialias: true,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width;
+    }
+
+    pub fn cap(&self) -> LineCap {
+        self.cap
+    }
+
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.cap = cap;
+    }
+
+    pub fn join(&self) -> LineJoin {
+        self.join
+    }
+
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.join = join;
+    }
+
+    pub fn miter_limit(&self) -> f32 {
+        self.miter_limit
+    }
+
+    /// longest a miter can be as a multiple of the width, 1 bevels every corner
+    pub fn set_miter_limit(&mut self, miter_limit: f32) {
+        self.miter_limit = miter_limit.max(1.0);
+    }
+
+    pub fn dashes(&self) -> &[f32] {
+        &self.dashes
+    }
+
+    pub fn dash_offset(&self) -> f32 {
+        self.dash_offset
+    }
+
+    /// alternating on and off lengths starting with on, an odd pattern repeats twice so on and off swap the second time
+    ///
+    /// offset is how far into the pattern the line starts. an empty pattern, one with a negative length or one adding up to
+    /// less than a pixel is solid
+    pub fn set_dashes(&mut self, pattern: &[f32], offset: f32) {
+        self.dashes = pattern.to_vec();
+        self.dash_offset = offset;
+    }
+
+    pub fn antialias(&self) -> bool {
+        self.antialias
+    }
+
+    pub fn set_antialias(&mut self, antialias: bool) {
+        self.antialias = antialias;
+    }
+
+    // the pattern with an even length, None when it's solid
+    fn dash_pattern(&self) -> Option<Vec<f32>> {
+        let valid = self.dashes.iter().all(|d| d.is_finite() && *d >= 0.0);
+        if !valid || self.dashes.iter().sum::<f32>() < 1.0 {
+            return None;
+        }
+        let mut pattern = self.dashes.clone();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&self.dashes);
+        }
+        Some(pattern)
+    }
+}
+
+/// ## Line
+/// a straight line from a to b
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Line<S: Space = World> {
+    a: Pos2D<S>,
+    b: Pos2D<S>,
+}
+
+/// ## Polyline
+/// lines joined end to end through a list of points, a closed polyline has one more line back to the first point
+///
+/// closed polylines are how outlines of the straight edged shapes get drawn, see Triangle::outline and Rect::outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline<S: Space = World> {
+    points: Vec<Pos2D<S>>,
+    closed: bool,
+}
+
+impl<S: Space> Line<S> {
+    pub fn new(a: Pos2D<S>, b: Pos2D<S>) -> Line<S> {
+        Line { a, b }
+    }
+
+    pub fn a(&self) -> Pos2D<S> {
+        self.a
+    }
+
+    pub fn b(&self) -> Pos2D<S> {
+        self.b
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.b - self.a).length()
+    }
+
+    pub fn bounds(&self) -> Aabb2D<S> {
+        Aabb2D::new(self.a, self.b)
+    }
+
+    /// a and b, what transform takes
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        vec![self.a, self.b]
+    }
+
+    pub fn closest_point(&self, p: &Pos2D<S>) -> Pos2D<S> {
+        geometry::closest_point_on_segment(*p, self.a, self.b)
+    }
+
+    /// how far p is from the nearest point on the line
+    pub fn distance(&self, p: &Pos2D<S>) -> f32 {
+        (*p - self.closest_point(p)).length()
+    }
+
+    /// where the ray crosses the line, the normal faces back towards the ray's origin
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        ray.intersect_segment(self.a, self.b)
+    }
+}
+
+impl<S: Space> Polyline<S> {
+    pub fn new(points: Vec<Pos2D<S>>) -> Polyline<S> {
+        Polyline { points, closed: false }
+    }
+
+    /// with the extra line from the last point back to the first
+    pub fn new_closed(points: Vec<Pos2D<S>>) -> Polyline<S> {
+        Polyline { points, closed: true }
+    }
+
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        self.points.clone()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    pub fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+    }
+
+    /// adds a point to the end
+    pub fn push(&mut self, p: Pos2D<S>) {
+        self.points.push(p);
+    }
+
+    /// every line in order, including the one back to the start when closed
+    pub fn lines(&self) -> Vec<Line<S>> {
+        path_segments(&self.points, self.closed).into_iter().map(|(a, b)| Line::new(a, b)).collect()
+    }
+
+    /// total length of all the lines
+    pub fn length(&self) -> f32 {
+        self.lines().iter().map(Line::length).sum()
+    }
+
+    /// None without any points
+    pub fn bounds(&self) -> Option<Aabb2D<S>> {
+        Aabb2D::from_points(&self.points)
+    }
+
+    /// the nearest point on any of the lines, None without any points
+    pub fn closest_point(&self, p: &Pos2D<S>) -> Option<Pos2D<S>> {
+        if self.points.len() == 1 {
+            return Some(self.points[0]);
+        }
+        self.lines()
+            .iter()
+            .map(|line| line.closest_point(p))
+            .min_by(|a, b| (*a - *p).length_squared().total_cmp(&(*b - *p).length_squared()))
+    }
+
+    /// how far p is from the polyline, infinite without any points
+    pub fn distance(&self, p: &Pos2D<S>) -> f32 {
+        self.closest_point(p).map_or(f32::INFINITY, |closest| (*p - closest).length())
+    }
+
+    /// the first line the ray crosses
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        self.lines().iter().fold(None, |closest, line| RayHit2D::closest(closest, line.intersect_ray(ray)))
+    }
+}
+
+impl<S: Space> Shape for Line<S> {
+    type ReturnType = Line<S>;
+    type Coords = S;
+
+    // a line has no inside, this is whether p is on it give or take rounding
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        self.closest_point(p).approx_eq(p)
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.a.translate(tx, ty);
+        self.b.translate(tx, ty);
+    }
+
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        self.a = *points.first().expect("missing point");
+        self.b = *points.get(1).expect("missing point");
+    }
+
+    shape_lerps!();
+}
+
+impl<S: Space> Shape for Polyline<S> {
+    type ReturnType = Polyline<S>;
+    type Coords = S;
+
+    // same as Line, on one of the lines give or take rounding. closed or not the area it goes around isn't inside
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        self.closest_point(p).is_some_and(|closest| closest.approx_eq(p))
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        for p in &mut self.points {
+            p.translate(tx, ty);
+        }
+    }
+
+    // the new points replace the old ones, there can be more or fewer of them
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        self.points = points;
+    }
+
+    shape_lerps!();
+}
+
+// drawing is the same for lines and polylines, a line is a polyline with two points
+macro_rules! impl_path_draw {
+    ($($shape:ident),*) => {$(
+        impl $shape<Normalized> {
+            /// one pixel wide with Bresenham's algorithm relative to the height/width of the fb
+            pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
+                draw_thin(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+            }
+
+            /// one pixel wide and antialiased with Xiaolin Wu's algorithm, blended over the pixels already there
+            pub fn draw_aa(&self, framebuffer: &mut Framebuffer, color: u32) {
+                draw_wu(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+            }
+
+            /// thick with caps, joins and dashes, the stroke's sizes are in pixels
+            pub fn draw_stroke(&self, framebuffer: &mut Framebuffer, color: u32, stroke: &Stroke) {
+                draw_stroke(self, framebuffer, color, stroke, &PixelMap::normalized(framebuffer));
+            }
+        }
+
+        impl $shape<World> {
+            // if true we can draw the 1 pixel line, thick strokes reach a little further
+            pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
+                Aabb2D::from_points(&self.vertices()).is_some_and(|bounds| bounds.overlaps(&cam.viewport(width, height)))
+            }
+
+            pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+                if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+                    draw_thin(self, framebuffer, color, &PixelMap::camera(cam, framebuffer));
+                }
+            }
+
+            pub fn draw_aa_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+                if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+                    draw_wu(self, framebuffer, color, &PixelMap::camera(cam, framebuffer));
+                }
+            }
+
+            // no render_guard here, the stroke pieces get checked against the framebuffer once they're in pixels
+            pub fn draw_stroke_abs(&self, framebuffer: &mut Framebuffer, color: u32, stroke: &Stroke, cam: &Camera2D) {
+                draw_stroke(self, framebuffer, color, stroke, &PixelMap::camera(cam, framebuffer));
+            }
+        }
+    )*};
+}
+
+impl_path_draw!(Line, Polyline);
+
+/// ## Polygon
+/// a filled outline of straight edges, convex or concave, with any number of holes cut out of it
+///
+/// the fill rule decides what's inside where the outline crosses itself or holes overlap, NonZero unless it's set
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon<S: Space = World> {
+    outline: Vec<Pos2D<S>>,
+    holes: Vec<Vec<Pos2D<S>>>,
+    rule: FillRule,
+}
+
+impl<S: Space> Polygon<S> {
+    /// the edge from the last point back to the first is implied
+    pub fn new(outline: Vec<Pos2D<S>>) -> Polygon<S> {
+        Polygon { outline, holes: Vec::new(), rule: FillRule::NonZero }
+    }
+
+    /// see add_hole
+    pub fn new_with_holes(outline: Vec<Pos2D<S>>, holes: Vec<Vec<Pos2D<S>>>) -> Polygon<S> {
+        let mut polygon = Polygon::new(outline);
+        for hole in holes {
+            polygon.add_hole(hole);
+        }
+        polygon
+    }
+
+    pub fn outline(&self) -> &[Pos2D<S>] {
+        &self.outline
+    }
+
+    pub fn holes(&self) -> &[Vec<Pos2D<S>>] {
+        &self.holes
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.rule
+    }
+
+    pub fn set_fill_rule(&mut self, rule: FillRule) {
+        self.rule = rule;
+    }
+
+    /// cuts a hole, it gets turned to wind against the outline so it comes out under either fill rule
+    pub fn add_hole(&mut self, mut hole: Vec<Pos2D<S>>) {
+        let outline = geometry::winding(&self.outline);
+        if outline.is_some() && geometry::winding(&hole) == outline {
+            hole.reverse();
+        }
+        self.holes.push(hole);
+    }
+
+    /// moves one corner of the outline, panics if there's no corner at index
+    pub fn set_vertex(&mut self, index: usize, p: Pos2D<S>) {
+        self.outline[index] = p;
+    }
+
+    /// adds a corner to the outline before index, an index one past the last corner adds it at the end
+    pub fn insert_vertex(&mut self, index: usize, p: Pos2D<S>) {
+        self.outline.insert(index, p);
+    }
+
+    /// takes a corner off the outline, panics if there's no corner at index
+    pub fn remove_vertex(&mut self, index: usize) -> Pos2D<S> {
+        self.outline.remove(index)
+    }
+
+    /// None without any points
+    pub fn bounds(&self) -> Option<Aabb2D<S>> {
+        Aabb2D::from_points(&self.points())
+    }
+
+    /// the outline's area with the holes taken out, only right when nothing crosses and the holes are inside the outline
+    pub fn area(&self) -> f32 {
+        geometry::area(&self.outline) - self.holes.iter().map(|hole| geometry::area(hole)).sum::<f32>()
+    }
+
+    /// every corner, the outline first then each hole, what transform takes
+    pub fn points(&self) -> Vec<Pos2D<S>> {
+        self.rings().flatten().copied().collect()
+    }
+
+    /// the outline and the holes as closed polylines, for drawing the edges
+    pub fn outlines(&self) -> Vec<Polyline<S>> {
+        self.rings().map(|ring| Polyline::new_closed(ring.clone())).collect()
+    }
+
+    /// triangles covering the polygon, see geometry::triangulate for the shapes it can handle
+    pub fn triangulate(&self) -> Vec<Triangle<S>> {
+        geometry::triangulate(&self.outline, &self.holes)
+    }
+
+    /// the first edge the ray crosses, outline or hole
+    pub fn intersect_ray(&self, ray: &Ray2D<S>) -> Option<RayHit2D<S>> {
+        self.rings()
+            .flat_map(|ring| path_segments(ring, true))
+            .fold(None, |closest, (a, b)| RayHit2D::closest(closest, ray.intersect_segment(a, b)))
+    }
+
+    // the outline then the holes
+    fn rings(&self) -> impl Iterator<Item = &Vec<Pos2D<S>>> {
+        std::iter::once(&self.outline).chain(&self.holes)
+    }
+}
+
+impl Polygon<Normalized> {
+    /// scanline filled relative to the height/width of the fb
+    pub fn draw(&self, framebuffer: &mut Framebuffer, color: u32) {
+        fill_polygon(self, framebuffer, color, &PixelMap::normalized(framebuffer));
+    }
+}
+
+impl Polygon<World> {
+    // if true we can draw the polygon
+    pub fn render_guard(&self, cam: &Camera2D, width: f32, height: f32) -> bool {
+        self.bounds().is_some_and(|bounds| bounds.overlaps(&cam.viewport(width, height)))
+    }
+
+    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, color: u32, cam: &Camera2D) {
+        if self.render_guard(cam, framebuffer.width() as f32, framebuffer.height() as f32) {
+            fill_polygon(self, framebuffer, color, &PixelMap::camera(cam, framebuffer));
+        }
+    }
+}
+
+impl<S: Space> Shape for Polygon<S> {
+    type ReturnType = Polygon<S>;
+    type Coords = S;
+
+    // exact for any outline, the outline and holes each wind around p and the fill rule decides on the total
+    fn inside_eh(&self, p: &Pos2D<S>) -> bool {
+        self.rule.inside(self.rings().map(|ring| geometry::winding_number(*p, ring)).sum())
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        for p in self.outline.iter_mut().chain(self.holes.iter_mut().flatten()) {
+            p.translate(tx, ty);
+        }
+    }
+
+    // as many points as points() gives moves every corner of the outline and holes in place,
+    // any other number is a new outline and the holes go
+    fn transform(&mut self, points: Vec<Pos2D<S>>) {
+        if points.len() != self.points().len() {
+            self.outline = points;
+            self.holes.clear();
+            return;
+        }
+        for (p, to) in self.outline.iter_mut().chain(self.holes.iter_mut().flatten()).zip(points) {
+            *p = to;
+        }
+    }
+
+    shape_lerps!();
+}
+shape_access!(Rect, Circle, Ellipse, Arc, Pie, Line, Polyline, Polygon);
+
+// shapes compare vertex by vertex in order, the same points in a different order aren't equal
+impl_approx_eq_by_parts!([S: Space] Triangle<S>, f32, |s| [s.a, s.b, s.c]);
+impl_approx_eq_by_parts!([S: Space] Rect<S>, f32, |s| [s.a, s.b, s.c, s.d]);
+impl_approx_eq_by_parts!([S: Space] Circle<S>, f32, |s| (s.center, s.radius));
+impl_approx_eq_by_parts!([S: Space] Ellipse<S>, f32, |s| (s.center, [s.radius_x, s.radius_y]));
+impl_approx_eq_by_parts!([S: Space] Arc<S>, f32, |s| (s.center, [s.radius, s.start, s.sweep]));
+impl_approx_eq_by_parts!([S: Space] Pie<S>, f32, |s| (s.center, [s.radius, s.start, s.sweep]));
+impl_approx_eq_by_parts!([S: Space] Line<S>, f32, |s| [s.a, s.b]);
+impl_approx_eq_by_parts!([S: Space] Polygon<S>, f32, |s| s.points());
+
+/// pixel ranges covered by a box in screen space, clipped to the framebuffer
+///
+/// the max side rounds up so every pixel the box touches is in range, whether its corner or its middle gets sampled
+fn pixel_bounds(bounds: &Aabb2D<Screen>, framebuffer: &Framebuffer) -> (Range<usize>, Range<usize>) {
+    let (min, max) = (bounds.min().max(Pos2D::ZERO), bounds.max().max(Pos2D::ZERO).ceil());
+    (
+        (min.x() as usize)..(max.x() as usize).min(framebuffer.width()),
+        (min.y() as usize)..(max.y() as usize).min(framebuffer.height()),
+    )
+}
+
+// a straight line between two points
+type Segment<S> = (Pos2D<S>, Pos2D<S>);
+
+// what the round shapes share for drawing
+trait RoundShape: Shape {
+    fn center(&self) -> Pos2D<Self::Coords>;
+    fn radii(&self) -> Vec2D;
+
+    // which pixels of the filled ellipse to keep, p is the middle of the pixel in the shape's space
+    fn fill_keep(&self, _p: &Pos2D<Self::Coords>) -> bool {
+        true
+    }
+
+    // same for the outline
+    fn outline_keep(&self, _p: &Pos2D<Self::Coords>) -> bool {
+        true
+    }
+
+    // straight lines drawn with the outline
+    fn outline_edges(&self) -> Vec<Segment<Self::Coords>> {
+        Vec::new()
+    }
+}
+
+// how a space lands on the framebuffer
+struct PixelMap<'a, S: Space> {
+    to_screen: Box<dyn Fn(Pos2D<S>) -> Pos2D<Screen> + 'a>,
+    from_screen: Box<dyn Fn(Pos2D<Screen>) -> Pos2D<S> + 'a>,
+    // pixels per unit on each axis
+    scale: Vec2D,
+}
+
+impl PixelMap<'_, Normalized> {
+    fn normalized(framebuffer: &Framebuffer) -> PixelMap<'static, Normalized> {
+        let (width, height) = (framebuffer.width() as f32, framebuffer.height() as f32);
+        PixelMap {
+            to_screen: Box::new(move |p| p.to_screen(width, height)),
+            from_screen: Box::new(move |p| p.to_normalized(width, height)),
+            scale: Vec2D::new(width, height),
+        }
+    }
+}
+
+impl<'a> PixelMap<'a, World> {
+    fn camera(cam: &'a Camera2D, framebuffer: &Framebuffer) -> PixelMap<'a, World> {
+        let (width, height) = (framebuffer.width() as f32, framebuffer.height() as f32);
+        PixelMap {
+            to_screen: Box::new(move |p| cam.world_to_screen(p, width, height)),
+            from_screen: Box::new(move |p| cam.screen_to_world(p, width, height)),
+            scale: Vec2D::new(1.0, 1.0),
+        }
+    }
+}
+
+impl<S: Space> PixelMap<'_, S> {
+    fn ellipse<R: RoundShape<Coords = S>>(&self, shape: &R) -> PixelEllipse {
+        let radii = shape.radii().mul_elem(self.scale);
+        PixelEllipse::new((self.to_screen)(shape.center()), radii.dx(), radii.dy())
+    }
+
+    // the middle of pixel (x, y) in S
+    fn middle(&self, x: i64, y: i64) -> Pos2D<S> {
+        self.at(x as f32 + 0.5, y as f32 + 0.5)
+    }
+
+    // any point on screen in S
+    fn at(&self, x: f32, y: f32) -> Pos2D<S> {
+        (self.from_screen)(Pos2D::new(x, y))
+    }
+
+    fn screen(&self, p: Pos2D<S>) -> Pos2D<Screen> {
+        (self.to_screen)(p)
+    }
+
+    fn pixel(&self, p: Pos2D<S>) -> (i64, i64) {
+        let p = self.screen(p);
+        (p.x().floor() as i64, p.y().floor() as i64)
+    }
+}
+
+// shapes with an inside to fill, bounds is an Option for the ones that can be empty
+trait AreaShape: Shape {
+    fn area_bounds(&self) -> Option<Aabb2D<Self::Coords>>;
+}
+
+// blends every pixel under the shape's box by the share of antialias's samples inside it
+fn fill_coverage<A: AreaShape>(shape: &A, framebuffer: &mut Framebuffer, color: u32, antialias: Antialias, map: &PixelMap<A::Coords>) {
+    let Some(bounds) = shape.area_bounds() else {
+        return;
+    };
+    let Some(screen) = Aabb2D::from_points(&bounds.corners().map(|p| map.screen(p))) else {
+        return;
+    };
+    let samples = antialias.samples();
+    let (xs, ys) = pixel_bounds(&screen, framebuffer);
+    for y in ys {
+        for x in xs.clone() {
+            let inside = samples.iter().filter(|&&(dx, dy)| shape.inside_eh(&map.at(x as f32 + dx, y as f32 + dy))).count();
+            blend(framebuffer, x as i64, y as i64, color, inside as f32 / samples.len() as f32);
+        }
+    }
+}
+
+fn fill_round<R: RoundShape>(shape: &R, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<R::Coords>) {
+    map.ellipse(shape).fill(framebuffer, |x, y| shape.fill_keep(&map.middle(x, y)), color);
+}
+
+fn outline_round<R: RoundShape>(shape: &R, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<R::Coords>) {
+    map.ellipse(shape).outline(framebuffer, |x, y| shape.outline_keep(&map.middle(x, y)), color);
+    for (from, to) in shape.outline_edges() {
+        let ((x0, y0), (x1, y1)) = (map.pixel(from), map.pixel(to));
+        bresenham(x0, y0, x1, y1, |x, y| plot(framebuffer, x, y, color));
+    }
+}
+
+// radii in pixels past which round shapes stop walking the midpoint rim
+const MIDPOINT_LIMIT: i64 = 1 << 16;
+
+// a round shape in framebuffer pixels, the center is a whole pixel and the radii are whole numbers of pixels
+#[derive(Debug, Copy, Clone)]
+struct PixelEllipse {
+    cx: i64,
+    cy: i64,
+    rx: i64,
+    ry: i64,
+}
+
+impl PixelEllipse {
+    fn new(center: Pos2D<Screen>, rx: f32, ry: f32) -> PixelEllipse {
+        PixelEllipse {
+            cx: center.x().floor() as i64,
+            cy: center.y().floor() as i64,
+            rx: rx.abs().round() as i64,
+            ry: ry.abs().round() as i64,
+        }
+    }
+
+    // midpoint ellipse, the rim pixels with x and y >= 0 as offsets from the center
+    //
+    // decision values are scaled by 4 so the half pixel midpoints stay whole numbers. they grow with the radius to the
+    // fourth power, past i64 from about 46k pixels, so they're worked out in i128
+    fn quadrant(&self) -> Vec<(i64, i64)> {
+        if self.ry == 0 {
+            return (0..=self.rx).map(|x| (x, 0)).collect();
+        }
+        let (rx, ry) = (self.rx as i128, self.ry as i128);
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let mut points = Vec::new();
+        let (mut x, mut y) = (0, ry);
+        let (mut px, mut py) = (0, 2 * rx2 * y);
+
+        // region 1, flatter than 45 degrees so x steps every time
+        let mut p = 4 * ry2 - 4 * rx2 * ry + rx2;
+        while px < py {
+            points.push((x as i64, y as i64));
+            x += 1;
+            px += 2 * ry2;
+            if p < 0 {
+                p += 4 * (ry2 + px);
+            } else {
+                y -= 1;
+                py -= 2 * rx2;
+                p += 4 * (ry2 + px - py);
+            }
+        }
+
+        // region 2, steeper so y steps every time
+        let mut p = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+        while y >= 0 {
+            points.push((x as i64, y as i64));
+            y -= 1;
+            py -= 2 * rx2;
+            if p > 0 {
+                p += 4 * (rx2 - py);
+            } else {
+                x += 1;
+                px += 2 * ry2;
+                p += 4 * (rx2 - py + px);
+            }
+        }
+        points
+    }
+
+    // how far the span in row dy reaches either side of the center, dy is at most ry.
+    // small ellipses take it from the midpoint rim so outlines and fills line up, past MIDPOINT_LIMIT walking the
+    // whole rim takes too long for the few rows that fit on a framebuffer so it comes from the ellipse equation
+    fn half_widths(&self) -> impl Fn(i64) -> i64 {
+        let table = (self.rx <= MIDPOINT_LIMIT && self.ry <= MIDPOINT_LIMIT).then(|| {
+            let mut half_widths = vec![0; self.ry as usize + 1];
+            for (x, y) in self.quadrant() {
+                half_widths[y as usize] = half_widths[y as usize].max(x);
+            }
+            half_widths
+        });
+        let (rx, ry) = (self.rx as f64, self.ry as f64);
+        move |dy| match &table {
+            Some(half_widths) => half_widths[dy as usize],
+            None if ry == 0.0 => rx as i64,
+            None => (rx * (1.0 - (dy as f64 / ry).powi(2)).max(0.0).sqrt()).round() as i64,
+        }
+    }
+
+    // the framebuffer rows the ellipse covers and how far each is from the center row
+    fn rows(&self, framebuffer: &Framebuffer) -> impl Iterator<Item = (i64, i64)> {
+        let first = self.cy.saturating_sub(self.ry).max(0);
+        let last = self.cy.saturating_add(self.ry).min(framebuffer.height() as i64 - 1);
+        let cy = self.cy;
+        (first..=last).map(move |y| (y, (y - cy).abs()))
+    }
+
+    // screen columns cx + from ..= cx + to clipped to the framebuffer
+    fn columns(&self, framebuffer: &Framebuffer, from: i64, to: i64) -> RangeInclusive<i64> {
+        self.cx.saturating_add(from).max(0)..=self.cx.saturating_add(to).min(framebuffer.width() as i64 - 1)
+    }
+
+    // one span per row out to the rim, only the rows on the framebuffer
+    fn fill(&self, framebuffer: &mut Framebuffer, keep: impl Fn(i64, i64) -> bool, color: u32) {
+        let half_width = self.half_widths();
+        for (y, dy) in self.rows(framebuffer) {
+            let half = half_width(dy);
+            for x in self.columns(framebuffer, -half, half) {
+                if keep(x, y) {
+                    framebuffer.set_pixel(x as usize, y as usize, color);
+                }
+            }
+        }
+    }
+
+    // the rim mirrored into all four quadrants without doubling up on the axes. small ellipses plot the midpoint rim,
+    // big ones plot each row on the framebuffer from the end of the row outside it to the end of its own span
+    fn outline(&self, framebuffer: &mut Framebuffer, keep: impl Fn(i64, i64) -> bool, color: u32) {
+        if self.rx <= MIDPOINT_LIMIT && self.ry <= MIDPOINT_LIMIT {
+            for (dx, dy) in self.quadrant() {
+                for (sx, sy) in [(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+                    if (sx < 0 && dx == 0) || (sy < 0 && dy == 0) {
+                        continue;
+                    }
+                    let (x, y) = (self.cx + sx * dx, self.cy + sy * dy);
+                    if keep(x, y) {
+                        plot(framebuffer, x, y, color);
+                    }
+                }
+            }
+            return;
+        }
+        let half_width = self.half_widths();
+        for (y, dy) in self.rows(framebuffer) {
+            let half = half_width(dy);
+            let outside = if dy == self.ry { -1 } else { half_width(dy + 1) };
+            let inner = (outside + 1).clamp(0, half);
+            let right = self.columns(framebuffer, inner, half);
+            let left = self.columns(framebuffer, -half, -inner).filter(|&x| inner > 0 || x != self.cx);
+            for x in right.chain(left) {
+                if keep(x, y) {
+                    framebuffer.set_pixel(x as usize, y as usize, color);
+                }
+            }
+        }
+    }
+}
+
+/// every pixel on the line from (x0, y0) to (x1, y1), Bresenham's algorithm for any direction
+fn bresenham(x0: i64, y0: i64, x1: i64, y1: i64, mut plot: impl FnMut(i64, i64)) {
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+    let (mut x, mut y) = (x0, y0);
+    let mut err = dx + dy;
+    loop {
+        plot(x, y);
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// sets a pixel if it's on the framebuffer
+fn plot(framebuffer: &mut Framebuffer, x: i64, y: i64, color: u32) {
+    if x >= 0 && y >= 0 && (x as usize) < framebuffer.width() && (y as usize) < framebuffer.height() {
+        framebuffer.set_pixel(x as usize, y as usize, color);
+    }
+}
+
+// is theta within the sweep that starts at start, any direction and any number of turns
+fn in_sweep(start: f32, sweep: f32, theta: f32) -> bool {
+    if sweep.abs() >= TAU {
+        return true;
+    }
+    let (from, span) = if sweep < 0.0 { (start + sweep, -sweep) } else { (start, sweep) };
+    Radians::new(theta - from).wrap_positive().value() <= span
+}
+
+// what lines and polylines share for drawing
+trait PathShape: Shape {
+    fn vertices(&self) -> Vec<Pos2D<Self::Coords>>;
+    fn closed(&self) -> bool;
+}
+
+impl<S: Space> PathShape for Line<S> {
+    fn vertices(&self) -> Vec<Pos2D<S>> {
+        vec![self.a, self.b]
+    }
+
+    fn closed(&self) -> bool {
+        false
+    }
+}
+
+impl<S: Space> PathShape for Polyline<S> {
+    fn vertices(&self) -> Vec<Pos2D<S>> {
+        self.points.clone()
+    }
+
+    fn closed(&self) -> bool {
+        self.closed
+    }
+}
+
+// consecutive pairs of points, plus the last back to the first when closed
+fn path_segments<S: Space>(points: &[Pos2D<S>], closed: bool) -> Vec<Segment<S>> {
+    let mut segments: Vec<Segment<S>> = points.windows(2).map(|pair| (pair[0], pair[1])).collect();
+    if closed && points.len() > 2 {
+        segments.push((points[points.len() - 1], points[0]));
+    }
+    segments
+}
+
+// the whole framebuffer in screen space
+fn framebuffer_area(framebuffer: &Framebuffer) -> Aabb2D<Screen> {
+    Aabb2D::new(Pos2D::ZERO, Pos2D::new(framebuffer.width() as f32, framebuffer.height() as f32))
+}
+
+// how much of from -> to is inside the box as fractions of the way along, Liang-Barsky
+fn clip_segment(from: Pos2D<Screen>, to: Pos2D<Screen>, area: &Aabb2D<Screen>) -> Option<(f32, f32)> {
+    let d = to - from;
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    let (min, max) = (area.min(), area.max());
+    for (p, q) in [(-d.dx(), from.x() - min.x()), (d.dx(), max.x() - from.x()), (-d.dy(), from.y() - min.y()), (d.dy(), max.y() - from.y())] {
+        if p == 0.0 {
+            // parallel to this side, either all inside it or all outside
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    (t0 <= t1).then_some((t0, t1))
+}
+
+// the on screen part of every segment in pixels, clipped so lines running far off screen don't step through every pixel out there
+fn screen_segments<P: PathShape>(path: &P, framebuffer: &Framebuffer, map: &PixelMap<P::Coords>) -> Vec<Segment<Screen>> {
+    let area = framebuffer_area(framebuffer).expand(2.0);
+    path_segments(&path.vertices(), path.closed())
+        .into_iter()
+        .filter_map(|(from, to)| {
+            let (from, to) = (map.screen(from), map.screen(to));
+            let (t0, t1) = clip_segment(from, to, &area)?;
+            Some((from + (to - from) * t0, from + (to - from) * t1))
+        })
+        .collect()
+}
+
+fn draw_thin<P: PathShape>(path: &P, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<P::Coords>) {
+    for (from, to) in screen_segments(path, framebuffer, map) {
+        let (x0, y0, x1, y1) = (from.x().floor() as i64, from.y().floor() as i64, to.x().floor() as i64, to.y().floor() as i64);
+        bresenham(x0, y0, x1, y1, |x, y| plot(framebuffer, x, y, color));
+    }
+}
+
+fn draw_wu<P: PathShape>(path: &P, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<P::Coords>) {
+    for (from, to) in screen_segments(path, framebuffer, map) {
+        wu(from, to, |x, y, coverage| blend(framebuffer, x, y, color, coverage));
+    }
+}
+
+// Xiaolin Wu's line, each step along the long axis splits one pixel of ink between the two pixels the line passes between
+//
+// everything is moved back half a pixel so whole numbers are pixel middles like the algorithm expects
+fn wu(from: Pos2D<Screen>, to: Pos2D<Screen>, mut plot: impl FnMut(i64, i64, f32)) {
+    let (mut x0, mut y0, mut x1, mut y1) = (from.x() - 0.5, from.y() - 0.5, to.x() - 0.5, to.y() - 0.5);
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+    if steep {
+        std::mem::swap(&mut x0, &mut y0);
+        std::mem::swap(&mut x1, &mut y1);
+    }
+    if x0 > x1 {
+        std::mem::swap(&mut x0, &mut x1);
+        std::mem::swap(&mut y0, &mut y1);
+    }
+    let gradient = if x1 == x0 { 1.0 } else { (y1 - y0) / (x1 - x0) };
+    let fract = |v: f32| v - v.floor();
+    let mut put = |x: i64, y: f32, coverage: f32| {
+        let below = y.floor() as i64;
+        for (y, share) in [(below, 1.0 - fract(y)), (below + 1, fract(y))] {
+            if steep {
+                plot(y, x, share * coverage);
+            } else {
+                plot(x, y, share * coverage);
+            }
+        }
+    };
+
+    // the ends only get the part of their pixel the line actually reaches
+    let (start_x, end_x) = (x0.round(), x1.round());
+    let start_y = y0 + gradient * (start_x - x0);
+    put(start_x as i64, start_y, 1.0 - fract(x0 + 0.5));
+    put(end_x as i64, y1 + gradient * (end_x - x1), fract(x1 + 0.5));
+
+    let mut y = start_y + gradient;
+    for x in start_x as i64 + 1..end_x as i64 {
+        put(x, y, 1.0);
+        y += gradient;
+    }
+}
+
+// mixes color into the pixel by how much of it is covered, 1 paints right over it. pixels off the framebuffer are skipped
+fn blend(framebuffer: &mut Framebuffer, x: i64, y: i64, color: u32, coverage: f32) {
+    if coverage <= 0.0 || x < 0 || y < 0 || x as usize >= framebuffer.width() || y as usize >= framebuffer.height() {
+        return;
+    }
+    let (x, y) = (x as usize, y as usize);
+    if coverage >= 1.0 {
+        framebuffer.set_pixel(x, y, color);
+        return;
+    }
+    let under = Color::from_u32(framebuffer.get_pixel(x, y));
+    framebuffer.set_pixel(x, y, Color::interpolate(under, Color::from_u32(color), coverage).to_u32());
+}
+
+fn draw_stroke<P: PathShape>(path: &P, framebuffer: &mut Framebuffer, color: u32, stroke: &Stroke, map: &PixelMap<P::Coords>) {
+    let points: Vec<Pos2D<Screen>> = path.vertices().into_iter().map(|p| map.screen(p)).collect();
+    if points.is_empty() || stroke.width().is_nan() || stroke.width() <= 0.0 {
+        return;
+    }
+    // far enough past the framebuffer that nothing reaching back onto it gets dropped, miters reach the furthest
+    let reach = stroke.width() * stroke.miter_limit().max(SQRT_2) + 1.0;
+    let area = framebuffer_area(framebuffer).expand(reach);
+    let pieces = stroke_pieces(&points, path.closed(), stroke, &area);
+    fill_pieces(&pieces, framebuffer, color, stroke.antialias());
+}
+
+// a convex bit of a thick line in pixels, the stroke is everywhere at least one piece covers
+#[derive(Debug, Clone)]
+enum StrokePiece {
+    Polygon(Vec<Pos2D<Screen>>),
+    Disc(Pos2D<Screen>, f32),
+}
+
+impl StrokePiece {
+    fn bounds(&self) -> Aabb2D<Screen> {
+        match self {
+            StrokePiece::Polygon(corners) => Aabb2D::from_points(corners).expect("pieces always have corners"),
+            StrokePiece::Disc(center, radius) => Aabb2D::from_center(*center, Vec2D::new(*radius, *radius)),
+        }
+    }
+
+    // works with either winding, the edges count as inside
+    fn contains(&self, p: Pos2D<Screen>) -> bool {
+        match self {
+            StrokePiece::Polygon(corners) => {
+                let mut side = 0.0;
+                for (a, b) in path_segments(corners, true) {
+                    let turn = (b - a).perp_dot(p - a);
+                    if turn * side < 0.0 {
+                        return false;
+                    }
+                    if turn != 0.0 {
+                        side = turn;
+                    }
+                }
+                true
+            }
+            StrokePiece::Disc(center, radius) => (p - *center).length_squared() <= radius * radius,
+        }
+    }
+}
+
+// a quad for every segment of every dash, then the joins between them and the caps on their ends
+fn stroke_pieces(points: &[Pos2D<Screen>], closed: bool, stroke: &Stroke, area: &Aabb2D<Screen>) -> Vec<StrokePiece> {
+    let half = stroke.width() / 2.0;
+    let mut pieces = Vec::new();
+    for (mut run, closed) in dash_runs(points, closed, stroke, area) {
+        run.dedup();
+        if closed && run.len() > 1 && run[0] == run[run.len() - 1] {
+            run.pop();
+        }
+        // two points can't go around anything so they get caps like any open line
+        let closed = closed && run.len() > 2;
+        if run.len() == 1 {
+            // a dot, only the caps have anything to show
+            match stroke.cap() {
+                LineCap::Butt => {}
+                LineCap::Round => pieces.push(StrokePiece::Disc(run[0], half)),
+                LineCap::Square => pieces.push(StrokePiece::Polygon(Aabb2D::from_center(run[0], Vec2D::new(half, half)).corners().to_vec())),
+            }
+            continue;
+        }
+
+        let segments = path_segments(&run, closed);
+        for &(from, to) in &segments {
+            let side = (to - from).normalize().perp() * half;
+            pieces.push(StrokePiece::Polygon(vec![from + side, to + side, to - side, from - side]));
+        }
+        for pair in segments.windows(2) {
+            stroke_join(&mut pieces, pair[0], pair[1], stroke);
+        }
+        if closed {
+            stroke_join(&mut pieces, segments[segments.len() - 1], segments[0], stroke);
+        } else {
+            let last = run.len() - 1;
+            stroke_cap(&mut pieces, run[0], (run[0] - run[1]).normalize(), stroke);
+            stroke_cap(&mut pieces, run[last], (run[last] - run[last - 1]).normalize(), stroke);
+        }
+    }
+    pieces
+}
+
+// fills in the outside of the corner where into meets out
+fn stroke_join(pieces: &mut Vec<StrokePiece>, into: Segment<Screen>, out: Segment<Screen>, stroke: &Stroke) {
+    let half = stroke.width() / 2.0;
+    let corner = into.1;
+    if stroke.join() == LineJoin::Round {
+        pieces.push(StrokePiece::Disc(corner, half));
+        return;
+    }
+    let (d0, d1) = ((into.1 - into.0).normalize(), (out.1 - out.0).normalize());
+    let turn = d0.perp_dot(d1);
+    if turn == 0.0 {
+        // straight on there's no gap, and doubling straight back there's no outside to fill
+        return;
+    }
+    // the outside of the corner is the side the path turns away from
+    let outward = if turn > 0.0 { -1.0 } else { 1.0 };
+    let (n0, n1) = (d0.perp() * outward, d1.perp() * outward);
+    let (edge0, edge1) = (corner + n0 * half, corner + n1 * half);
+    if stroke.join() == LineJoin::Miter {
+        // the tip is half the width over the cosine of half the turn out along the middle of the two normals
+        let middle = (n0 + n1).normalize();
+        let cos = middle.dot(n0);
+        if cos > 0.0 && 1.0 / cos <= stroke.miter_limit() {
+            pieces.push(StrokePiece::Polygon(vec![corner, edge0, corner + middle * (half / cos), edge1]));
+            return;
+        }
+    }
+    pieces.push(StrokePiece::Polygon(vec![corner, edge0, edge1]));
+}
+
+// finishes the end of a dash at end, heading out in direction
+fn stroke_cap(pieces: &mut Vec<StrokePiece>, end: Pos2D<Screen>, direction: Vec2D, stroke: &Stroke) {
+    let half = stroke.width() / 2.0;
+    let side = direction.perp() * half;
+    match stroke.cap() {
+        LineCap::Butt => {}
+        LineCap::Round => pieces.push(StrokePiece::Disc(end, half)),
+        LineCap::Square => {
+            let past = direction * half;
+            pieces.push(StrokePiece::Polygon(vec![end + side, end + side + past, end - side + past, end - side]));
+        }
+    }
+}
+
+// the parts of the path the dash pattern draws, each with whether it goes all the way around
+//
+// a solid stroke is the whole path in one run
+fn dash_runs(points: &[Pos2D<Screen>], closed: bool, stroke: &Stroke, area: &Aabb2D<Screen>) -> Vec<(Vec<Pos2D<Screen>>, bool)> {
+    let Some(pattern) = stroke.dash_pattern() else {
+        return vec![(points.to_vec(), closed)];
+    };
+    let mut dasher = Dasher::new(pattern, stroke.dash_offset(), points[0]);
+    for (from, to) in path_segments(points, closed) {
+        let length = (to - from).length();
+        // the parts off screen move the pattern along without making dashes nobody will see
+        let (t0, t1) = clip_segment(from, to, area).unwrap_or((1.0, 1.0));
+        let along = |t: f32| from + (to - from) * t;
+        dasher.skip(length * t0, along(t0));
+        dasher.walk(along(t0), along(t1));
+        dasher.skip(length * (1.0 - t1), to);
+    }
+    dasher.finish(points[0], closed)
+}
+
+// walks along a path handing out the parts the dash pattern draws
+struct Dasher {
+    // even length, on first
+    pattern: Vec<f32>,
+    index: usize,
+    // how much of the current dash or gap is still to go
+    left: f32,
+    run: Vec<Pos2D<Screen>>,
+    runs: Vec<Vec<Pos2D<Screen>>>,
+    // the pattern turned off somewhere, a closed path that's never off is one closed run
+    broken: bool,
+}
+
+impl Dasher {
+    fn new(pattern: Vec<f32>, offset: f32, start: Pos2D<Screen>) -> Dasher {
+        let mut dasher = Dasher { pattern, index: 0, left: 0.0, run: Vec::new(), runs: Vec::new(), broken: false };
+        dasher.seek(offset);
+        if dasher.on() {
+            dasher.run.push(start);
+        }
+        dasher
+    }
+
+    fn on(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    // puts the pattern position distance past its start, any distance wraps around
+    fn seek(&mut self, distance: f32) {
+        let mut distance = distance.rem_euclid(self.pattern.iter().sum());
+        self.index = 0;
+        // the pattern adds up to more than distance so this stops within a lap or two
+        while distance >= self.pattern[self.index] {
+            distance -= self.pattern[self.index];
+            self.index = (self.index + 1) % self.pattern.len();
+        }
+        self.left = self.pattern[self.index] - distance;
+    }
+
+    // moves along the pattern distance without drawing, at is where that lands
+    fn skip(&mut self, distance: f32, at: Pos2D<Screen>) {
+        if distance <= 0.0 {
+            return;
+        }
+        if !self.run.is_empty() {
+            self.runs.push(std::mem::take(&mut self.run));
+        }
+        let done: f32 = self.pattern[..self.index].iter().sum::<f32>() + self.pattern[self.index] - self.left;
+        self.seek(done + distance);
+        if self.on() {
+            self.run.push(at);
+        }
+        self.broken = true;
+    }
+
+    fn walk(&mut self, from: Pos2D<Screen>, to: Pos2D<Screen>) {
+        let direction = (to - from).normalize();
+        let mut length = (to - from).length();
+        let mut at = from;
+        while length > self.left {
+            at += direction * self.left;
+            length -= self.left;
+            // a dash ends here or a gap does, either way the point belongs to a dash
+            self.run.push(at);
+            if self.on() {
+                self.runs.push(std::mem::take(&mut self.run));
+            }
+            self.index = (self.index + 1) % self.pattern.len();
+            self.left = self.pattern[self.index];
+            self.broken = true;
+        }
+        self.left -= length;
+        if self.on() {
+            self.run.push(to);
+        }
+    }
+
+    // a closed path whose first and last dashes meet at the start gets them joined into one
+    fn finish(mut self, start: Pos2D<Screen>, closed: bool) -> Vec<(Vec<Pos2D<Screen>>, bool)> {
+        if closed && !self.broken {
+            return if self.run.is_empty() { Vec::new() } else { vec![(self.run, true)] };
+        }
+        if !self.run.is_empty() {
+            let wraps = closed && self.run.last() == Some(&start) && self.runs.first().is_some_and(|first| first[0] == start);
+            if wraps {
+                self.run.extend_from_slice(&self.runs[0][1..]);
+                self.runs[0] = std::mem::take(&mut self.run);
+            } else {
+                self.runs.push(std::mem::take(&mut self.run));
+            }
+        }
+        self.runs.into_iter().map(|run| (run, false)).collect()
+    }
+}
+
+// samples per side of a pixel when antialiasing, 4 x 4 gives coverage in sixteenths
+const STROKE_SAMPLES: usize = 4;
+
+// paints every pixel under the pieces, antialiased pixels get painted by the share of their samples that are covered
+fn fill_pieces(pieces: &[StrokePiece], framebuffer: &mut Framebuffer, color: u32, antialias: bool) {
+    let Some(area) = pieces.iter().map(StrokePiece::bounds).reduce(|a, b| a.union(&b)) else {
+        return;
+    };
+    let (xs, ys) = pixel_bounds(&area, framebuffer);
+    if xs.is_empty() || ys.is_empty() {
+        return;
+    }
+    let samples = if antialias { STROKE_SAMPLES } else { 1 };
+    let step = 1.0 / samples as f32;
+    // a bit per sample so pieces that overlap don't paint a pixel twice
+    let mut masks = vec![0u16; xs.len() * ys.len()];
+    for piece in pieces {
+        let (pxs, pys) = pixel_bounds(&piece.bounds(), framebuffer);
+        for y in pys {
+            for x in pxs.clone() {
+                let mask = &mut masks[(y - ys.start) * xs.len() + x - xs.start];
+                for i in 0..samples * samples {
+                    let p = Pos2D::new(x as f32 + ((i % samples) as f32 + 0.5) * step, y as f32 + ((i / samples) as f32 + 0.5) * step);
+                    if *mask & (1 << i) == 0 && piece.contains(p) {
+                        *mask |= 1 << i;
+                    }
+                }
+            }
+        }
+    }
+    for (i, mask) in masks.iter().enumerate() {
+        let (x, y) = (xs.start + i % xs.len(), ys.start + i / xs.len());
+        blend(framebuffer, x as i64, y as i64, color, mask.count_ones() as f32 / (samples * samples) as f32);
+    }
+}
+
+// a polygon edge in pixels from its top to its bottom, winding is 1 if it really went down the screen and -1 if it went up
+#[derive(Debug, Copy, Clone)]
+struct ScanEdge {
+    top: f32,
+    bottom: f32,
+    x_top: f32,
+    // x moved per pixel down
+    slope: f32,
+    winding: i32,
+}
+
+impl ScanEdge {
+    // None for flat edges, no scanline ever crosses them
+    fn new(a: Pos2D<Screen>, b: Pos2D<Screen>) -> Option<ScanEdge> {
+        if a.y() == b.y() {
+            return None;
+        }
+        let (top, bottom, winding) = if a.y() < b.y() { (a, b, 1) } else { (b, a, -1) };
+        Some(ScanEdge {
+            top: top.y(),
+            bottom: bottom.y(),
+            x_top: top.x(),
+            slope: (bottom.x() - top.x()) / (bottom.y() - top.y()),
+            winding,
+        })
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        self.x_top + (y - self.top) * self.slope
+    }
+}
+
+// scanline fill, a row at a time through the middles of the pixels. the edges cutting the row are sorted left to right
+// and walked adding up their windings, the fill rule says which gaps between them get painted
+//
+// edges and spans are half open so polygons sharing an edge never both paint the pixels along it
+fn fill_polygon<S: Space>(polygon: &Polygon<S>, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<S>) {
+    let mut edges: Vec<ScanEdge> = polygon
+        .rings()
+        .filter(|ring| ring.len() >= 3)
+        .flat_map(|ring| path_segments(ring, true))
+        .filter_map(|(a, b)| ScanEdge::new(map.screen(a), map.screen(b)))
+        .collect();
+    if edges.is_empty() {
+        return;
+    }
+    edges.sort_by(|a, b| a.top.total_cmp(&b.top));
+    let bottom = edges.iter().map(|edge| edge.bottom).fold(f32::NEG_INFINITY, f32::max);
+    let width = framebuffer.width() as f32;
+    // rows with their middle between the top and bottom
+    let rows = (edges[0].top - 0.5).ceil().max(0.0) as usize..((bottom - 0.5).ceil().max(0.0) as usize).min(framebuffer.height());
+
+    let mut next = 0;
+    let mut active: Vec<ScanEdge> = Vec::new();
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for y in rows {
+        let middle = y as f32 + 0.5;
+        while next < edges.len() && edges[next].top <= middle {
+            active.push(edges[next]);
+            next += 1;
+        }
+        active.retain(|edge| edge.bottom > middle);
+
+        crossings.clear();
+        crossings.extend(active.iter().map(|edge| (edge.x_at(middle), edge.winding)));
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            if polygon.rule.inside(winding) {
+                // the pixels with their middle in left..right
+                let left = (pair[0].0 - 0.5).ceil().clamp(0.0, width) as usize;
+                let right = (pair[1].0 - 0.5).ceil().clamp(0.0, width) as usize;
+                for x in left..right {
+                    framebuffer.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+fn fill_triangle<S: Space>(triangle: &Triangle<S>, framebuffer: &mut Framebuffer, color: u32, map: &PixelMap<S>) {
+    let corners = [triangle.a, triangle.b, triangle.c].map(|p| map.screen(p));
+    let clip = (0..framebuffer.width(), 0..framebuffer.height());
+    raster_triangle(corners, clip, |x, y, _| framebuffer.set_pixel(x, y, color));
+}
+
+fn shade_triangle<S: Space, V: Varying>(
+    triangle: &Triangle<S>,
+    framebuffer: &mut Framebuffer,
+    varyings: &Varyings<V>,
+    map: &PixelMap<S>,
+    mut shader: impl FnMut(&Fragment<V>) -> Option<u32>,
+) {
+    let corners = [triangle.a, triangle.b, triangle.c].map(|p| map.screen(p));
+    let clip = (0..framebuffer.width(), 0..framebuffer.height());
+    raster_triangle(corners, clip, |x, y, weights| {
+        let fragment = varyings.fragment(x, y, weights, framebuffer.get_pixel(x, y));
+        if let Some(color) = shader(&fragment) {
+            framebuffer.set_pixel(x, y, color);
+        }
+    });
+}
+
+// sub-pixel precision of the triangle rasterizer, corners snap to 1/256 of a pixel so the edge functions are exact integers
+const SUBPIXEL_BITS: u32 = 8;
+
+// corners snap no further than this from the screen in sub-pixel units, about 4.5e15 pixels. far enough that
+// the triangle looks the same on screen, close enough that edge functions between any two snapped points fit in an i128
+const SNAP_LIMIT: i64 = 1 << 60;
+
+// a screen point on the sub-pixel grid
+fn snap(p: Pos2D<Screen>) -> (i64, i64) {
+    let scale = (1 << SUBPIXEL_BITS) as f32;
+    let snap = |v: f32| ((v * scale).round() as i64).clamp(-SNAP_LIMIT, SNAP_LIMIT);
+    (snap(p.x()), snap(p.y()))
+}
+
+// one edge of a triangle on the sub-pixel grid, wound so the inside is where the edge function is positive
+#[derive(Debug, Copy, Clone)]
+struct RasterEdge {
+    from: (i64, i64),
+    dx: i128,
+    dy: i128,
+    // 0 for top and left edges so pixels exactly on them are in, -1 for the rest so they're out
+    bias: i128,
+}
+
+impl RasterEdge {
+    fn new(from: (i64, i64), to: (i64, i64)) -> RasterEdge {
+        let (dx, dy) = (to.0 as i128 - from.0 as i128, to.1 as i128 - from.1 as i128);
+        // with y down and the inside on the positive side a top edge runs right and a left edge runs up
+        let top_left = dy < 0 || (dy == 0 && dx > 0);
+        RasterEdge { from, dx, dy, bias: if top_left { 0 } else { -1 } }
+    }
+
+    // the biased edge function at a sub-pixel point, inside is >= 0. worked out in i128 so snapped corners can't overflow it
+    fn at(&self, x: i64, y: i64) -> i128 {
+        self.dx * (y as i128 - self.from.1 as i128) - self.dy * (x as i128 - self.from.0 as i128) + self.bias
+    }
+
+    // how much at changes one whole pixel to the right
+    fn step_x(&self) -> i128 {
+        -self.dy << SUBPIXEL_BITS
+    }
+}
+
+/// every pixel in clip (columns, rows) whose middle is inside the triangle, corners in screen space in either winding
+///
+/// top-left fill rule: a pixel middle exactly on an edge only counts for top edges (flat with the inside below) and
+/// left edges. every edge shared by two triangles is a top or left edge for exactly one of them, so a mesh covers
+/// each pixel once with no gaps or double ups. corners snap to 1/256 of a pixel first so this is exact
+///
+/// fragment also gets the pixel middle's barycentric weights for the corners in the order they came in.
+/// the edge functions are exact so splitting the screen up and drawing each piece on its own gives the same pixels
+pub(crate) fn raster_triangle(
+    corners: [Pos2D<Screen>; 3],
+    clip: (Range<usize>, Range<usize>),
+    mut fragment: impl FnMut(usize, usize, [f32; 3]),
+) {
+    let [a, mut b, mut c] = corners.map(snap);
+    // twice the area, the sign says which way it winds
+    let ab = RasterEdge::new(a, b);
+    let area = ab.at(c.0, c.1) - ab.bias;
+    if area == 0 {
+        return;
+    }
+    let swapped = area < 0;
+    if swapped {
+        std::mem::swap(&mut b, &mut c);
+    }
+    let edges = [RasterEdge::new(a, b), RasterEdge::new(b, c), RasterEdge::new(c, a)];
+    // each edge's unbiased value over the area is the weight of the corner across from it
+    let area = area.abs() as f64;
+    let weights = |w: [i128; 3]| {
+        let [ab, bc, ca] = [0, 1, 2].map(|i| ((w[i] - edges[i].bias) as f64 / area) as f32);
+        if swapped { [bc, ab, ca] } else { [bc, ca, ab] }
+    };
+
+    // pixels with their middle inside the corners' box, clipped
+    let one = 1i64 << SUBPIXEL_BITS;
+    let half = one / 2;
+    let first = |v: i64, range: &Range<usize>| (v - half + one - 1).div_euclid(one).max(range.start as i64);
+    let last = |v: i64, range: &Range<usize>| (v - half).div_euclid(one).min(range.end as i64 - 1);
+    let (x0, x1) = (first(a.0.min(b.0).min(c.0), &clip.0), last(a.0.max(b.0).max(c.0), &clip.0));
+    let (y0, y1) = (first(a.1.min(b.1).min(c.1), &clip.1), last(a.1.max(b.1).max(c.1), &clip.1));
+    if x0 > x1 || y0 > y1 {
+        return;
+    }
+
+    let steps = edges.map(|edge| edge.step_x());
+    for y in y0..=y1 {
+        let middle_y = y * one + half;
+        let mut w = edges.map(|edge| edge.at(x0 * one + half, middle_y));
+        for x in x0..=x1 {
+            if w.iter().all(|&w| w >= 0) {
+                fragment(x as usize, y as usize, weights(w));
+            }
+            for (w, step) in w.iter_mut().zip(steps) {
+                *w += step;
+            }
+        }
+    }
+}
+
+// the float version of raster_triangle's test for a single point, same top-left rule and either winding
+fn covers(corners: [Pos2D<Screen>; 3], p: Pos2D<Screen>) -> bool {
+    let [a, mut b, mut c] = corners;
+    let area = edge_value(a, b, c);
+    if area.is_nan() || area == 0.0 {
+        return false;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut b, &mut c);
+    }
+    [(a, b), (b, c), (c, a)].into_iter().all(|(from, to)| {
+        let value = edge_value(from, to, p);
+        let (dx, dy) = (to.x() - from.x(), to.y() - from.y());
+        value > 0.0 || (value == 0.0 && (dy < 0.0 || (dy == 0.0 && dx > 0.0)))
+    })
+}
+
+// p against the edge from -> to, worked out from the lower of the two ends whichever way round they come
+// so the triangles either side of a shared edge get exactly opposite values
+fn edge_value(from: Pos2D<Screen>, to: Pos2D<Screen>, p: Pos2D<Screen>) -> f32 {
+    if (from.x(), from.y()) <= (to.x(), to.y()) {
+        (to - from).perp_dot(p - from)
+    } else {
+        -(from - to).perp_dot(p - to)
+    }
+}
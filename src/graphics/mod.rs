@@ -1,4 +1,6 @@
 pub mod window;
 pub mod gl_wrapper;
 pub mod window_minifb;
-pub mod texture;
\ No newline at end of file
+pub mod texture;
+pub mod shading;
+pub mod tiles;
\ No newline at end of file
@@ -0,0 +1,160 @@
+//! Shading
+//! Values pinned to the corners of a triangle (colors, uvs, depths, any float channels) get blended across it while it's drawn,
+//! and a fragment shader closure picks each pixel's final color from the blend.
+//!
+//! The blend uses barycentric weights, how close a pixel is to each corner. Straight from the screen they're fine for flat 2D,
+//! but a triangle seen in perspective squashes its far end, so each corner can carry its clip space w and the weights get
+//! divided through by it to come out perspective correct.
+//! resource: https://www.scratchapixel.com/lessons/3d-basic-rendering/rasterization-practical-implementation/perspective-correct-interpolation-vertex-attributes.html
+
+use crate::util::color::Color;
+use crate::util::linear::{Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+/// ## Varying
+/// values that can be blended across a triangle
+pub trait Varying: Copy {
+    /// a * weights[0] + b * weights[1] + c * weights[2], the weights add up to 1
+    fn barycentric(a: Self, b: Self, c: Self, weights: [f32; 3]) -> Self;
+}
+
+/// ## Varyings
+/// a value for each corner of a triangle in the same order as its points, and how far each corner is from the eye
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Varyings<V: Varying> {
+    values: [V; 3],
+    w: [f32; 3],
+}
+
+/// ## Fragment
+/// one pixel of a triangle being shaded
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Fragment<V: Varying> {
+    x: usize,
+    y: usize,
+    weights: [f32; 3],
+    w: f32,
+    value: V,
+    under: u32,
+}
+
+impl<V: Varying> Varyings<V> {
+    /// blended evenly across the screen, right for anything flat on it
+    pub fn new(a: V, b: V, c: V) -> Varyings<V> {
+        Varyings { values: [a, b, c], w: [1.0; 3] }
+    }
+
+    /// blended perspective correct, w is each corner's clip space w. w has to be positive, corners behind the eye need clipping first
+    pub fn new_perspective(a: V, b: V, c: V, w: [f32; 3]) -> Varyings<V> {
+        Varyings { values: [a, b, c], w }
+    }
+
+    pub fn values(&self) -> [V; 3] {
+        self.values
+    }
+
+    pub fn w(&self) -> [f32; 3] {
+        self.w
+    }
+
+    /// the fragment for pixel (x, y) from its screen space barycentric weights
+    pub fn fragment(&self, x: usize, y: usize, screen_weights: [f32; 3], under: u32) -> Fragment<V> {
+        // blending 1 / w across the screen is linear, everything else is linear once divided by w
+        let over_w = [0, 1, 2].map(|i| screen_weights[i] / self.w[i]);
+        let total: f32 = over_w.iter().sum();
+        let weights = over_w.map(|weight| weight / total);
+        let [a, b, c] = self.values;
+        Fragment { x, y, weights, w: 1.0 / total, value: V::barycentric(a, b, c, weights), under }
+    }
+}
+
+impl<V: Varying> Fragment<V> {
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// how much of each corner went into value, perspective corrected and adding up to 1
+    pub fn weights(&self) -> [f32; 3] {
+        self.weights
+    }
+
+    /// the clip space w here, 1 without perspective
+    pub fn w(&self) -> f32 {
+        self.w
+    }
+
+    /// the corner values blended for this pixel
+    pub fn value(&self) -> V {
+        self.value
+    }
+
+    /// the color already in the framebuffer, for shaders that blend
+    pub fn under(&self) -> u32 {
+        self.under
+    }
+}
+
+macro_rules! impl_varying_linear {
+    ($($t:ty),*) => {$(
+        impl Varying for $t {
+            fn barycentric(a: $t, b: $t, c: $t, weights: [f32; 3]) -> $t {
+                a * weights[0] + b * weights[1] + c * weights[2]
+            }
+        }
+    )*};
+}
+
+impl_varying_linear!(f32, Vec2D, Vec3D);
+
+// points can't be scaled, only moved, so the blend goes out from a
+impl<S: Space> Varying for Pos2D<S> {
+    fn barycentric(a: Pos2D<S>, b: Pos2D<S>, c: Pos2D<S>, weights: [f32; 3]) -> Pos2D<S> {
+        a + (b - a) * weights[1] + (c - a) * weights[2]
+    }
+}
+
+impl Varying for Pos3D {
+    fn barycentric(a: Pos3D, b: Pos3D, c: Pos3D, weights: [f32; 3]) -> Pos3D {
+        a + (b - a) * weights[1] + (c - a) * weights[2]
+    }
+}
+
+/// each channel is blended on its own and rounded
+impl Varying for Color {
+    fn barycentric(a: Color, b: Color, c: Color, weights: [f32; 3]) -> Color {
+        let channel = |a: u8, b: u8, c: u8| f32::barycentric(a as f32, b as f32, c as f32, weights).round().clamp(0.0, 255.0) as u8;
+        Color::new(
+            channel(a.r(), b.r(), c.r()),
+            channel(a.g(), b.g(), c.g()),
+            channel(a.b(), b.b(), c.b()),
+            channel(a.a(), b.a(), c.a()),
+        )
+    }
+}
+
+/// any number of channels blended side by side
+impl<T: Varying, const N: usize> Varying for [T; N] {
+    fn barycentric(a: [T; N], b: [T; N], c: [T; N], weights: [f32; 3]) -> [T; N] {
+        std::array::from_fn(|i| T::barycentric(a[i], b[i], c[i], weights))
+    }
+}
+
+/// pairs and triples to carry different kinds of values at once, like a color and a uv
+impl<A: Varying, B: Varying> Varying for (A, B) {
+    fn barycentric(a: (A, B), b: (A, B), c: (A, B), weights: [f32; 3]) -> (A, B) {
+        (A::barycentric(a.0, b.0, c.0, weights), B::barycentric(a.1, b.1, c.1, weights))
+    }
+}
+
+impl<A: Varying, B: Varying, C: Varying> Varying for (A, B, C) {
+    fn barycentric(a: (A, B, C), b: (A, B, C), c: (A, B, C), weights: [f32; 3]) -> (A, B, C) {
+        (
+            A::barycentric(a.0, b.0, c.0, weights),
+            B::barycentric(a.1, b.1, c.1, weights),
+            C::barycentric(a.2, b.2, c.2, weights),
+        )
+    }
+}
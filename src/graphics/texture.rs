@@ -1,4 +1,12 @@
+//! Texture
+//! Images kept in memory that triangles and rects can be painted with, the base of sprites.
+//!
+//! Textures are looked up with uvs, u goes left to right across the image and v top to bottom, both 0 to 1.
+//! Texel (x, y) covers u from x / width to (x + 1) / width so its middle is at (x + 0.5) / width.
+//! Anything outside 0 to 1 is brought back in by the Wrap mode, which is how a texture tiles.
+
 use crate::util::color::Color;
+use crate::util::linear::Vec2D;
 
 #[allow(unused)]
 pub struct Material {
@@ -9,4 +17,152 @@ pub struct Material {
     transparency: f32,
     specularity: f32,
     emmission: f32,
-}
\ No newline at end of file
+}
+
+/// ## Wrap
+/// what uvs outside 0 to 1 land on
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Wrap {
+    /// the edge texels stretch out forever
+    Clamp,
+    /// the image tiles
+    Repeat,
+    /// the image tiles flipping every other copy so the seams match up
+    Mirror,
+}
+
+/// ## Filter
+/// how a uv between texel middles picks its color
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// the texel the uv is in, blocky when magnified which suits pixel art
+    Nearest,
+    /// blends the four closest texels by how close they are, smooth when magnified
+    Bilinear,
+}
+
+/// ## Texture
+/// an RGBA image, rows top to bottom
+#[derive(Debug, Clone, PartialEq)]
+pub struct Texture {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+    wrap: Wrap,
+    filter: Filter,
+}
+
+impl Texture {
+    /// pixels are row by row from the top left, None unless there are exactly width * height of them and at least one.
+    /// starts off clamped and nearest
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> Option<Texture> {
+        if width == 0 || height == 0 || width.checked_mul(height) != Some(pixels.len()) {
+            return None;
+        }
+        Some(Texture { width, height, pixels, wrap: Wrap::Clamp, filter: Filter::Nearest })
+    }
+
+    /// a texture with each texel worked out from its x and y
+    pub fn from_fn(width: usize, height: usize, texel: impl Fn(usize, usize) -> Color) -> Option<Texture> {
+        let pixels = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| texel(x, y)).collect();
+        Texture::new(width, height, pixels)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Color {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    pub fn set_wrap(&mut self, wrap: Wrap) {
+        self.wrap = wrap;
+    }
+
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: Filter) {
+        self.filter = filter;
+    }
+
+    /// the uvs of the corners of a block of texels, top left, top right, bottom right, bottom left.
+    /// the order Rect keeps its corners in, so a sprite sheet frame goes straight to Rect::draw_textured
+    pub fn region(&self, x: usize, y: usize, width: usize, height: usize) -> [Vec2D; 4] {
+        let (left, top) = (x as f32 / self.width as f32, y as f32 / self.height as f32);
+        let (right, bottom) = ((x + width) as f32 / self.width as f32, (y + height) as f32 / self.height as f32);
+        [Vec2D::new(left, top), Vec2D::new(right, top), Vec2D::new(right, bottom), Vec2D::new(left, bottom)]
+    }
+
+    /// region for the whole image
+    pub fn uvs(&self) -> [Vec2D; 4] {
+        self.region(0, 0, self.width, self.height)
+    }
+
+    /// the texel at any whole x and y, wrapped back onto the image
+    pub fn texel(&self, x: i64, y: i64) -> Color {
+        self.get_pixel(self.wrap.apply(x, self.width), self.wrap.apply(y, self.height))
+    }
+
+    /// the color at uv with the texture's wrap and filter
+    pub fn sample(&self, uv: Vec2D) -> Color {
+        let (x, y) = (uv.dx() * self.width as f32, uv.dy() * self.height as f32);
+        match self.filter {
+            Filter::Nearest => self.texel(x.floor() as i64, y.floor() as i64),
+            Filter::Bilinear => {
+                // the texel middles around the uv and how far it is between them
+                let (x, y) = (x - 0.5, y - 0.5);
+                let (left, top) = (x.floor(), y.floor());
+                let (tx, ty) = (x - left, y - top);
+                // huge uvs cast to the ends of i64, so the next texel over saturates rather than overflowing
+                let (left, top) = (left as i64, top as i64);
+                let (right, bottom) = (left.saturating_add(1), top.saturating_add(1));
+                let corners = [
+                    (self.texel(left, top), (1.0 - tx) * (1.0 - ty)),
+                    (self.texel(right, top), tx * (1.0 - ty)),
+                    (self.texel(left, bottom), (1.0 - tx) * ty),
+                    (self.texel(right, bottom), tx * ty),
+                ];
+                // sum every channel in floats and round once
+                let channel = |pick: fn(&Color) -> u8| {
+                    corners.iter().map(|(color, weight)| pick(color) as f32 * weight).sum::<f32>().round().clamp(0.0, 255.0) as u8
+                };
+                Color::new(channel(Color::r), channel(Color::g), channel(Color::b), channel(Color::a))
+            }
+        }
+    }
+}
+
+impl Wrap {
+    /// brings i back into 0..size
+    pub fn apply(self, i: i64, size: usize) -> usize {
+        let size = size as i64;
+        let wrapped = match self {
+            Wrap::Clamp => i.clamp(0, size - 1),
+            Wrap::Repeat => i.rem_euclid(size),
+            Wrap::Mirror => {
+                let i = i.rem_euclid(2 * size);
+                if i < size { i } else { 2 * size - 1 - i }
+            }
+        };
+        wrapped as usize
+    }
+}
@@ -0,0 +1,208 @@
+//! Tiles
+//! Draws big batches of triangles by splitting the framebuffer into square tiles and filling them on several threads at once.
+//!
+//! Each triangle is first binned into the tiles its box touches. Worker threads then take whole rows of tiles one at a time
+//! and draw each tile's triangles straight into the framebuffer in the order they were pushed, so later triangles end up on top.
+//! Tiles never share pixels and the rasterizer's edge functions are exact, so the result is pixel for pixel
+//! what drawing the triangles one at a time gives. On a single thread there's nothing to split so the triangles are drawn straight through.
+
+use std::ops::Range;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::components::nodes::node::Camera2D;
+use crate::components::shapes::{raster_triangle, Triangle};
+use crate::graphics::window_minifb::Framebuffer;
+use crate::util::linear::{Aabb2D, Normalized, Pos2D, Screen, Space, World};
+
+/// ## TriangleBatch
+/// flat colored triangles drawn together, later ones on top of earlier ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangleBatch<S: Space = World> {
+    triangles: Vec<(Triangle<S>, u32)>,
+}
+
+/// ## TileRasterizer
+/// how a TriangleBatch gets split up to be drawn in parallel
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TileRasterizer {
+    tile_size: usize,
+    threads: usize,
+}
+
+impl<S: Space> TriangleBatch<S> {
+    pub fn new() -> TriangleBatch<S> {
+        TriangleBatch { triangles: Vec::new() }
+    }
+
+    pub fn push(&mut self, triangle: Triangle<S>, color: u32) {
+        self.triangles.push((triangle, color));
+    }
+
+    pub fn triangles(&self) -> &[(Triangle<S>, u32)] {
+        &self.triangles
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.triangles.clear();
+    }
+}
+
+impl<S: Space> Default for TriangleBatch<S> {
+    fn default() -> TriangleBatch<S> {
+        TriangleBatch::new()
+    }
+}
+
+impl TriangleBatch<Normalized> {
+    /// every triangle's draw in turn on this thread
+    pub fn draw(&self, framebuffer: &mut Framebuffer) {
+        for (triangle, color) in &self.triangles {
+            triangle.draw(framebuffer, *color);
+        }
+    }
+
+    /// the same pixels as draw, split into tiles across rasterizer's threads
+    pub fn draw_tiled(&self, framebuffer: &mut Framebuffer, rasterizer: &TileRasterizer) {
+        let (width, height) = (framebuffer.width() as f32, framebuffer.height() as f32);
+        // the same triangles draw would skip are left out
+        let screen: Vec<_> = self
+            .triangles
+            .iter()
+            .filter(|(t, _)| t.bounds().overlaps(&Aabb2D::new(Pos2D::ZERO, Pos2D::ONE)))
+            .map(|(t, color)| ([t.a(), t.b(), t.c()].map(|p| p.to_screen(width, height)), *color))
+            .collect();
+        rasterizer.draw(framebuffer, &screen);
+    }
+}
+
+impl TriangleBatch<World> {
+    /// every triangle's draw_abs in turn on this thread
+    pub fn draw_abs(&self, framebuffer: &mut Framebuffer, cam: &Camera2D) {
+        for (triangle, color) in &self.triangles {
+            triangle.draw_abs(framebuffer, *color, cam);
+        }
+    }
+
+    /// the same pixels as draw_abs, split into tiles across rasterizer's threads
+    pub fn draw_tiled_abs(&self, framebuffer: &mut Framebuffer, rasterizer: &TileRasterizer, cam: &Camera2D) {
+        let (width, height) = (framebuffer.width() as f32, framebuffer.height() as f32);
+        let screen: Vec<_> = self
+            .triangles
+            .iter()
+            .filter(|(t, _)| t.render_guard(cam, width, height))
+            .map(|(t, color)| ([t.a(), t.b(), t.c()].map(|p| cam.world_to_screen(p, width, height)), *color))
+            .collect();
+        rasterizer.draw(framebuffer, &screen);
+    }
+}
+
+impl TileRasterizer {
+    /// 64 pixel tiles on as many threads as the machine can run at once
+    pub fn new() -> TileRasterizer {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        TileRasterizer { tile_size: 64, threads }
+    }
+
+    pub fn tile_size(&self) -> usize {
+        self.tile_size
+    }
+
+    /// side of the square tiles in pixels, at least 1. smaller tiles share the work out more evenly
+    /// but triangles covering several get binned into each of them
+    pub fn set_tile_size(&mut self, tile_size: usize) {
+        self.tile_size = tile_size.max(1);
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// at least 1, 1 skips the tiles and draws everything on the calling thread
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// draws triangles already in screen space, later ones on top
+    pub fn draw(&self, framebuffer: &mut Framebuffer, triangles: &[([Pos2D<Screen>; 3], u32)]) {
+        let (width, height) = (framebuffer.width(), framebuffer.height());
+        if width == 0 || height == 0 || triangles.is_empty() {
+            return;
+        }
+        let size = self.tile_size;
+        let (columns, rows) = (width.div_ceil(size), height.div_ceil(size));
+
+        // with nothing to share the work with binning is pure overhead, every triangle just goes straight in
+        let threads = self.threads.min(rows);
+        if threads == 1 {
+            for &(corners, color) in triangles {
+                raster_triangle(corners, (0..width, 0..height), |x, y, _| framebuffer.set_pixel(x, y, color));
+            }
+            return;
+        }
+
+        // which triangles might touch each tile, in the order they came
+        let mut bins = vec![Vec::new(); columns * rows];
+        for (i, (corners, _)) in triangles.iter().enumerate() {
+            let (xs, ys) = tile_span(corners, size, columns, rows);
+            for row in ys {
+                for column in xs.clone() {
+                    bins[row * columns + column].push(i);
+                }
+            }
+        }
+
+        // each row of tiles is a band of the framebuffer no other thread touches, they're handed out one at a time
+        // so a thread that gets cheap ones just takes more
+        let bands = Mutex::new(framebuffer.bands_mut(size).enumerate());
+        let draw_bands = || loop {
+            let Some((row, band)) = bands.lock().expect("tile worker panicked").next() else {
+                return;
+            };
+            let ys = row * size..((row + 1) * size).min(height);
+            for column in 0..columns {
+                let xs = column * size..((column + 1) * size).min(width);
+                for &i in &bins[row * columns + column] {
+                    let (corners, color) = triangles[i];
+                    raster_triangle(corners, (xs.clone(), ys.clone()), |x, y, _| band[(y - ys.start) * width + x] = color);
+                }
+            }
+        };
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(draw_bands);
+            }
+        });
+    }
+}
+
+impl Default for TileRasterizer {
+    fn default() -> TileRasterizer {
+        TileRasterizer::new()
+    }
+}
+
+// the columns and rows of tiles the triangle's box touches with a pixel to spare for the rasterizer snapping corners,
+// a corner off at infinity or NaN could land anywhere once snapped so that gets every tile
+fn tile_span(corners: &[Pos2D<Screen>; 3], size: usize, columns: usize, rows: usize) -> (Range<usize>, Range<usize>) {
+    if corners.iter().any(|p| !p.x().is_finite() || !p.y().is_finite()) {
+        return (0..columns, 0..rows);
+    }
+    let span = |values: [f32; 3], count: usize| {
+        let low = values.into_iter().fold(f32::INFINITY, f32::min);
+        let high = values.into_iter().fold(f32::NEG_INFINITY, f32::max);
+        let first = (low - 1.0).floor().max(0.0) as usize / size;
+        let last = ((high + 1.0).ceil().max(0.0) as usize / size + 1).min(count);
+        first..last
+    };
+    (span(corners.map(|p| p.x()), columns), span(corners.map(|p| p.y()), rows))
+}
@@ -1,6 +1,6 @@
 use crate::components::nodes::node::Camera2D;
 use crate::pos2d;
-use crate::util::{color, linear::Pos2D};
+use crate::util::{color, linear::{Normalized, Pos2D}};
 use crate::components::shapes::{Rect, Triangle};
 
 /// # Window
@@ -17,7 +17,7 @@ pub struct Framebuffer {
     height: usize,
 }
 
-static POINTS: &[Pos2D] = &[
+static POINTS: &[Pos2D<Normalized>] = &[
     Pos2D::new(-0.5, 0.3),
     Pos2D::new(0.7, 0.3),
     Pos2D::new(0.5, 0.7),
@@ -145,9 +145,19 @@ impl Framebuffer {
         self.data[x + y * self.width] = value
     }
 
+    pub fn get_pixel(&self, x: usize, y: usize) -> u32 {
+        self.data[x + y * self.width]
+    }
+
     pub fn clear(&mut self, value: u32) {
         for i in 0..self.data.len() {
             self.data[i] = value;
         }
     }
+
+    /// the pixels split into bands of whole rows top to bottom, the last band can be shorter.
+    /// each band can be drawn into on its own thread
+    pub fn bands_mut(&mut self, rows: usize) -> std::slice::ChunksMut<'_, u32> {
+        self.data.chunks_mut((rows * self.width).max(1))
+    }
 }
\ No newline at end of file
@@ -8,11 +8,23 @@ pub mod graphics;
 pub mod util;
 pub mod components;
 
+// every pixel that isn't 0, column by column from the top left
+#[cfg(test)]
+fn lit(fb: &graphics::window_minifb::Framebuffer) -> Vec<(usize, usize)> {
+    (0..fb.width()).flat_map(|x| (0..fb.height()).map(move |y| (x, y))).filter(|&(x, y)| fb.get_pixel(x, y) != 0).collect()
+}
+
+// every pixel's value, row by row from the top left
+#[cfg(test)]
+fn pixels(fb: &graphics::window_minifb::Framebuffer) -> Vec<u32> {
+    (0..fb.height()).flat_map(|y| (0..fb.width()).map(move |x| (x, y))).map(|(x, y)| fb.get_pixel(x, y)).collect()
+}
+
 #[test]
 fn test_pos2d() {
     // make new Pos3Ds and test fmt::Display
-    let mut pos1 = Pos2D::new(2.0, 4.0);
-    let mut pos2 = Pos2D::new(5.0, 2.0);
+    let mut pos1: Pos2D = Pos2D::new(2.0, 4.0);
+    let mut pos2: Pos2D = Pos2D::new(5.0, 2.0);
     println!("{}", pos1);
     println!("{}", pos2);
 
@@ -30,7 +42,7 @@ fn test_pos2d() {
     assert_eq!(pos2, Pos2D::new(6.0, 4.0));
 
     // lerp tests
-    let pos3 = Pos2D::ZERO;
+    let pos3: Pos2D = Pos2D::ZERO;
     let pos4 = Pos2D::new(100.0, 100.0);
     assert_eq!(Pos2D::lerp(pos3, pos4, 0.5), Pos2D::new(50.0, 50.0));
 
@@ -130,6 +142,1771 @@ fn test_vec2d() {
     assert_eq!(v1, v3);
 }
 
+#[test]
+fn test_vector_algebra() {
+    use std::f32::consts::FRAC_PI_2;
+    let a = Vec2D::new(3.0, 4.0);
+    let b = Vec2D::new(1.0, 0.0);
+
+    // dot, perp dot, length
+    assert_eq!(a.dot(b), 3.0);
+    assert_eq!(b.perp_dot(Vec2D::new(0.0, 1.0)), 1.0);
+    assert_eq!(b.perp(), Vec2D::new(0.0, 1.0));
+    assert_eq!(a.length(), 5.0);
+    assert_eq!(a.length_squared(), 25.0);
+    assert_eq!(a.normalize(), Vec2D::new(0.6, 0.8));
+    assert_eq!(Vec2D::<f32>::ZERO.try_normalize(), None);
+    assert_eq!(Vec2D::<f32>::ZERO.normalize(), Vec2D::ZERO);
+    assert!((b.angle_between(Vec2D::new(0.0, 2.0)) - FRAC_PI_2).abs() < 1e-6);
+
+    // project, reject and reflect
+    assert_eq!(a.project(b), Vec2D::new(3.0, 0.0));
+    assert_eq!(a.reject(b), Vec2D::new(0.0, 4.0));
+    assert_eq!(a.project(b) + a.reject(b), a);
+    assert_eq!(Vec2D::new(1.0, -1.0).reflect(Vec2D::new(0.0, 1.0)), Vec2D::new(1.0, 1.0));
+
+    // operators and component-wise ops
+    assert_eq!(-a, Vec2D::new(-3.0, -4.0));
+    assert_eq!(a / 2.0, Vec2D::new(1.5, 2.0));
+    assert_eq!(2.0 * a, a * 2.0);
+    assert_eq!(a.mul_elem(Vec2D::new(2.0, 0.5)), Vec2D::new(6.0, 2.0));
+    assert_eq!(a.div_elem(Vec2D::new(3.0, 2.0)), Vec2D::new(1.0, 2.0));
+    assert_eq!(a.min(b), Vec2D::new(1.0, 0.0));
+    assert_eq!((-a).abs(), a);
+
+    // points and vectors
+    let p1: Pos2D = Pos2D::new(1.0, 1.0);
+    let p2 = Pos2D::new(4.0, 5.0);
+    assert_eq!(p2 - p1, a);
+    assert_eq!(p2 - a, p1);
+
+    // 3D
+    let x = Vec3D::new(1.0, 0.0, 0.0);
+    let y = Vec3D::new(0.0, 1.0, 0.0);
+    let v = Vec3D::new(2.0, 3.0, 6.0);
+    assert_eq!(x.cross(y), Vec3D::new(0.0, 0.0, 1.0));
+    assert_eq!(x.dot(y), 0.0);
+    assert_eq!(v.length(), 7.0);
+    assert!((v.normalize().length() - 1.0).abs() < 1e-6);
+    assert_eq!(Vec3D::<f32>::ZERO.try_normalize(), None);
+    assert!((x.angle_between(y) - FRAC_PI_2).abs() < 1e-6);
+    assert_eq!(v.project(x), Vec3D::new(2.0, 0.0, 0.0));
+    assert_eq!(v.reject(x), Vec3D::new(0.0, 3.0, 6.0));
+    assert_eq!(Vec3D::new(1.0, -1.0, 0.0).reflect(y), Vec3D::new(1.0, 1.0, 0.0));
+    assert_eq!(v + x - x, v);
+    assert_eq!(-v * 2.0, Vec3D::new(-4.0, -6.0, -12.0));
+    assert_eq!(v / 2.0, Vec3D::new(1.0, 1.5, 3.0));
+    assert_eq!(v.mul_elem(Vec3D::ONE * 2.0), v * 2.0);
+    assert_eq!(Pos3D::new(3.0, 4.0, 7.0) - Pos3D::ONE, v);
+    assert_eq!(Pos3D::new(3.0, 4.0, 7.0) - v, Pos3D::new(1.0, 1.0, 1.0));
+}
+
+#[test]
+fn test_mat3() {
+    let p: Pos2D = Pos2D::new(1.0, 0.0);
+    let v = Vec2D::new(1.0, 0.0);
+
+    // translation only moves points
+    let t = Mat3::translation(2.0, 3.0);
+    assert_eq!(t * p, Pos2D::new(3.0, 3.0));
+    assert_eq!(t * v, Vec2D::new(1.0, 0.0));
+
+    // rotation a quarter turn counter-clockwise
+    let r = Mat3::rotation(std::f32::consts::FRAC_PI_2);
+    let rp = r * p;
+    assert!(rp.x().abs() < 1e-6 && (rp.y() - 1.0).abs() < 1e-6);
+
+    // scale and shear
+    assert_eq!(Mat3::scale(2.0, 3.0) * Pos2D::<World>::new(1.0, 1.0), Pos2D::new(2.0, 3.0));
+    assert_eq!(Mat3::shear(1.0, 0.0) * Pos2D::<World>::new(1.0, 2.0), Pos2D::new(3.0, 2.0));
+
+    // composition reads right to left, then reads left to right
+    let m = t * Mat3::scale(2.0, 2.0);
+    assert_eq!(m * p, Pos2D::new(4.0, 3.0));
+    assert_eq!(Mat3::scale(2.0, 2.0).then(t), m);
+
+    // determinant and inverse
+    assert_eq!(Mat3::scale(2.0, 3.0).determinant(), 6.0);
+    assert_eq!(Mat3::scale(0.0, 1.0).inverse(), None);
+    let inv = m.inverse().unwrap();
+    assert_eq!(inv * (m * p), p);
+    assert_eq!(m * inv, Mat3::IDENTITY);
+    // small isn't singular, only degenerate is
+    let tiny = Mat3::scale(1e-4, 1e-4).inverse().unwrap();
+    let small: Pos2D = Pos2D::new(1e-4, 2e-4);
+    assert!((tiny * small - Pos2D::new(1.0, 2.0)).length() < 1e-3);
+    assert_eq!(Mat3::scale(1.0, f32::NAN).inverse(), None);
+
+    // rotating about a pivot leaves the pivot where it is
+    let pivot: Pos2D = Pos2D::new(5.0, 5.0);
+    assert_eq!(Mat3::rotation_about(pivot, 1.0) * pivot, pivot);
+}
+
+#[test]
+fn test_mat4() {
+    use components::nodes::node::{Camera3D, Direction3D};
+    let p = Pos3D::new(1.0, 2.0, 3.0);
+
+    // translation moves points but not vectors
+    let t = Mat4::translation(1.0, 1.0, 1.0);
+    assert_eq!(t * p, Pos3D::new(2.0, 3.0, 4.0));
+    assert_eq!(t * Vec3D::new(1.0, 2.0, 3.0), Vec3D::new(1.0, 2.0, 3.0));
+
+    // inverse, transpose and determinant
+    let m = t * Mat4::scale(2.0, 4.0, 8.0) * Mat4::rotation_y(0.5);
+    assert!((m.determinant() - 64.0).abs() < 1e-3);
+    let back = m.inverse().unwrap() * (m * p);
+    assert!((back.x() - p.x()).abs() < 1e-4 && (back.y() - p.y()).abs() < 1e-4 && (back.z() - p.z()).abs() < 1e-4);
+    assert_eq!(Mat4::scale(1.0, 0.0, 1.0).inverse(), None);
+    let tiny = Mat4::scale(1e-4, 1e-4, 1e-4).inverse().unwrap();
+    assert!((tiny * Pos3D::new(1e-4, 2e-4, 3e-4) - Pos3D::new(1.0, 2.0, 3.0)).length() < 1e-3);
+    assert_eq!(Mat4::scale(1.0, f32::INFINITY, 1.0).inverse(), None);
+    assert_eq!(m.transpose().transpose(), m);
+
+    // perspective puts the near plane at -1 and the far plane at 1 after the divide
+    let proj = Mat4::perspective(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+    assert!(((proj * Pos3D::new(0.0, 0.0, -1.0)).z() + 1.0).abs() < 1e-5);
+    assert!(((proj * Pos3D::new(0.0, 0.0, -100.0)).z() - 1.0).abs() < 1e-5);
+    // with a 90 degree fov the edge of the view at depth 2 is at x = 2
+    assert!(((proj * Pos3D::new(2.0, 0.0, -2.0)).x() - 1.0).abs() < 1e-5);
+
+    // orthographic maps the box onto -1..1
+    let ortho = Mat4::orthographic(0.0, 10.0, 0.0, 20.0, 1.0, 11.0);
+    assert_eq!(ortho * Pos3D::new(10.0, 20.0, -11.0), Pos3D::new(1.0, 1.0, 1.0));
+    assert_eq!(ortho * Pos3D::new(0.0, 0.0, -1.0), Pos3D::new(-1.0, -1.0, -1.0));
+
+    // look_at puts the target straight down -z
+    let view = Mat4::look_at(Pos3D::new(0.0, 0.0, 5.0), Pos3D::ZERO, Vec3D::new(0.0, 1.0, 0.0));
+    assert_eq!(view * Pos3D::ZERO, Pos3D::new(0.0, 0.0, -5.0));
+
+    let cam = Camera3D::new(Pos3D::new(0.0, 0.0, 5.0), Direction3D::WEST);
+    assert_eq!(cam.view_matrix(), view);
+}
+
+#[test]
+fn test_quat() {
+    use std::f32::consts::{FRAC_PI_2, PI};
+    use components::nodes::node::{Camera3D, Direction3D};
+    let close = |a: Vec3D, b: Vec3D| (a.dx() - b.dx()).abs() < 1e-5 && (a.dy() - b.dy()).abs() < 1e-5 && (a.dz() - b.dz()).abs() < 1e-5;
+
+    // a quarter turn about y takes +x to -z
+    let q = Quat::from_axis_angle(Vec3D::new(0.0, 1.0, 0.0), FRAC_PI_2);
+    assert!(close(q * Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(0.0, 0.0, -1.0)));
+    let p = q * Pos3D::new(1.0, 0.0, 0.0);
+    assert!(p.x().abs() < 1e-5 && (p.z() + 1.0).abs() < 1e-5);
+
+    // composition, conjugate and inverse
+    assert!(close((q * q) * Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(-1.0, 0.0, 0.0)));
+    assert!(close((q * q.conjugate()) * Vec3D::new(1.0, 2.0, 3.0), Vec3D::new(1.0, 2.0, 3.0)));
+    assert!(close(q.inverse() * (q * Vec3D::new(1.0, 2.0, 3.0)), Vec3D::new(1.0, 2.0, 3.0)));
+
+    // euler yaw matches the axis angle version
+    assert!(close(Quat::from_euler(FRAC_PI_2, 0.0, 0.0) * Vec3D::new(1.0, 0.0, 0.0), q * Vec3D::new(1.0, 0.0, 0.0)));
+    let (axis, angle) = q.to_axis_angle();
+    assert!(close(axis, Vec3D::new(0.0, 1.0, 0.0)) && (angle - FRAC_PI_2).abs() < 1e-5);
+
+    // the rotation matrix agrees with rotating directly
+    let v = Vec3D::new(0.3, -2.0, 1.5);
+    assert!(close(q.to_mat4() * v, q * v));
+
+    // slerp halfway through a half turn is a quarter turn, nlerp agrees at the ends
+    let half = Quat::from_axis_angle(Vec3D::new(0.0, 1.0, 0.0), PI * 0.99);
+    let mid = Quat::slerp(Quat::IDENTITY, half, 0.5);
+    assert!((mid.to_axis_angle().1 - PI * 0.495).abs() < 1e-4);
+    assert!(close(Quat::nlerp(Quat::IDENTITY, q, 1.0) * v, q * v));
+    assert!(close(Quat::slerp(Quat::IDENTITY, q, 0.0) * v, v));
+
+    // rotation arcs and the camera's orientation
+    let arc = Quat::from_rotation_arc(Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(0.0, 0.0, 1.0));
+    assert!(close(arc * Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(0.0, 0.0, 1.0)));
+    let mut cam = Camera3D::new(Pos3D::ZERO, Direction3D::NORTH);
+    assert!(close(cam.direction().to_vec3d(), Direction3D::NORTH.to_vec3d()));
+    cam.slerp_orientation(Quat::IDENTITY, 1.0);
+    assert!(close(cam.direction().to_vec3d(), Camera3D::FORWARD.to_vec3d()));
+    cam.set_direction(Direction3D::UP);
+    assert!(close(cam.direction().to_vec3d(), Direction3D::UP.to_vec3d()));
+}
+
+#[test]
+fn test_spaces() {
+    use graphics::window_minifb::Framebuffer;
+    let cam = Camera2D::new(Pos2D::new(0.0, 0.0));
+    let (width, height) = (100.0, 100.0);
+
+    // the camera is the middle of the screen and world y points up
+    let world: Pos2D = Pos2D::new(10.0, 20.0);
+    let screen = cam.world_to_screen(world, width, height);
+    assert_eq!(screen, Pos2D::<Screen>::new(60.0, 30.0));
+    assert_eq!(cam.screen_to_world(screen, width, height), world);
+    assert_eq!(cam.world_to_normalized(world, width, height), Pos2D::<Normalized>::new(0.6, 0.3));
+    assert_eq!(cam.normalized_to_world(Pos2D::new(0.5, 0.5), width, height), Pos2D::ZERO);
+
+    // normalized and screen are just a scale apart
+    let n: Pos2D<Normalized> = Pos2D::new(0.25, 0.75);
+    assert_eq!(n.to_screen(width, height), Pos2D::new(25.0, 75.0));
+    assert_eq!(n.to_screen(width, height).to_normalized(width, height), n);
+
+    // draw_abs puts world positions where world_to_screen says they go
+    let mut fb = Framebuffer::new(100, 100);
+    let t: Triangle = Triangle::new(Pos2D::new(50.0, 10.0), Pos2D::new(-30.0, 10.0), Pos2D::new(50.0, -30.0));
+    t.draw_abs(&mut fb, 1, &cam);
+    let inside = cam.world_to_screen(Pos2D::new(30.0, 9.0), width, height);
+    let outside = cam.world_to_screen(Pos2D::new(-40.0, 40.0), width, height);
+    assert_eq!(fb.get_pixel(inside.x() as usize, inside.y() as usize), 1);
+    assert_eq!(fb.get_pixel(outside.x() as usize, outside.y() as usize), 0);
+}
+
+#[test]
+fn test_aabb() {
+    let pts: [Pos2D; 3] = [Pos2D::new(1.0, 5.0), Pos2D::new(-2.0, 3.0), Pos2D::new(4.0, -1.0)];
+    let b = Aabb2D::from_points(&pts).unwrap();
+    assert_eq!(b.min(), Pos2D::new(-2.0, -1.0));
+    assert_eq!(b.max(), Pos2D::new(4.0, 5.0));
+    assert_eq!(Aabb2D::<World>::from_points(&[]), None);
+    assert_eq!(b.center(), Pos2D::new(1.0, 2.0));
+    assert_eq!(b.extents(), Vec2D::new(3.0, 3.0));
+    assert_eq!(b.size(), Vec2D::new(6.0, 6.0));
+
+    // containment and overlaps, edges count
+    assert!(b.contains(&Pos2D::new(4.0, 5.0)));
+    assert!(!b.contains(&Pos2D::new(4.1, 5.0)));
+    let other = Aabb2D::new(Pos2D::new(3.0, 4.0), Pos2D::new(10.0, 10.0));
+    assert!(b.overlaps(&other));
+    assert_eq!(b.intersection(&other), Some(Aabb2D::new(Pos2D::new(3.0, 4.0), Pos2D::new(4.0, 5.0))));
+    assert_eq!(b.union(&other), Aabb2D::new(Pos2D::new(-2.0, -1.0), Pos2D::new(10.0, 10.0)));
+    let away = Aabb2D::new(Pos2D::new(20.0, 20.0), Pos2D::new(30.0, 30.0));
+    assert!(!b.overlaps(&away));
+    assert_eq!(b.clamp_to(&away), None);
+    assert!(b.union(&other).contains_aabb(&b));
+
+    // growing and clamping
+    assert_eq!(b.expand(1.0), Aabb2D::new(Pos2D::new(-3.0, -2.0), Pos2D::new(5.0, 6.0)));
+    assert_eq!(b.expand(-2.0), Aabb2D::new(Pos2D::new(0.0, 1.0), Pos2D::new(2.0, 3.0)));
+    // shrinking past the middle flattens onto the center instead of flipping inside out
+    assert_eq!(b.expand(-4.0), Aabb2D::new(b.center(), b.center()));
+    assert!(b.expand(-4.0).is_empty());
+    let wide: Aabb2D = Aabb2D::new(Pos2D::new(0.0, 0.0), Pos2D::new(10.0, 2.0));
+    assert_eq!(wide.expand(-2.0), Aabb2D::new(Pos2D::new(2.0, 1.0), Pos2D::new(8.0, 1.0)));
+    assert_eq!(b.expand_to(Pos2D::new(8.0, 0.0)).max(), Pos2D::new(8.0, 5.0));
+    assert_eq!(b.clamp_point(Pos2D::new(100.0, -100.0)), Pos2D::new(4.0, -1.0));
+
+    // 3D
+    let b3 = Aabb3D::from_points(&[Pos3D::new(1.0, 2.0, 3.0), Pos3D::new(-1.0, 0.0, 5.0)]).unwrap();
+    assert_eq!(b3.min(), Pos3D::new(-1.0, 0.0, 3.0));
+    assert_eq!(b3.center(), Pos3D::new(0.0, 1.0, 4.0));
+    assert_eq!(b3.extents(), Vec3D::new(1.0, 1.0, 1.0));
+    assert!(b3.contains(&Pos3D::new(0.0, 1.0, 4.0)));
+    let o3 = Aabb3D::from_center(Pos3D::new(1.0, 2.0, 5.0), Vec3D::new(1.0, 1.0, 1.0));
+    assert_eq!(b3.intersection(&o3), Some(Aabb3D::new(Pos3D::new(0.0, 1.0, 4.0), Pos3D::new(1.0, 2.0, 5.0))));
+    assert_eq!(b3.union(&o3).max(), Pos3D::new(2.0, 3.0, 6.0));
+    assert!(!b3.overlaps(&Aabb3D::new(Pos3D::new(5.0, 5.0, 5.0), Pos3D::new(6.0, 6.0, 6.0))));
+    assert_eq!(b3.expand(1.0).min(), Pos3D::new(-2.0, -1.0, 2.0));
+    assert_eq!(b3.expand(-0.5), Aabb3D::new(Pos3D::new(-0.5, 0.5, 3.5), Pos3D::new(0.5, 1.5, 4.5)));
+    assert_eq!(b3.expand(-2.0), Aabb3D::new(b3.center(), b3.center()));
+}
+
+#[test]
+fn test_ray() {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+
+    // 2D primitives
+    let ray: Ray2D = Ray2D::new(Pos2D::new(0.0, 0.0), Vec2D::new(2.0, 0.0)).unwrap();
+    assert_eq!(ray.direction(), Vec2D::new(1.0, 0.0));
+    assert_eq!(Ray2D::<World>::new(Pos2D::ZERO, Vec2D::ZERO), None);
+
+    let hit = ray.intersect_segment(Pos2D::new(5.0, -1.0), Pos2D::new(5.0, 1.0)).unwrap();
+    assert_eq!((hit.distance(), hit.point(), hit.normal()), (5.0, Pos2D::new(5.0, 0.0), Vec2D::new(-1.0, 0.0)));
+    assert_eq!(ray.intersect_segment(Pos2D::new(-5.0, -1.0), Pos2D::new(-5.0, 1.0)), None);
+
+    let hit = ray.intersect_circle(Pos2D::new(10.0, 0.0), 2.0).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (8.0, Vec2D::new(-1.0, 0.0)));
+    // from inside the circle we hit the far side
+    assert_eq!(ray.intersect_circle(Pos2D::ZERO, 3.0).unwrap().distance(), 3.0);
+    assert_eq!(ray.intersect_circle(Pos2D::new(0.0, 10.0), 2.0), None);
+
+    let b = Aabb2D::new(Pos2D::new(4.0, -1.0), Pos2D::new(6.0, 1.0));
+    let hit = ray.intersect_aabb(&b).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (4.0, Vec2D::new(-1.0, 0.0)));
+    let inside: Ray2D = Ray2D::new(Pos2D::new(5.0, 0.0), Vec2D::new(0.0, -1.0)).unwrap();
+    let hit = inside.intersect_aabb(&b).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (1.0, Vec2D::new(0.0, -1.0)));
+    assert_eq!(ray.intersect_aabb(&Aabb2D::new(Pos2D::new(4.0, 2.0), Pos2D::new(6.0, 3.0))), None);
+
+    // shapes
+    let t: Triangle = Triangle::new(Pos2D::new(3.0, -2.0), Pos2D::new(3.0, 2.0), Pos2D::new(6.0, 0.0));
+    let hit = t.intersect_ray(&ray).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (3.0, Vec2D::new(-1.0, 0.0)));
+    let up: Ray2D = Ray2D::new(Pos2D::new(4.0, -5.0), Vec2D::new(0.0, 1.0)).unwrap();
+    assert!(t.intersect_ray(&up).unwrap().normal().dy() < 0.0);
+
+    let r: Rect = Rect::new(Pos2D::new(2.0, 1.0), 2.0, 2.0);
+    let hit = r.intersect_ray(&ray).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (2.0, Vec2D::new(-1.0, 0.0)));
+    let down: Ray2D = Ray2D::new(Pos2D::new(3.0, 0.0), Vec2D::new(0.0, -1.0)).unwrap();
+    let hit = r.intersect_ray(&down).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (1.0, Vec2D::new(0.0, -1.0)));
+
+    // 3D
+    let ray3 = Ray3D::new(Pos3D::new(0.0, 0.0, 10.0), Vec3D::new(0.0, 0.0, -1.0)).unwrap();
+    let hit = ray3.intersect_plane(Pos3D::ZERO, Vec3D::new(0.0, 0.0, -1.0)).unwrap();
+    assert_eq!((hit.distance(), hit.point(), hit.normal()), (10.0, Pos3D::ZERO, Vec3D::new(0.0, 0.0, 1.0)));
+    assert_eq!(ray3.intersect_plane(Pos3D::ZERO, Vec3D::new(1.0, 0.0, 0.0)), None);
+
+    let hit = ray3.intersect_sphere(Pos3D::ZERO, 1.0).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (9.0, Vec3D::new(0.0, 0.0, 1.0)));
+
+    let hit = ray3.intersect_aabb(&Aabb3D::from_center(Pos3D::ZERO, Vec3D::ONE)).unwrap();
+    assert_eq!((hit.distance(), hit.normal()), (9.0, Vec3D::new(0.0, 0.0, 1.0)));
+
+    let (a, b, c) = (Pos3D::new(-1.0, -1.0, 0.0), Pos3D::new(1.0, -1.0, 0.0), Pos3D::new(0.0, 1.0, 0.0));
+    let hit = ray3.intersect_triangle(a, b, c).unwrap();
+    assert!(close(hit.distance(), 10.0) && close(hit.normal().dz(), 1.0));
+    // the winding doesn't matter
+    assert!(ray3.intersect_triangle(a, c, b).is_some());
+    let miss = Ray3D::new(Pos3D::new(2.0, 2.0, 10.0), Vec3D::new(0.0, 0.0, -1.0)).unwrap();
+    assert_eq!(miss.intersect_triangle(a, b, c), None);
+}
+
+#[test]
+fn test_easing() {
+    use util::{color::Color, easing::{self, Easing}};
+
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+    let curves = [
+        Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut,
+        Easing::CubicInOut, Easing::QuartIn, Easing::QuartOut, Easing::QuartInOut, Easing::SineIn, Easing::SineOut,
+        Easing::SineInOut, Easing::ExpoIn, Easing::ExpoOut, Easing::ExpoInOut, Easing::BackIn, Easing::BackOut,
+        Easing::BackInOut, Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut, Easing::BounceIn,
+        Easing::BounceOut, Easing::BounceInOut, Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+    ];
+    // every curve starts at 0 and ends at 1
+    for e in curves {
+        assert!(close(e.apply(0.0), 0.0), "{:?}", e);
+        assert!(close(e.apply(1.0), 1.0), "{:?}", e);
+    }
+    // t is clamped
+    assert_eq!(Easing::QuadIn.apply(2.0), 1.0);
+
+    assert!(close(Easing::QuadIn.apply(0.5), 0.25));
+    assert!(close(Easing::QuadOut.apply(0.5), 0.75));
+    assert!(close(Easing::CubicInOut.apply(0.5), 0.5));
+    assert!(close(Easing::SineInOut.apply(0.5), 0.5));
+    assert!(close(Easing::BounceOut.apply(1.0 / 2.75), 1.0));
+    // back pulls back before it goes forward
+    assert!(Easing::BackIn.apply(0.2) < 0.0);
+    assert!(Easing::ElasticOut.apply(0.1) > 1.0);
+    // a straight bezier is linear and ease-in-out is symmetric
+    assert!(close(Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.3), 0.3));
+    let ease_in_out = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+    assert!(close(ease_in_out.apply(0.5), 0.5));
+    assert!(close(ease_in_out.apply(0.25) + ease_in_out.apply(0.75), 1.0));
+
+    // positions
+    let start: Pos2D = Pos2D::ZERO;
+    let end = Pos2D::new(10.0, 20.0);
+    assert_eq!(Pos2D::ease(start, end, 0.5, Easing::Linear), Pos2D::lerp(start, end, 0.5));
+    assert_eq!(Pos2D::ease(start, end, 0.5, Easing::QuadIn), Pos2D::new(2.5, 5.0));
+    let steps = Pos2D::ease_steps(start, end, 4, Easing::QuadOut);
+    assert_eq!(steps.len(), 5);
+    assert_eq!((steps[0], steps[4]), (start, end));
+    assert!(steps.windows(2).all(|w| w[1].x() >= w[0].x()));
+    assert_eq!(Pos3D::ease(Pos3D::ZERO, Pos3D::ONE, 0.5, Easing::QuadOut), Pos3D::new(0.75, 0.75, 0.75));
+    assert_eq!(Pos3D::ease_steps(Pos3D::ZERO, Pos3D::ONE, 2, Easing::Linear), Pos3D::lerp_steps(Pos3D::ZERO, Pos3D::ONE, 2));
+
+    // scalars and colors
+    assert_eq!(easing::ease(10.0, 20.0, 0.5, Easing::QuadIn), 12.5);
+    assert_eq!(easing::ease_steps(0.0, 1.0, 2, Easing::Linear), vec![0.0, 0.5, 1.0]);
+    let black = Color::new(0, 0, 0, 255);
+    let white = Color::new(255, 255, 255, 255);
+    assert_eq!(easing::ease(black, white, 0.5, Easing::Linear), Color::new(128, 128, 128, 255));
+    assert_eq!(easing::ease(black, white, 0.5, Easing::Linear).to_u32(), 0x808080);
+    // overshooting curves can't wrap a channel around
+    assert_eq!(easing::ease(black, white, 0.2, Easing::BackIn), black);
+}
+
+#[test]
+fn test_spline() {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+
+    // a quadratic arch
+    let (a, b, c): (Pos2D, Pos2D, Pos2D) = (Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 2.0), Pos2D::new(2.0, 0.0));
+    let quad = QuadraticBezier::new(a, b, c);
+    assert_eq!((quad.at(0.0), quad.at(0.5), quad.at(1.0)), (a, Pos2D::new(1.0, 1.0), c));
+    assert_eq!(quad.tangent(0.0), Vec2D::new(2.0, 4.0));
+    assert_eq!(quad.tangent(0.5), Vec2D::new(2.0, 0.0));
+    // the bounds hug the curve and not the control point
+    let bounds = quad.bounds();
+    assert_eq!((bounds.min(), bounds.max()), (a, Pos2D::new(2.0, 1.0)));
+    let (left, right) = quad.split(0.5);
+    assert_eq!((left.at(1.0), right.at(0.0)), (quad.at(0.5), quad.at(0.5)));
+    assert!(close(left.at(0.5).x(), quad.at(0.25).x()) && close(left.at(0.5).y(), quad.at(0.25).y()));
+    let cubic = quad.to_cubic();
+    for t in [0.1, 0.4, 0.8] {
+        assert!(close(cubic.at(t).x(), quad.at(t).x()) && close(cubic.at(t).y(), quad.at(t).y()));
+    }
+
+    // a straight cubic is a line, so its length and constant speed points are easy to check
+    let line: CubicBezier = CubicBezier::new(Pos2D::ZERO, Pos2D::new(1.0, 0.0), Pos2D::new(2.0, 0.0), Pos2D::new(10.0, 0.0));
+    let table = line.arc_length(256);
+    assert!(close(table.total(), 10.0) && close(line.length(), 10.0));
+    for d in [0.0, 2.5, 5.0, 7.5, 10.0] {
+        assert!((line.at_distance(&table, d).x() - d).abs() < 0.05, "{}", d);
+    }
+    assert_eq!(table.t_at_distance(-1.0), 0.0);
+    assert_eq!(table.t_at_distance(100.0), 1.0);
+    assert!(close(line.at(table.t_at_fraction(0.5)).x(), 5.0));
+
+    // an s-bend gets flattened into more than one line and every point stays on the curve
+    let s_bend: CubicBezier = CubicBezier::new(Pos2D::ZERO, Pos2D::new(0.0, 10.0), Pos2D::new(10.0, -10.0), Pos2D::new(10.0, 0.0));
+    let poly = s_bend.flatten(0.1);
+    assert!(poly.len() > 4);
+    assert_eq!((poly[0], *poly.last().unwrap()), (s_bend.at(0.0), s_bend.at(1.0)));
+    let bounds = s_bend.bounds();
+    assert!(poly.iter().all(|p| bounds.expand(1e-3).contains(p)));
+    assert!(bounds.max().y() < 10.0 && bounds.max().y() > 2.0);
+    // a flat curve needs barely any points
+    assert!(line.flatten(0.1).len() <= 5);
+
+    // catmull-rom goes through every point
+    let points: Vec<Pos2D> = vec![Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 1.0), Pos2D::new(2.0, 0.0), Pos2D::new(3.0, 1.0)];
+    let path = CatmullRom::new(points.clone()).unwrap();
+    assert_eq!(path.segment_count(), 3);
+    for (i, p) in points.iter().enumerate() {
+        let q = path.at(i as f32 / 3.0);
+        assert!(close(q.x(), p.x()) && close(q.y(), p.y()), "{} {}", q, p);
+    }
+    assert!(path.tangent(0.5).dx() > 0.0);
+    let bounds = path.bounds();
+    assert!(points.iter().all(|p| bounds.contains(p)));
+    let poly = path.flatten(0.01);
+    assert!(points.iter().all(|p| poly.contains(p)));
+    assert_eq!(CatmullRom::<Pos2D>::new(vec![Pos2D::ZERO]), None);
+
+    // 3D
+    let arc = CubicBezier::new(Pos3D::ZERO, Pos3D::new(0.0, 0.0, 1.0), Pos3D::new(1.0, 0.0, 1.0), Pos3D::new(1.0, 1.0, 1.0));
+    assert_eq!(arc.at(1.0), Pos3D::new(1.0, 1.0, 1.0));
+    assert_eq!(arc.tangent(0.0), Vec3D::new(0.0, 0.0, 3.0));
+    let bounds = arc.bounds();
+    assert_eq!((bounds.min(), bounds.max()), (Pos3D::ZERO, Pos3D::ONE));
+    let length = arc.length();
+    assert!(length > 3.0_f32.sqrt() && length < 3.0);
+}
+
+#[test]
+fn test_scalars() {
+    use std::collections::HashSet;
+
+    // f64 keeps precision far from the origin where f32 can't tell the points apart
+    let far: DPos2D = Pos2D::new(100_000_000.0, 0.0);
+    let step = far + Vec2D::new(0.5, 0.0);
+    assert_eq!((step - far).dx(), 0.5);
+    let far32: Pos2D = far.cast();
+    assert_eq!(((far32 + Vec2D::new(0.5, 0.0)) - far32).dx(), 0.0);
+    assert_eq!(DVec3D::new(3.0, 4.0, 0.0).length(), 5.0);
+    assert_eq!(DPos3D::lerp(DPos3D::ZERO, DPos3D::ONE, 0.25), Pos3D::new(0.25, 0.25, 0.25));
+
+    // lossless conversions
+    let p: Pos2D = Pos2D::new(1.5, -2.25);
+    let wide: DPos2D = p.into();
+    assert_eq!((wide.x(), wide.y()), (1.5, -2.25));
+    let tile: IPos2D = Pos2D::new(3, -4);
+    let tile64: Pos2D<World, i64> = tile.into();
+    assert_eq!(tile64, Pos2D::new(3i64, -4i64));
+    assert_eq!(DVec2D::from(IVec2D::new(2, 7)), Vec2D::new(2.0, 7.0));
+    assert_eq!(Pos3D::<f64>::from(IPos3D::new(1, 2, 3)), Pos3D::new(1.0, 2.0, 3.0));
+
+    // lossy conversions truncate like `as`, floor first to find the tile a point is in
+    let world: Pos2D = Pos2D::new(2.7, -1.2);
+    assert_eq!(world.cast::<i32>(), IPos2D::new(2, -1));
+    assert_eq!(world.floor().cast::<i32>(), IPos2D::new(2, -2));
+    assert_eq!(world.round().cast::<i32>(), IPos2D::new(3, -1));
+    assert_eq!(Vec3D::new(1.9, -0.5, 4.0).cast::<i32>(), IVec3D::new(1, 0, 4));
+
+    // float min and max skip NaN like f32's own, so a NaN corner doesn't spread through a box
+    assert_eq!(Scalar::min(f32::NAN, 1.0), 1.0);
+    assert_eq!(Scalar::max(2.0, f64::NAN), 2.0);
+    assert_eq!(Scalar::min(3, -2), -2);
+    let nan: Pos2D = Pos2D::new(f32::NAN, 3.0);
+    assert_eq!(nan.min(Pos2D::new(1.0, 5.0)), Pos2D::new(1.0, 3.0));
+
+    // integer grids
+    let a: IPos2D = Pos2D::new(0, 0);
+    let b = Pos2D::new(3, -4);
+    assert_eq!(a.manhattan_distance(b), 7);
+    assert_eq!(a.chebyshev_distance(b), 4);
+    assert_eq!((b - a).manhattan_length(), 7);
+    let n4 = a.neighbours_4();
+    assert!(n4.iter().all(|n| a.manhattan_distance(*n) == 1));
+    let n8: HashSet<IPos2D> = a.neighbours_8().into_iter().collect();
+    assert_eq!(n8.len(), 8);
+    assert!(!n8.contains(&a) && n8.contains(&Pos2D::new(-1, 1)));
+    let cell = IPos3D::new(1, 1, 1);
+    assert!(cell.neighbours_6().iter().all(|n| cell.manhattan_distance(*n) == 1));
+    assert_eq!(IVec2D::new(2, 3) * 2, Vec2D::new(4, 6));
+    assert_eq!(3 * IVec2D::new(1, -1), Vec2D::new(3, -3));
+    assert_eq!(format!("{}", tile), "Pos2D<World>: { x: 3, y: -4 }");
+}
+
+#[test]
+fn test_approx() {
+    use std::f32::consts::FRAC_PI_2;
+
+    // scalars
+    assert!(0.1f64 + 0.2 != 0.3 && (0.1f64 + 0.2).approx_eq(&0.3));
+    assert!(1.0f32.abs_diff_eq(&1.05, 0.1) && !1.0f32.abs_diff_eq(&1.2, 0.1));
+    assert!(1_000_000.0f32.relative_eq(&1_000_050.0, 0.0, 1e-4));
+    assert!(!1_000_000.0f32.relative_eq(&1_001_000.0, 0.0, 1e-4));
+    let next = f32::from_bits(1.0f32.to_bits() + 2);
+    assert!(1.0f32.ulps_eq(&next, 0.0, 2) && !1.0f32.ulps_eq(&next, 0.0, 1));
+    assert!(!(-1e-3f32).ulps_eq(&1e-3, 0.0, 100));
+    assert!(!f32::NAN.approx_eq(&f32::NAN));
+    assert!(f32::INFINITY.approx_eq(&f32::INFINITY));
+    assert!(1e-13f64.approx_eq(&0.0) && !1e-6f64.approx_eq(&0.0));
+
+    // a quarter turn there and back doesn't land exactly where it started
+    let p: Pos2D = Pos2D::new(3.0, 4.0);
+    let turned = Mat3::rotation(-FRAC_PI_2) * (Mat3::rotation(FRAC_PI_2) * p);
+    assert_approx_eq!(turned, p);
+    assert_approx_eq!(Mat3::rotation(FRAC_PI_2) * p, Pos2D::new(-4.0, 3.0));
+    assert_approx_ne!(turned, Pos2D::new(3.0, 4.1));
+    assert_approx_eq!(turned, Pos2D::new(3.0, 4.01), epsilon = 0.02);
+    assert_approx_eq!(Vec3D::new(1.0, 2.0, 2.0).normalize(), Vec3D::new(1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0));
+    assert_approx_eq!(DVec2D::new(1e9, 0.0), Vec2D::new(1e9 + 1.0, 0.0), max_relative = 1e-6);
+    assert_approx_eq!(Pos3D::new(1.0, 1.0, 1.0), Pos3D::new(1.0, 1.0, 1.0), ulps = 0);
+
+    // matrices, quaternions and boxes compare every component
+    let m = Mat4::rotation_y(0.7);
+    assert_approx_eq!(m * m.inverse().unwrap(), Mat4::IDENTITY);
+    assert_approx_ne!(m, Mat4::IDENTITY);
+    let q = Quat::from_axis_angle(Vec3D::new(0.0, 1.0, 0.0), 0.7);
+    assert_approx_eq!(q.to_mat4(), m);
+    assert_approx_eq!(q * q.inverse(), Quat::IDENTITY);
+    let aabb: Aabb2D = Aabb2D::new(Pos2D::ZERO, turned);
+    assert_approx_eq!(aabb, Aabb2D::new(Pos2D::ZERO, p));
+    assert_approx_eq!(Aabb3D::new(Pos3D::ZERO, Pos3D::ONE).center(), Pos3D::new(0.5, 0.5, 0.5));
+    let ray: Ray2D = Ray2D::new(Pos2D::ZERO, Vec2D::new(1.0, 1.0)).unwrap();
+    assert_approx_eq!(ray.direction(), Vec2D::new(0.5f32.sqrt(), 0.5f32.sqrt()));
+    assert_approx_eq!(ray, Ray2D::between(Pos2D::ZERO, Pos2D::new(2.0, 2.0)).unwrap());
+    let curve: QuadraticBezier = QuadraticBezier::new(Pos2D::ZERO, Pos2D::ONE, Pos2D::new(2.0, 0.0));
+    assert_approx_eq!(curve.split(0.5).0.at(1.0), curve.at(0.5));
+    assert_approx_eq!(vec![1.0f32, 2.0], vec![1.0, 2.0 + 1e-7]);
+    assert_approx_ne!(vec![1.0f32, 2.0], vec![1.0]);
+
+    // shapes compare vertex by vertex
+    let mut t1: Triangle = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 0.0), Pos2D::new(0.0, 1.0));
+    let t2 = t1;
+    t1.apply_transform(&Mat3::rotation(FRAC_PI_2));
+    t1.apply_transform(&Mat3::rotation(-FRAC_PI_2));
+    assert_approx_eq!(t1, t2);
+    let r: Rect = Rect::new(Pos2D::new(0.0, 10.0), 10.0, 10.0);
+    assert_approx_eq!(r, Rect::new(Pos2D::new(1e-6, 10.0), 10.0, 10.0));
+    assert_approx_ne!(r, Rect::new(Pos2D::new(0.0, 10.0), 10.0, 5.0));
+}
+
+#[test]
+fn test_coordinates() {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    // units
+    assert_approx_eq!(Degrees::new(180.0).to_radians(), Radians::new(PI));
+    assert_approx_eq!(Radians::new(FRAC_PI_2).to_degrees(), Degrees::new(90.0));
+    let turn: Radians = Degrees::new(45.0).into();
+    assert_approx_eq!(turn, Radians::new(FRAC_PI_4));
+    assert_approx_eq!(Radians::new(1.0) + Radians::new(0.5) * 2.0, Radians::new(2.0));
+
+    // wrapping
+    assert_approx_eq!(Radians::new(3.0 * PI).wrap_signed(), Radians::new(-PI));
+    assert_approx_eq!(Radians::new(-FRAC_PI_2).wrap_positive(), Radians::new(3.0 * FRAC_PI_2));
+    assert_approx_eq!(Radians::new(5.0 * PI + 0.25).wrap_signed(), Radians::new(-PI + 0.25));
+    assert_eq!(Radians::new(-1e-9f32).wrap_positive().value(), 0.0);
+    assert_approx_eq!(Degrees::new(-90.0).wrap_positive(), Degrees::new(270.0), epsilon = 1e-3);
+    assert_approx_eq!(Degrees::new(370.0).wrap_signed(), Degrees::new(10.0), epsilon = 1e-3);
+    // the short way from just below a full turn to just above zero crosses zero
+    let (a, b) = (Degrees::new(350.0).to_radians(), Degrees::new(10.0).to_radians());
+    assert_approx_eq!(a.shortest_to(b).to_degrees(), Degrees::new(20.0), epsilon = 1e-3);
+    assert_approx_eq!(Radians::lerp(a, b, 0.5).wrap_positive().to_degrees(), Degrees::new(0.0), epsilon = 1e-3);
+
+    // 2D polar
+    let p: Pos2D = Pos2D::new(0.0, 2.0);
+    let (r, theta) = p.cartesian_to_polar();
+    assert_approx_eq!((r, theta.value()), (2.0, FRAC_PI_2));
+    assert_approx_eq!(Pos2D::<World>::polar_to_cartesian(r, theta), p);
+    let v = Vec2D::new(-3.0, -4.0);
+    let (len, angle) = v.to_polar();
+    assert_approx_eq!(Vec2D::from_polar(len, angle), v);
+    assert_approx_eq!(Vec2D::from_angle(Radians::new(PI)), Vec2D::new(-1.0, 0.0));
+
+    // spherical, y is up and the azimuth turns from +z to +x
+    let up = Vec3D::new(0.0, 5.0, 0.0).to_spherical();
+    assert_approx_eq!([up.radius(), up.polar().value(), up.azimuth().value()], [5.0, 0.0, 0.0]);
+    let east = Vec3D::new(2.0, 0.0, 0.0).to_spherical();
+    assert_approx_eq!((east.polar().value(), east.azimuth().value()), (FRAC_PI_2, FRAC_PI_2));
+    let orbit = Spherical::new(10.0, Radians::new(FRAC_PI_4), Radians::new(3.0 * FRAC_PI_4));
+    let eye = Pos3D::from_spherical(orbit);
+    assert_approx_eq!(eye, Pos3D::new(5.0, 50f32.sqrt(), -5.0));
+    assert_approx_eq!(eye.to_spherical(), orbit);
+    // azimuths come back in -PI..PI
+    let behind = Pos3D::from_spherical(Spherical::new(1.0, Radians::new(FRAC_PI_2), Radians::new(1.5 * PI)));
+    assert_approx_eq!(behind.to_spherical().azimuth(), Radians::new(-FRAC_PI_2));
+    for p in [Pos3D::new(1.0, -2.0, 3.0), Pos3D::new(-4.0, 0.5, -0.25), Pos3D::new(0.0, -7.0, 0.0)] {
+        assert_approx_eq!(Pos3D::from_spherical(p.to_spherical()), p);
+        assert_approx_eq!(Pos3D::from_cylindrical(p.to_cylindrical()), p);
+    }
+    assert_eq!(Vec3D::<f32>::ZERO.to_spherical(), Spherical::new(0.0, Radians::ZERO, Radians::ZERO));
+
+    // cylindrical keeps the height as is
+    let c = Vec3D::new(3.0, 7.0, 4.0).to_cylindrical();
+    assert_approx_eq!((c.radius(), c.height()), (5.0, 7.0));
+    assert_approx_eq!(Vec3D::from_cylindrical(Cylindrical::new(2.0, Radians::new(FRAC_PI_2), -1.0)), Vec3D::new(2.0, -1.0, 0.0));
+
+    // f64 works the same
+    let d: DPos3D = Pos3D::new(1e6, 2e6, -3e6);
+    assert_approx_eq!(Pos3D::from_spherical(d.to_spherical()), d, max_relative = 1e-12);
+}
+
+#[test]
+fn test_geometry() {
+    use util::geometry::*;
+
+    let square = [Pos2D::new(0.0, 0.0), Pos2D::new(10.0, 0.0), Pos2D::new(10.0, 10.0), Pos2D::new(0.0, 10.0)];
+    let hole = vec![Pos2D::new(3.0, 3.0), Pos2D::new(7.0, 3.0), Pos2D::new(7.0, 7.0), Pos2D::new(3.0, 7.0)];
+
+    // area, centroid and winding
+    assert_eq!(signed_area(&square), 100.0);
+    let mut backwards = square;
+    backwards.reverse();
+    assert_eq!(signed_area(&backwards), -100.0);
+    assert_eq!(area(&backwards), 100.0);
+    assert_eq!(winding(&square), Some(Winding::CounterClockwise));
+    assert_eq!(winding(&backwards), Some(Winding::Clockwise));
+    assert_eq!(winding(&[Pos2D::<World>::ZERO, Pos2D::new(1.0, 1.0)]), None);
+    assert_approx_eq!(centroid(&square).unwrap(), Pos2D::new(5.0, 5.0));
+    let l_shape = [Pos2D::new(0.0, 0.0), Pos2D::new(2.0, 0.0), Pos2D::new(2.0, 1.0), Pos2D::new(1.0, 1.0), Pos2D::new(1.0, 2.0), Pos2D::new(0.0, 2.0)];
+    assert_eq!(area(&l_shape), 3.0);
+    assert_approx_eq!(centroid(&l_shape).unwrap(), Pos2D::new(5.0 / 6.0, 5.0 / 6.0));
+
+    // convex hull drops inside and collinear points
+    let mut cloud = square.to_vec();
+    cloud.extend([Pos2D::new(5.0, 5.0), Pos2D::new(5.0, 0.0), Pos2D::new(2.0, 8.0), Pos2D::new(10.0, 10.0)]);
+    assert_eq!(convex_hull(&cloud), square.to_vec());
+    let line = [Pos2D::<World>::new(2.0, 2.0), Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 1.0)];
+    assert_eq!(convex_hull(&line), vec![Pos2D::new(0.0, 0.0), Pos2D::new(2.0, 2.0)]);
+
+    // segments
+    let a = (Pos2D::<World>::new(0.0, 0.0), Pos2D::new(4.0, 4.0));
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(0.0, 4.0), Pos2D::new(4.0, 0.0)), Some(SegmentIntersection::Point(Pos2D::new(2.0, 2.0))));
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(4.0, 4.0), Pos2D::new(6.0, 0.0)), Some(SegmentIntersection::Point(Pos2D::new(4.0, 4.0))));
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(5.0, 0.0), Pos2D::new(6.0, 0.0)), None);
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(1.0, 0.0), Pos2D::new(5.0, 4.0)), None);
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(6.0, 6.0), Pos2D::new(2.0, 2.0)), Some(SegmentIntersection::Overlap(Pos2D::new(2.0, 2.0), Pos2D::new(4.0, 4.0))));
+    assert_eq!(segment_intersection(a.0, a.1, Pos2D::new(5.0, 5.0), Pos2D::new(6.0, 6.0)), None);
+    assert_eq!(closest_point_on_segment(Pos2D::new(0.0, 4.0), a.0, a.1), Pos2D::new(2.0, 2.0));
+    assert_eq!(closest_point_on_segment(Pos2D::new(9.0, 5.0), a.0, a.1), a.1);
+
+    // point in polygon, a pentagram winds twice around its middle
+    let star: Vec<Pos2D> = (0..5).map(|i| Pos2D::polar_to_cartesian(1.0, Radians::new(i as f32 * 4.0 * std::f32::consts::PI / 5.0))).collect();
+    assert_eq!(winding_number(Pos2D::ZERO, &star), 2);
+    assert!(!point_in_polygon(Pos2D::ZERO, &star, FillRule::EvenOdd));
+    assert!(point_in_polygon(Pos2D::ZERO, &star, FillRule::NonZero));
+    assert!(point_in_polygon(Pos2D::new(0.5, 0.5), &l_shape, FillRule::EvenOdd));
+    assert!(!point_in_polygon(Pos2D::new(1.5, 1.5), &l_shape, FillRule::NonZero));
+    assert_eq!(winding_number(Pos2D::new(5.0, 5.0), &backwards), -1);
+
+    // triangulation covers exactly the polygon and always hands back counter-clockwise triangles
+    let check = |triangles: &[Triangle], count: usize, expected_area: f32| {
+        assert_eq!(triangles.len(), count);
+        let total: f32 = triangles.iter().map(|t| signed_area(&[t.a(), t.b(), t.c()])).sum();
+        assert_approx_eq!(total, expected_area);
+        assert!(triangles.iter().all(|t| winding(&[t.a(), t.b(), t.c()]) == Some(Winding::CounterClockwise)));
+    };
+    check(&triangulate(&l_shape, &[]), 4, 3.0);
+    check(&triangulate(&backwards, &[]), 2, 100.0);
+    let with_hole = triangulate(&square, std::slice::from_ref(&hole));
+    check(&with_hole, 8, 84.0);
+    for t in &with_hole {
+        let middle = centroid(&[t.a(), t.b(), t.c()]).unwrap();
+        assert!(!point_in_polygon(middle, &hole, FillRule::NonZero));
+    }
+    // two holes, the second one given counter-clockwise
+    let left = vec![Pos2D::new(1.0, 1.0), Pos2D::new(1.0, 4.0), Pos2D::new(4.0, 4.0), Pos2D::new(4.0, 1.0)];
+    let right = vec![Pos2D::new(6.0, 6.0), Pos2D::new(9.0, 6.0), Pos2D::new(9.0, 9.0), Pos2D::new(6.0, 9.0)];
+    check(&triangulate(&square, &[left, right]), 14, 82.0);
+    assert!(triangulate(&square[..2], &[]).is_empty());
+}
+
+#[test]
+fn test_random() {
+    use util::random::*;
+
+    // matches the PCG reference demo for seed 42 on stream 54
+    let mut rng = Rng::with_stream(42, 54);
+    let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+    assert_eq!(expected.map(|_| rng.next_u32()), expected);
+
+    // same seed, same everything
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    let rolls: Vec<i32> = (0..100).map(|_| a.range(-3..4)).collect();
+    assert_eq!(rolls, (0..100).map(|_| b.range(-3..4)).collect::<Vec<i32>>());
+    assert!(rolls.iter().all(|r| (-3..4).contains(r)));
+    assert!((-3..4).all(|n| rolls.contains(&n)));
+    assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    assert_ne!(Rng::with_stream(7, 1).next_u64(), Rng::with_stream(7, 2).next_u64());
+
+    let mut rng = Rng::new(1234);
+    for _ in 0..1000 {
+        let f = rng.range(2.0..2.5f32);
+        assert!((2.0..2.5).contains(&f));
+        let u = rng.next_f64();
+        assert!((0.0..1.0).contains(&u));
+    }
+    assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+
+    // shuffles keep every item
+    let mut deck: Vec<u32> = (0..52).collect();
+    rng.shuffle(&mut deck);
+    assert_ne!(deck, (0..52).collect::<Vec<u32>>());
+    deck.sort();
+    assert_eq!(deck, (0..52).collect::<Vec<u32>>());
+    assert_eq!(rng.choose::<u32>(&[]), None);
+    assert_eq!(rng.choose(&[5]), Some(&5));
+
+    // weighted picks never land on nothing and roughly follow the weights
+    let weights = [1.0, 0.0, 3.0, -2.0];
+    let mut counts = [0; 4];
+    for _ in 0..4000 {
+        counts[rng.weighted_index(&weights).unwrap()] += 1;
+    }
+    assert_eq!((counts[1], counts[3]), (0, 0));
+    assert!((2700..3300).contains(&counts[2]));
+    assert_eq!(rng.weighted_index(&[0.0, -1.0]), None);
+    let loot = [("sword", 0.0), ("gem", 1.0)];
+    assert_eq!(rng.choose_weighted(&loot, |l| l.1).unwrap().0, "gem");
+
+    // directions and shapes
+    for _ in 0..200 {
+        assert_approx_eq!(rng.unit_vec2d().length(), 1.0);
+        assert_approx_eq!(rng.unit_vec3d().length(), 1.0);
+        let center: Pos2D = Pos2D::new(3.0, -2.0);
+        assert!((rng.pos_in_circle(center, 2.0) - center).length() <= 2.0 + 1e-5);
+        let center = Pos3D::new(1.0, 2.0, 3.0);
+        assert!((rng.pos_in_sphere(center, 0.5) - center).length() <= 0.5 + 1e-5);
+        let triangle: Triangle = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(4.0, 0.0), Pos2D::new(0.0, 4.0));
+        let p = rng.pos_in_triangle(&triangle);
+        assert!(p.x() >= 0.0 && p.y() >= 0.0 && p.x() + p.y() <= 4.0 + 1e-5);
+        let rect: Rect = Rect::new(Pos2D::new(-1.0, 1.0), 2.0, 3.0);
+        assert!(rect.bounds().contains(&rng.pos_in_rect(&rect)));
+    }
+
+    // poisson disc keeps its distance and still fills the box
+    let bounds: Aabb2D = Aabb2D::new(Pos2D::new(0.0, 0.0), Pos2D::new(20.0, 10.0));
+    let points = Rng::new(99).poisson_disc(&bounds, 1.0);
+    assert_eq!(points, Rng::new(99).poisson_disc(&bounds, 1.0));
+    assert!(points.len() > 100);
+    assert!(points.iter().all(|p| bounds.contains(p)));
+    for (i, p) in points.iter().enumerate() {
+        assert!(points[i + 1..].iter().all(|q| (*q - *p).length() >= 1.0));
+    }
+    assert!(rng.poisson_disc(&bounds, 0.0).is_empty());
+}
+
+#[test]
+fn test_noise() {
+    use graphics::window_minifb::Framebuffer;
+    use util::color::{Color, Gradient};
+    use util::noise::*;
+
+    let perlin = Perlin::new(3);
+    let simplex = Simplex::new(3);
+    let value = Value::new(3);
+    let worley = Worley::new(3, WorleyDistance::F1);
+    let grid: Vec<Pos2D> = (0..40).flat_map(|y| (0..40).map(move |x| Pos2D::new(x as f32 * 0.173 - 3.1, y as f32 * 0.137 - 2.9))).collect();
+    let grid_3d: Vec<Pos3D> = grid.iter().map(|p| Pos3D::new(p.x(), p.y(), p.x() * 0.7 - p.y())).collect();
+
+    // seeded, ranged and smooth
+    fn check<N: Noise>(noise: &N, grid: &[Pos2D], grid_3d: &[Pos3D], range: std::ops::RangeInclusive<f32>) {
+        for (&p, &q) in grid.iter().zip(grid_3d) {
+            let (a, b) = (noise.sample_2d(p), noise.sample_3d(q));
+            assert!(range.contains(&a) && range.contains(&b), "{} {} out of {:?}", a, b, range);
+            assert!((noise.sample_2d(p + Vec2D::new(0.001, 0.0)) - a).abs() < 0.02);
+            assert!((noise.sample_3d(q + Vec3D::new(0.0, 0.0, 0.001)) - b).abs() < 0.02);
+        }
+    }
+    check(&perlin, &grid, &grid_3d, -1.0..=1.0);
+    check(&simplex, &grid, &grid_3d, -1.0..=1.0);
+    check(&value, &grid, &grid_3d, -1.0..=1.0);
+    check(&worley, &grid, &grid_3d, 0.0..=1.8);
+    check(&Worley::new(3, WorleyDistance::F2MinusF1), &grid, &grid_3d, 0.0..=1.8);
+    check(&Fractal::fbm(Perlin::new(3), 5), &grid, &grid_3d, -1.0..=1.0);
+    check(&Fractal::ridged(Simplex::new(3), 4), &grid, &grid_3d, 0.0..=1.0);
+    check(&Fractal::turbulence(Value::new(3), 4), &grid, &grid_3d, 0.0..=1.0);
+
+    let p = Pos2D::<World>::new(1.37, -4.2);
+    assert_eq!(Perlin::new(3).sample_2d(p), perlin.sample_2d(p));
+    assert_ne!(Perlin::new(4).sample_2d(p), perlin.sample_2d(p));
+    assert_eq!(perlin.sample_2d(Pos2D::<World>::new(2.0, -7.0)), 0.0);
+    assert_eq!(perlin.sample_3d(Pos3D::new(2.0, -7.0, 5.0)), 0.0);
+    // the noise actually moves
+    let spread = |f: &dyn Fn(Pos2D) -> f32| grid.iter().map(|&p| f(p)).fold(f32::MIN, f32::max) - grid.iter().map(|&p| f(p)).fold(f32::MAX, f32::min);
+    assert!(spread(&|p| perlin.sample_2d(p)) > 1.0);
+    assert!(spread(&|p| simplex.sample_2d(p)) > 1.0);
+    assert!(spread(&|p| value.sample_2d(p)) > 1.0);
+    assert!(spread(&|p| worley.sample_2d(p)) > 0.5);
+
+    // one octave of fbm is the base noise
+    assert_eq!(Fractal::fbm(Perlin::new(3), 1).sample_2d(p), perlin.sample_2d(p));
+    let warped = Warp::new(Simplex::new(3), Perlin::new(9), 0.5);
+    assert_eq!(warped.sample_2d(p), simplex.sample_2d(p + warped.offset_2d(p)));
+    assert!(warped.offset_2d(p).length() <= 0.5 * 2f32.sqrt());
+    assert_ne!(warped.sample_3d(Pos3D::new(0.3, 0.2, 0.1)), simplex.sample_3d(Pos3D::new(0.3, 0.2, 0.1)));
+
+    // gradients
+    let (black, white, red) = (Color::new(0, 0, 0, 255), Color::new(255, 255, 255, 255), Color::new(255, 0, 0, 255));
+    assert!(Gradient::new(&[]).is_none());
+    let gradient = Gradient::new(&[(1.0, red), (-1.0, black), (0.0, white)]).unwrap();
+    assert_eq!(gradient.at(-5.0), black);
+    assert_eq!(gradient.at(0.0), white);
+    assert_eq!(gradient.at(0.5), Color::new(255, 128, 128, 255));
+    assert_eq!(gradient.at(5.0), red);
+    assert_eq!(Gradient::between(black, white).at(0.5), Color::new(128, 128, 128, 255));
+
+    // filling a region clips to the framebuffer and leaves the rest alone
+    let mut framebuffer = Framebuffer::new(16, 8);
+    fill(&mut framebuffer, 4..100, 2..6, &simplex, 0.1, &gradient);
+    for y in 0..8 {
+        for x in 0..16 {
+            let expected = if x >= 4 && (2..6).contains(&y) {
+                gradient.at(simplex.sample_2d(Pos2D::<Screen>::new(x as f32 * 0.1, y as f32 * 0.1))).to_u32()
+            } else {
+                0
+            };
+            assert_eq!(framebuffer.get_pixel(x, y), expected);
+        }
+    }
+}
+
+#[test]
+fn test_integrate() {
+    use util::integrate::*;
+
+    // unit spring, energy should stay 0.5 forever
+    let spring = |x: Pos2D, _v: Vec2D| -(x - Pos2D::ZERO);
+    let energy = |b: &Body<Pos2D>| 0.5 * (b.velocity().length_squared() + (b.position() - Pos2D::ZERO).length_squared());
+    let start = Body::new(Pos2D::new(1.0, 0.0), Vec2D::new(0.0, 0.0));
+    // ten periods at dt = 0.01
+    let steps = (10.0 * std::f32::consts::TAU / 0.01) as usize;
+    let drift = |integrator: Integrator| {
+        let run = integrator.trajectory(start, 0.01, steps, spring);
+        run.iter().map(|b| (energy(b) - 0.5).abs() / 0.5).fold(0.0, f32::max)
+    };
+    assert!(drift(Integrator::Euler) > 0.5);
+    assert!(drift(Integrator::SemiImplicitEuler) < 0.01);
+    assert!(drift(Integrator::Verlet) < 1e-3);
+    assert!(drift(Integrator::Rk4) < 1e-4);
+    // and ends up back where it started
+    let end = Integrator::Rk4.trajectory(start, 0.01, steps, spring)[steps];
+    assert!((end.position() - start.position()).length() < 1e-2);
+
+    // projectile under gravity, exact answer is x0 + v0 t + g t^2 / 2
+    let gravity = Vec3D::new(0.0, -9.81, 0.0);
+    let launch = Body::new(Pos3D::new(0.0, 2.0, 0.0), Vec3D::new(3.0, 12.0, -1.0));
+    let t = 2.0;
+    let exact = launch.position() + launch.velocity() * t + gravity * (0.5 * t * t);
+    let land = |integrator: Integrator| {
+        let mut body = launch;
+        for _ in 0..200 {
+            body.step(integrator, t / 200.0, |_, _| gravity);
+        }
+        body
+    };
+    assert!((land(Integrator::Verlet).position() - exact).length() < 1e-3);
+    assert!((land(Integrator::Rk4).position() - exact).length() < 1e-3);
+    assert!((land(Integrator::Euler).position() - exact).length() > 0.05);
+    assert!((land(Integrator::SemiImplicitEuler).position() - exact).length() > 0.05);
+    assert_approx_eq!(land(Integrator::Verlet).velocity(), launch.velocity() + gravity * t, epsilon = 1e-3);
+    // kinetic + potential energy of the projectile holds for the second order methods
+    let projectile_energy = |b: Body<Pos3D>| 0.5 * b.velocity().length_squared() + 9.81 * b.position().y();
+    assert!((projectile_energy(land(Integrator::Verlet)) - projectile_energy(launch)).abs() < 1e-2);
+
+    // drag only depends on velocity and bleeds energy off
+    let mut falling = Body::new(Pos2D::<World>::new(0.0, 100.0), Vec2D::new(0.0, 0.0));
+    for _ in 0..1000 {
+        falling.step(Integrator::SemiImplicitEuler, 0.01, |_, v| Vec2D::new(0.0, -9.81) - v * 0.5);
+    }
+    // terminal velocity is g / drag
+    assert_approx_eq!(falling.velocity().dy(), -19.62, epsilon = 0.2);
+}
+
+#[test]
+fn test_round_shapes() {
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+    use components::shapes::{Arc, Circle, Ellipse, Pie};
+    use graphics::window_minifb::Framebuffer;
+
+    // hit testing
+    let mut circle: Circle = Circle::new(Pos2D::new(1.0, 2.0), 3.0);
+    assert!(circle.inside_eh(&Pos2D::new(1.0, 2.0)));
+    assert!(circle.inside_eh(&Pos2D::new(4.0, 2.0)));
+    assert!(!circle.inside_eh(&Pos2D::new(3.2, 4.2)));
+    assert_eq!(circle.bounds(), Aabb2D::new(Pos2D::new(-2.0, -1.0), Pos2D::new(4.0, 5.0)));
+    let hit = circle.intersect_ray(&Ray2D::new(Pos2D::new(-9.0, 2.0), Vec2D::new(1.0, 0.0)).unwrap()).unwrap();
+    assert_approx_eq!(hit.point(), Pos2D::new(-2.0, 2.0));
+
+    let ellipse: Ellipse = Ellipse::new(Pos2D::ZERO, 4.0, 2.0);
+    assert!(ellipse.inside_eh(&Pos2D::new(4.0, 0.0)));
+    assert!(ellipse.inside_eh(&Pos2D::new(0.0, -2.0)));
+    assert!(!ellipse.inside_eh(&Pos2D::new(3.0, 1.5)));
+    assert!(!Ellipse::<World>::new(Pos2D::ZERO, 4.0, 0.0).inside_eh(&Pos2D::ZERO));
+
+    let quarter: Pie = Pie::new(Pos2D::ZERO, 2.0, 0.0, FRAC_PI_2);
+    assert!(quarter.inside_eh(&Pos2D::ZERO));
+    assert!(quarter.inside_eh(&Pos2D::new(1.0, 1.0)));
+    assert!(!quarter.inside_eh(&Pos2D::new(-1.0, 1.0)));
+    assert!(!quarter.inside_eh(&Pos2D::new(1.5, 1.5)));
+    assert_approx_eq!(quarter.bounds().max(), Pos2D::new(2.0, 2.0));
+    assert_approx_eq!(quarter.bounds().min(), Pos2D::ZERO);
+    let clockwise: Pie = Pie::new(Pos2D::ZERO, 2.0, 0.0, -FRAC_PI_2);
+    assert!(clockwise.inside_eh(&Pos2D::new(1.0, -1.0)));
+    assert!(!clockwise.inside_eh(&Pos2D::new(1.0, 1.0)));
+    assert!(Pie::<World>::new(Pos2D::ZERO, 1.0, 1.5 * PI, PI).contains_angle(0.1));
+
+    // a filled arc is the piece between the curve and its chord
+    let half: Arc = Arc::new(Pos2D::ZERO, 2.0, 0.0, PI);
+    assert!(half.inside_eh(&Pos2D::new(0.0, 0.5)));
+    assert!(!half.inside_eh(&Pos2D::new(0.0, -0.5)));
+    let small: Arc = Arc::new(Pos2D::ZERO, 2.0, 0.0, FRAC_PI_2);
+    assert!(!small.inside_eh(&Pos2D::new(0.4, 0.4)));
+    assert!(small.inside_eh(&Pos2D::new(1.2, 1.2)));
+    let big: Arc = Arc::new(Pos2D::ZERO, 2.0, 0.0, 1.5 * PI);
+    assert!(big.inside_eh(&Pos2D::ZERO));
+    assert!(!big.inside_eh(&Pos2D::new(1.2, -1.2)));
+    assert_approx_eq!(small.end_point(), Pos2D::new(0.0, 2.0));
+    assert_approx_eq!(big.bounds().min(), Pos2D::new(-2.0, -2.0));
+
+    // moving and reshaping
+    circle.translate(1.0, -2.0);
+    assert_eq!(circle, Circle::new(Pos2D::new(2.0, 0.0), 3.0));
+    circle.transform(vec![Pos2D::ZERO, Pos2D::new(0.0, 5.0)]);
+    assert_eq!(circle, Circle::new(Pos2D::ZERO, 5.0));
+    circle.lerp_transform(0.5, vec![Pos2D::new(2.0, 0.0), Pos2D::new(3.0, 0.0)]);
+    assert_eq!(circle, Circle::new(Pos2D::new(1.0, 0.0), 3.0));
+    let mut stretched = ellipse;
+    stretched.transform(vec![Pos2D::new(1.0, 1.0), Pos2D::new(-2.0, 2.0)]);
+    assert_eq!(stretched, Ellipse::new(Pos2D::new(1.0, 1.0), 3.0, 1.0));
+    let mut turned = clockwise;
+    turned.transform(turned.points());
+    assert_approx_eq!(turned, clockwise);
+    turned.lerp_steps_translate(4.0, 0.0, 2);
+    assert_approx_eq!(turned.center(), Pos2D::new(2.0, 0.0));
+    // a whole ring has its start and end points on top of each other and keeps its sweep through transforms
+    let mut ring_arc: Arc = Arc::new(Pos2D::ZERO, 10.0, 0.0, TAU);
+    ring_arc.transform(ring_arc.points());
+    assert_eq!(ring_arc.sweep(), TAU);
+    let mut ring_pie: Pie = Pie::new(Pos2D::ZERO, 10.0, 1.0, -TAU);
+    ring_pie.lerp_transform(0.5, vec![Pos2D::new(4.0, 0.0), Pos2D::new(14.0, 0.0), Pos2D::new(14.0, 0.0)]);
+    assert_eq!(ring_pie.sweep(), -TAU);
+    assert_approx_eq!(ring_pie.center(), Pos2D::new(2.0, 0.0));
+    assert!(ring_pie.inside_eh(&Pos2D::new(2.0, 5.0)) && ring_pie.inside_eh(&Pos2D::new(-5.0, 0.0)));
+
+    // rasterizing, pixels that light up are the ones the midpoint rim wraps
+    let cam = Camera2D::new(Pos2D::ZERO);
+    let ring: Circle = Circle::new(Pos2D::ZERO, 10.0);
+    let mut filled = Framebuffer::new(64, 64);
+    ring.draw_abs(&mut filled, 1, &cam);
+    let filled = lit(&filled);
+    // the rim pixels are filled too so it covers about a radius of 10.5
+    assert!(((PI * 10.5 * 10.5) as usize - 15..(PI * 10.5 * 10.5) as usize + 15).contains(&filled.len()), "{}", filled.len());
+    // screen pixel 32, 32 holds the world origin
+    let distance = |&(x, y): &(usize, usize)| ((x as f32 - 32.0).powi(2) + (y as f32 - 32.0).powi(2)).sqrt();
+    assert!(filled.iter().all(|p| distance(p) <= 10.5));
+    let mut outlined = Framebuffer::new(64, 64);
+    ring.draw_outline_abs(&mut outlined, 1, &cam);
+    let outlined = lit(&outlined);
+    assert!((50..70).contains(&outlined.len()), "{}", outlined.len());
+    assert!(outlined.iter().all(|p| (9.5..=10.5).contains(&distance(p))));
+    assert!(outlined.iter().all(|p| filled.contains(p)));
+
+    // a huge circle only draws the rows on the framebuffer, its top is a single pixel and the next row is already wider than the screen
+    let huge: Circle = Circle::new(Pos2D::new(0.0, -3e6), 3_000_010.0);
+    assert!(huge.render_guard(&cam, 64.0, 64.0));
+    let mut filled = Framebuffer::new(64, 64);
+    huge.draw_abs(&mut filled, 1, &cam);
+    assert!(lit(&filled).iter().all(|&(x, y)| y >= 23 || (x, y) == (32, 22)));
+    assert_eq!(lit(&filled).len(), 64 * 41 + 1);
+    let mut outlined = Framebuffer::new(64, 64);
+    huge.draw_outline_abs(&mut outlined, 1, &cam);
+    let mut rim = lit(&outlined);
+    rim.sort_by_key(|&(x, y)| (y, x));
+    // the pixel under the top one is inside the rim rather than on it
+    assert_eq!(rim, [(32, 22)].into_iter().chain((0..64).filter(|&x| x != 32).map(|x| (x, 23))).collect::<Vec<_>>());
+    // radii still walked along the midpoint rim but big enough to overflow its decision values in i64
+    let big: Circle = Circle::new(Pos2D::new(0.0, -59_990.0), 60_000.0);
+    let mut filled = Framebuffer::new(64, 64);
+    big.draw_abs(&mut filled, 1, &cam);
+    assert!(lit(&filled).iter().all(|&(_, y)| y >= 22) && lit(&filled).contains(&(32, 22)));
+    assert_eq!(lit(&filled).iter().filter(|&&(_, y)| y >= 30).count(), 64 * 34);
+    let mut outlined = Framebuffer::new(64, 64);
+    big.draw_outline_abs(&mut outlined, 1, &cam);
+    assert!(lit(&outlined).contains(&(32, 22)) && lit(&outlined).iter().all(|p| filled.get_pixel(p.0, p.1) != 0));
+    let mut covered = Framebuffer::new(64, 64);
+    Circle::<World>::new(Pos2D::ZERO, 60_000.0).draw_abs(&mut covered, 1, &cam);
+    assert_eq!(lit(&covered).len(), 64 * 64);
+    let mut outlined = Framebuffer::new(64, 64);
+    Circle::<World>::new(Pos2D::ZERO, 60_000.0).draw_outline_abs(&mut outlined, 1, &cam);
+    assert!(lit(&outlined).is_empty());
+    let mut screen = Framebuffer::new(1000, 1000);
+    Ellipse::<Normalized>::new(Pos2D::new(0.5, 0.5), 60.0, 60.0).draw(&mut screen, 1);
+    assert_eq!(lit(&screen).len(), 1000 * 1000);
+
+    let mut flat = Framebuffer::new(64, 64);
+    Ellipse::<World>::new(Pos2D::new(0.0, 5.0), 1e9, 3.0).draw_outline_abs(&mut flat, 1, &cam);
+    assert_eq!(lit(&flat).len(), 2 * 63 + 2);
+
+    // normalized shapes stretch with the framebuffer
+    let mut wide = Framebuffer::new(64, 32);
+    Circle::<Normalized>::new(Pos2D::new(0.5, 0.5), 0.25).draw(&mut wide, 1);
+    let count = lit(&wide).len() as f32;
+    assert!((count - PI * 16.5 * 8.5).abs() < 25.0, "{}", count);
+
+    // a pie only lights its slice, on screen up is -y
+    let mut slice = Framebuffer::new(64, 64);
+    Pie::<World>::new(Pos2D::ZERO, 10.0, 0.0, FRAC_PI_2).draw_abs(&mut slice, 1, &cam);
+    let slice = lit(&slice);
+    assert!(slice.iter().all(|&(x, y)| x >= 32 && y <= 32));
+    assert!((60..100).contains(&slice.len()), "{}", slice.len());
+    let mut edges = Framebuffer::new(64, 64);
+    Pie::<World>::new(Pos2D::ZERO, 10.0, 0.0, FRAC_PI_2).draw_outline_abs(&mut edges, 1, &cam);
+    assert_eq!(edges.get_pixel(32, 32), 1);
+    assert_eq!(edges.get_pixel(37, 32), 1);
+    assert_eq!(edges.get_pixel(32, 27), 1);
+    let mut segment = Framebuffer::new(64, 64);
+    half.draw_abs(&mut segment, 1, &cam);
+    assert!(lit(&segment).iter().all(|&(_, y)| y <= 32));
+
+    // culled and clipped
+    let far: Ellipse = Ellipse::new(Pos2D::new(500.0, 0.0), 20.0, 10.0);
+    assert!(!far.render_guard(&cam, 64.0, 64.0));
+    let mut fb = Framebuffer::new(64, 64);
+    far.draw_abs(&mut fb, 1, &cam);
+    // centered on the top left pixel so only its bottom right quarter lands on the framebuffer
+    Circle::<World>::new(Pos2D::new(-32.0, 32.0), 8.0).draw_abs(&mut fb, 1, &cam);
+    let corner = lit(&fb);
+    assert!(corner.contains(&(0, 0)) && corner.contains(&(8, 0)) && corner.contains(&(0, 8)));
+    assert!(corner.iter().all(|&(x, y)| x * x + y * y <= 72));
+}
+
+#[test]
+fn test_lines() {
+    use components::shapes::{Line, LineCap, LineJoin, Polyline, Stroke};
+    use graphics::window_minifb::Framebuffer;
+
+    // geometry
+    let mut line: Line = Line::new(Pos2D::new(0.0, 0.0), Pos2D::new(4.0, 3.0));
+    assert_eq!(line.length(), 5.0);
+    assert!(line.inside_eh(&Pos2D::new(2.0, 1.5)));
+    assert!(!line.inside_eh(&Pos2D::new(2.0, 1.6)));
+    assert!(!line.inside_eh(&Pos2D::new(8.0, 6.0)));
+    assert_approx_eq!(line.distance(&Pos2D::new(-3.0, -4.0)), 5.0);
+    let hit = line.intersect_ray(&Ray2D::new(Pos2D::new(4.0, 0.0), Vec2D::new(0.0, 1.0)).unwrap()).unwrap();
+    assert_approx_eq!(hit.point(), Pos2D::new(4.0, 3.0));
+    line.translate(1.0, 1.0);
+    line.lerp_transform(0.5, vec![Pos2D::new(1.0, 1.0), Pos2D::new(1.0, 4.0)]);
+    assert_approx_eq!(line, Line::new(Pos2D::new(1.0, 1.0), Pos2D::new(3.0, 4.0)));
+
+    let mut path: Polyline = Polyline::new(vec![Pos2D::new(0.0, 0.0), Pos2D::new(3.0, 0.0)]);
+    path.push(Pos2D::new(3.0, 4.0));
+    assert_eq!(path.lines().len(), 2);
+    assert_eq!(path.length(), 7.0);
+    assert!(path.inside_eh(&Pos2D::new(3.0, 2.0)));
+    assert!(!path.inside_eh(&Pos2D::new(1.0, 1.0)));
+    path.set_closed(true);
+    assert_eq!(path.length(), 12.0);
+    assert!(path.inside_eh(&Pos2D::new(1.5, 2.0)));
+    assert_approx_eq!(path.distance(&Pos2D::new(5.0, 2.0)), 2.0);
+    assert_eq!(path.bounds(), Some(Aabb2D::new(Pos2D::ZERO, Pos2D::new(3.0, 4.0))));
+    assert_eq!(Polyline::<World>::new(Vec::new()).distance(&Pos2D::ZERO), f32::INFINITY);
+    let outline = Rect::<World>::new(Pos2D::ZERO, 4.0, 2.0).outline();
+    assert!(outline.is_closed());
+    assert_eq!(outline.lines().len(), 4);
+    assert_eq!(outline.length(), 12.0);
+    assert_eq!(Triangle::<World>::new(Pos2D::ZERO, Pos2D::new(1.0, 0.0), Pos2D::new(0.0, 1.0)).outline().lines().len(), 3);
+
+    // rasterizing, the world origin is the corner between pixels 31 and 32 on a 64 x 64 framebuffer
+    let white = 0xffffff;
+    let cam = Camera2D::new(Pos2D::ZERO);
+    let flat: Line = Line::new(Pos2D::new(-10.0, -0.5), Pos2D::new(10.0, -0.5));
+
+    // bresenham, one pixel per column
+    let mut fb = Framebuffer::new(64, 64);
+    flat.draw_abs(&mut fb, white, &cam);
+    assert_eq!(lit(&fb), (22..=42).map(|x| (x, 32)).collect::<Vec<_>>());
+    let mut fb = Framebuffer::new(64, 64);
+    Line::<World>::new(Pos2D::new(-10.0, -10.0), Pos2D::new(10.0, 10.0)).draw_abs(&mut fb, white, &cam);
+    let diagonal = lit(&fb);
+    assert_eq!(diagonal.len(), 21);
+    assert!(diagonal.iter().all(|&(x, y)| x + y == 64));
+    // clipped before stepping so a huge line is as quick as a short one
+    let mut fb = Framebuffer::new(64, 64);
+    Line::<World>::new(Pos2D::new(-1e9, -0.5), Pos2D::new(1e9, -0.5)).draw_abs(&mut fb, white, &cam);
+    assert_eq!(lit(&fb).len(), 64);
+    assert!(!Line::<World>::new(Pos2D::new(100.0, 0.0), Pos2D::new(200.0, 0.0)).render_guard(&cam, 64.0, 64.0));
+    let mut fb = Framebuffer::new(10, 10);
+    Line::<Normalized>::new(Pos2D::new(0.0, 0.55), Pos2D::new(1.0, 0.55)).draw(&mut fb, white);
+    assert_eq!(lit(&fb), (0..10).map(|x| (x, 5)).collect::<Vec<_>>());
+
+    // wu, a line through pixel middles is solid and a slanted one shares each column between two pixels
+    let mut fb = Framebuffer::new(64, 64);
+    flat.draw_aa_abs(&mut fb, white, &cam);
+    assert_eq!(lit(&fb), (22..42).map(|x| (x, 32)).collect::<Vec<_>>());
+    assert!(lit(&fb).iter().all(|&(x, y)| fb.get_pixel(x, y) == white));
+    let mut fb = Framebuffer::new(64, 64);
+    Line::<World>::new(Pos2D::new(-20.0, -5.0), Pos2D::new(20.0, 5.0)).draw_aa_abs(&mut fb, white, &cam);
+    for x in 14..50 {
+        let column: Vec<u32> = (0..64).map(|y| fb.get_pixel(x, y) & 0xff).filter(|&v| v > 0).collect();
+        assert!(column.len() <= 2, "{:?}", column);
+        let ink: u32 = column.iter().sum();
+        assert!((250..=260).contains(&ink), "{}", ink);
+    }
+
+    // thick lines, 4 wide covers two rows either side of the origin
+    let thick: Line = Line::new(Pos2D::new(-10.0, 0.0), Pos2D::new(10.0, 0.0));
+    let mut stroke = Stroke::new(4.0);
+    stroke.set_antialias(false);
+    let count = |stroke: &Stroke| {
+        let mut fb = Framebuffer::new(64, 64);
+        thick.draw_stroke_abs(&mut fb, white, stroke, &cam);
+        lit(&fb).len()
+    };
+    assert_eq!(count(&stroke), 20 * 4);
+    stroke.set_cap(LineCap::Square);
+    assert_eq!(count(&stroke), 24 * 4);
+    stroke.set_cap(LineCap::Round);
+    assert!((81..96).contains(&count(&stroke)));
+
+    // joins on a right angle turn, only the miter fills the outside corner
+    let elbow: Polyline = Polyline::new(vec![Pos2D::new(-10.0, 0.0), Pos2D::ZERO, Pos2D::new(0.0, 10.0)]);
+    let corner = |join: LineJoin, miter_limit: f32| {
+        let mut stroke = Stroke::new(6.0);
+        stroke.set_antialias(false);
+        stroke.set_join(join);
+        stroke.set_miter_limit(miter_limit);
+        let mut fb = Framebuffer::new(64, 64);
+        elbow.draw_stroke_abs(&mut fb, white, &stroke, &cam);
+        (fb.get_pixel(34, 34), fb.get_pixel(33, 33))
+    };
+    assert_eq!(corner(LineJoin::Miter, 4.0), (white, white));
+    assert_eq!(corner(LineJoin::Miter, 1.0), (0, white));
+    assert_eq!(corner(LineJoin::Bevel, 4.0), (0, white));
+    assert_eq!(corner(LineJoin::Round, 4.0), (0, white));
+
+    // dashes, 4 on and 4 off along 20 pixels
+    let mut dashed = Stroke::new(2.0);
+    dashed.set_antialias(false);
+    dashed.set_dashes(&[4.0], 0.0);
+    let mut fb = Framebuffer::new(64, 64);
+    thick.draw_stroke_abs(&mut fb, white, &dashed, &cam);
+    let columns = |fb: &Framebuffer| (0..64).filter(|&x| fb.get_pixel(x, 31) != 0).collect::<Vec<_>>();
+    assert_eq!(columns(&fb), [22, 23, 24, 25, 30, 31, 32, 33, 38, 39, 40, 41]);
+    assert_eq!(lit(&fb).len(), 24);
+    dashed.set_dashes(&[4.0, 4.0], 2.0);
+    let mut fb = Framebuffer::new(64, 64);
+    thick.draw_stroke_abs(&mut fb, white, &dashed, &cam);
+    assert_eq!(columns(&fb), [22, 23, 28, 29, 30, 31, 36, 37, 38, 39]);
+    // too small to dash is solid
+    dashed.set_dashes(&[0.1, 0.1], 0.0);
+    assert_eq!(count(&dashed), 20 * 2);
+
+    // closed outlines go all the way around, the middle stays empty
+    let mut fb = Framebuffer::new(64, 64);
+    let mut border = Stroke::new(2.0);
+    border.set_antialias(false);
+    Rect::<World>::new(Pos2D::new(-10.0, 10.0), 20.0, 20.0).outline().draw_stroke_abs(&mut fb, white, &border, &cam);
+    assert_eq!(fb.get_pixel(22, 22), white);
+    assert_eq!(fb.get_pixel(41, 41), white);
+    assert_eq!(fb.get_pixel(32, 32), 0);
+    assert_eq!(lit(&fb).len(), 22 * 22 - 18 * 18);
+
+    // antialiased, a 1 wide line on the boundary between two rows is half in each
+    let mut fb = Framebuffer::new(64, 64);
+    thick.draw_stroke_abs(&mut fb, white, &Stroke::new(1.0), &cam);
+    assert_eq!(fb.get_pixel(30, 31), 0x808080);
+    assert_eq!(fb.get_pixel(30, 32), 0x808080);
+    assert_eq!(fb.get_pixel(30, 30), 0);
+}
+
+#[test]
+fn test_polygon() {
+    use components::shapes::Polygon;
+    use graphics::window_minifb::Framebuffer;
+    use util::geometry::FillRule;
+
+    let square = |min: f32, max: f32| vec![Pos2D::new(min, min), Pos2D::new(max, min), Pos2D::new(max, max), Pos2D::new(min, max)];
+
+    // an L, concave so the notch is outside
+    let mut l_shape: Polygon = Polygon::new(vec![
+        Pos2D::new(0.0, 0.0),
+        Pos2D::new(4.0, 0.0),
+        Pos2D::new(4.0, 1.0),
+        Pos2D::new(1.0, 1.0),
+        Pos2D::new(1.0, 3.0),
+        Pos2D::new(0.0, 3.0),
+    ]);
+    assert!(l_shape.inside_eh(&Pos2D::new(3.5, 0.5)));
+    assert!(l_shape.inside_eh(&Pos2D::new(0.5, 2.5)));
+    assert!(!l_shape.inside_eh(&Pos2D::new(2.0, 2.0)));
+    assert_eq!(l_shape.area(), 6.0);
+    assert_eq!(l_shape.bounds(), Some(Aabb2D::new(Pos2D::ZERO, Pos2D::new(4.0, 3.0))));
+    assert_eq!(l_shape.triangulate().len(), 4);
+    let hit = l_shape.intersect_ray(&Ray2D::new(Pos2D::new(3.0, 3.0), Vec2D::new(0.0, -1.0)).unwrap()).unwrap();
+    assert_approx_eq!(hit.point(), Pos2D::new(3.0, 1.0));
+
+    // editing corners
+    l_shape.set_vertex(2, Pos2D::new(4.0, 3.0));
+    assert!(l_shape.inside_eh(&Pos2D::new(3.5, 2.0)));
+    l_shape.insert_vertex(3, Pos2D::new(2.0, 2.0));
+    assert_eq!(l_shape.outline().len(), 7);
+    assert_eq!(l_shape.remove_vertex(3), Pos2D::new(2.0, 2.0));
+    l_shape.translate(1.0, 1.0);
+    assert_eq!(l_shape.outline()[0], Pos2D::new(1.0, 1.0));
+
+    // holes wind against the outline whichever way they were given so both rules cut them out
+    let mut framed: Polygon = Polygon::new_with_holes(square(-10.0, 10.0), vec![square(-5.0, 5.0)]);
+    assert_eq!(framed.holes()[0][0], Pos2D::new(-5.0, 5.0));
+    assert_eq!(framed.area(), 300.0);
+    assert!(framed.inside_eh(&Pos2D::new(-7.0, 0.0)));
+    assert!(!framed.inside_eh(&Pos2D::ZERO));
+    framed.set_fill_rule(FillRule::EvenOdd);
+    assert!(!framed.inside_eh(&Pos2D::ZERO));
+    assert_eq!(framed.points().len(), 8);
+    assert_eq!(framed.outlines().len(), 2);
+    assert_eq!(framed.triangulate().len(), 8);
+
+    // the same number of points moves every corner, holes included
+    let mut moved = framed.clone();
+    moved.transform(framed.points().iter().map(|&p| p + Vec2D::new(1.0, 0.0)).collect());
+    let mut shifted = framed.clone();
+    shifted.translate(1.0, 0.0);
+    assert_eq!(moved, shifted);
+    moved.transform(square(0.0, 1.0));
+    assert!(moved.holes().is_empty());
+    moved.lerp_transform(0.5, square(0.0, 3.0));
+    assert_approx_eq!(moved, Polygon::new(square(0.0, 2.0)));
+
+    // a pentagram winds twice around its middle
+    let star: Vec<Pos2D> = (0..5)
+        .map(|i| {
+            let theta = std::f32::consts::FRAC_PI_2 + i as f32 * 4.0 * std::f32::consts::PI / 5.0;
+            Pos2D::new(20.0 * theta.cos(), 20.0 * theta.sin())
+        })
+        .collect();
+    let mut pentagram: Polygon = Polygon::new(star);
+    assert!(pentagram.inside_eh(&Pos2D::ZERO));
+    assert!(pentagram.inside_eh(&Pos2D::new(0.0, 15.0)));
+    pentagram.set_fill_rule(FillRule::EvenOdd);
+    assert!(!pentagram.inside_eh(&Pos2D::ZERO));
+    assert!(pentagram.inside_eh(&Pos2D::new(0.0, 15.0)));
+
+    // rasterizing lights exactly the pixels whose middles are inside
+    let cam = Camera2D::new(Pos2D::ZERO);
+    let matches_inside = |polygon: &Polygon| {
+        let mut fb = Framebuffer::new(64, 64);
+        polygon.draw_abs(&mut fb, 1, &cam);
+        (0..64).all(|x| {
+            (0..64).all(|y| {
+                let middle = cam.screen_to_world(Pos2D::new(x as f32 + 0.5, y as f32 + 0.5), 64.0, 64.0);
+                (fb.get_pixel(x, y) != 0) == polygon.inside_eh(&middle)
+            })
+        })
+    };
+    let mut fb = Framebuffer::new(64, 64);
+    framed.draw_abs(&mut fb, 1, &cam);
+    assert_eq!(lit(&fb).len(), 300);
+    assert_eq!(fb.get_pixel(32, 32), 0);
+    assert!(matches_inside(&pentagram));
+    pentagram.set_fill_rule(FillRule::NonZero);
+    assert!(matches_inside(&pentagram));
+    let mut arrow: Polygon = Polygon::new(vec![
+        Pos2D::new(-20.3, -3.1),
+        Pos2D::new(5.2, -2.9),
+        Pos2D::new(4.7, -12.6),
+        Pos2D::new(21.1, 0.4),
+        Pos2D::new(4.9, 13.3),
+        Pos2D::new(5.1, 3.2),
+        Pos2D::new(-19.8, 2.7),
+    ]);
+    arrow.add_hole(vec![Pos2D::new(8.1, -1.3), Pos2D::new(12.4, 0.2), Pos2D::new(8.3, 2.1)]);
+    assert!(matches_inside(&arrow));
+
+    // two halves of a square sharing the diagonal paint every pixel exactly once
+    let first: Polygon = Polygon::new(vec![Pos2D::new(-9.3, -9.3), Pos2D::new(9.3, -9.3), Pos2D::new(9.3, 9.3)]);
+    let second: Polygon = Polygon::new(vec![Pos2D::new(-9.3, -9.3), Pos2D::new(9.3, 9.3), Pos2D::new(-9.3, 9.3)]);
+    let (mut a, mut b, mut whole) = (Framebuffer::new(64, 64), Framebuffer::new(64, 64), Framebuffer::new(64, 64));
+    first.draw_abs(&mut a, 1, &cam);
+    second.draw_abs(&mut b, 1, &cam);
+    Polygon::<World>::new(square(-9.3, 9.3)).draw_abs(&mut whole, 1, &cam);
+    let (a, b) = (lit(&a), lit(&b));
+    assert!(a.iter().all(|p| !b.contains(p)));
+    assert_eq!(a.len() + b.len(), lit(&whole).len());
+
+    // culled, clipped and normalized
+    assert!(!Polygon::<World>::new(square(100.0, 120.0)).render_guard(&cam, 64.0, 64.0));
+    let mut fb = Framebuffer::new(64, 64);
+    Polygon::<World>::new(square(-1000.0, 1000.0)).draw_abs(&mut fb, 1, &cam);
+    assert_eq!(lit(&fb).len(), 64 * 64);
+    let mut fb = Framebuffer::new(8, 8);
+    Polygon::<Normalized>::new(square(0.25, 0.75).iter().map(|p| Pos2D::new(p.x(), p.y())).collect()).draw(&mut fb, 1);
+    assert_eq!(lit(&fb), (2..6).flat_map(|x| (2..6).map(move |y| (x, y))).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_triangle_fill() {
+    use graphics::window_minifb::Framebuffer;
+    use util::random::Rng;
+
+    let cam = Camera2D::new(Pos2D::ZERO);
+
+    // either winding draws and contains the same thing
+    let (a, b, c) = (Pos2D::new(-10.3, -8.1), Pos2D::new(12.7, -3.4), Pos2D::new(-2.2, 14.9));
+    let ccw: Triangle = Triangle::new(a, b, c);
+    let cw: Triangle = Triangle::new(a, c, b);
+    let (mut first, mut second) = (Framebuffer::new(64, 64), Framebuffer::new(64, 64));
+    ccw.draw_abs(&mut first, 1, &cam);
+    cw.draw_abs(&mut second, 1, &cam);
+    assert!(lit(&first).len() > 200);
+    assert_eq!(lit(&first), lit(&second));
+    assert!(cw.inside_eh(&Pos2D::ZERO) && ccw.inside_eh(&Pos2D::ZERO));
+    assert!(!Triangle::<World>::new(a, b, a).inside_eh(&a));
+
+    // a point on a shared edge is in exactly one of the two triangles, whichever way they wind
+    let (p, q) = (Pos2D::new(0.0, 0.0), Pos2D::new(4.0, 4.0));
+    let above: Triangle = Triangle::new(p, q, Pos2D::new(0.0, 4.0));
+    let below: Triangle = Triangle::new(p, Pos2D::new(4.0, 0.0), q);
+    for t in [0.25, 0.5, 0.75] {
+        let on_edge = Pos2D::lerp(p, q, t);
+        assert!(above.inside_eh(&on_edge) != below.inside_eh(&on_edge));
+        assert!(above.inside_eh(&on_edge) != Triangle::new(q, p, Pos2D::new(4.0, 0.0)).inside_eh(&on_edge));
+    }
+    // top and left edges as they look on screen are in, world y is flipped so the top is the high edge
+    let square: Triangle = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(0.0, 4.0), Pos2D::new(4.0, 4.0));
+    assert!(square.inside_eh(&Pos2D::new(1.0, 4.0)));
+    assert!(square.inside_eh(&Pos2D::new(0.0, 3.0)));
+    assert!(!Triangle::<World>::new(Pos2D::new(4.0, 0.0), Pos2D::new(0.0, 0.0), Pos2D::new(4.0, 4.0)).inside_eh(&Pos2D::new(2.0, 0.0)));
+    let screen_square: Triangle<Normalized> = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(0.5, 0.0), Pos2D::new(0.0, 0.5));
+    assert!(screen_square.inside_eh(&Pos2D::new(0.25, 0.0)));
+    assert!(!screen_square.inside_eh(&Pos2D::new(0.25, 0.25)));
+
+    // a grid of quads split into triangles covers every pixel inside it exactly once. each corner is used by up to six
+    // triangles and the windings alternate so every kind of shared edge turns up
+    let coverage = |corners: &dyn Fn(usize, usize) -> Pos2D| {
+        let mut counts = vec![0; 64 * 64];
+        let mut triangles: Vec<Triangle> = Vec::new();
+        for i in 0..8 {
+            for j in 0..8 {
+                let (p00, p10, p01, p11) = (corners(i, j), corners(i + 1, j), corners(i, j + 1), corners(i + 1, j + 1));
+                if (i + j) % 2 == 0 {
+                    triangles.push(Triangle::new(p00, p10, p11));
+                    triangles.push(Triangle::new(p00, p01, p11));
+                } else {
+                    triangles.push(Triangle::new(p10, p00, p01));
+                    triangles.push(Triangle::new(p10, p11, p01));
+                }
+            }
+        }
+        for triangle in &triangles {
+            let mut fb = Framebuffer::new(64, 64);
+            triangle.draw_abs(&mut fb, 1, &cam);
+            for (x, y) in lit(&fb) {
+                counts[y * 64 + x] += 1;
+            }
+        }
+        // inside_eh agrees with the pixels it drew
+        for x in 0..64 {
+            for y in 0..64 {
+                let middle = cam.screen_to_world(Pos2D::new(x as f32 + 0.5, y as f32 + 0.5), 64.0, 64.0);
+                let inside = triangles.iter().filter(|t| t.inside_eh(&middle)).count();
+                assert_eq!(inside, counts[y * 64 + x], "pixel {} {}", x, y);
+            }
+        }
+        counts
+    };
+
+    // corners exactly on pixel middles so the edges run straight through rows and columns of them
+    let on_middles = coverage(&|i, j| Pos2D::new(-23.5 + 6.0 * i as f32, -23.5 + 6.0 * j as f32));
+    for x in 0..64 {
+        for y in 0..64 {
+            // the top and left sides of the grid are in and the bottom and right are out, world y flips on screen
+            let expected = if (8..56).contains(&x) && (7..55).contains(&y) { 1 } else { 0 };
+            assert_eq!(on_middles[y * 64 + x], expected, "pixel {} {}", x, y);
+        }
+    }
+
+    // the inside corners jittered so the edges go every which way, not so far that a triangle flips. the outer border stays put
+    let mut rng = Rng::new(21);
+    let jitter: Vec<Vec2D> = (0..81).map(|_| Vec2D::new(rng.range(-1.2..1.2), rng.range(-1.2..1.2))).collect();
+    let jittered = coverage(&|i, j| {
+        let offset = if (1..8).contains(&i) && (1..8).contains(&j) { jitter[i * 9 + j] } else { Vec2D::ZERO };
+        Pos2D::new(-24.0 + 6.0 * i as f32, -24.0 + 6.0 * j as f32) + offset
+    });
+    for x in 0..64 {
+        for y in 0..64 {
+            let expected = if (8..56).contains(&x) && (8..56).contains(&y) { 1 } else { 0 };
+            assert_eq!(jittered[y * 64 + x], expected, "pixel {} {}", x, y);
+        }
+    }
+
+    // a fan around one corner, every triangle touches the pixel middle it sits on
+    let mut counts = vec![0; 64 * 64];
+    let center: Pos2D = Pos2D::new(0.5, -0.5);
+    for i in 0..12 {
+        let theta = |k: i32| k as f32 * std::f32::consts::TAU / 12.0;
+        let rim = |k: i32| center + Vec2D::new(theta(k).cos(), theta(k).sin()) * 20.0;
+        let mut fb = Framebuffer::new(64, 64);
+        Triangle::new(center, rim(i), rim(i + 1)).draw_abs(&mut fb, 1, &cam);
+        for (x, y) in lit(&fb) {
+            counts[y * 64 + x] += 1;
+        }
+    }
+    assert_eq!(counts[32 * 64 + 32], 1);
+    assert!(counts.iter().all(|&count| count <= 1));
+
+    // corners too far away for the sub-pixel grid still draw, their edges are all but flat across the screen
+    let vast: Triangle = Triangle::new(Pos2D::new(-1e17, -10.0), Pos2D::new(1e17, -10.0), Pos2D::new(0.0, 10.0));
+    let mut fb = Framebuffer::new(64, 64);
+    vast.draw_abs(&mut fb, 1, &cam);
+    assert_eq!(lit(&fb), (0..64).flat_map(|x| (22..42).map(move |y| (x, y))).collect::<Vec<_>>());
+    let mut shaded = Framebuffer::new(64, 64);
+    vast.draw_shaded_abs(&mut shaded, &graphics::shading::Varyings::new(0.0, 0.0, 1.0), &cam, |_| Some(1));
+    assert_eq!(lit(&shaded), lit(&fb));
+
+    // normalized triangles follow the same rule
+    let mut fb = Framebuffer::new(8, 8);
+    Triangle::<Normalized>::new(Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 0.0), Pos2D::new(1.0, 1.0)).draw(&mut fb, 1);
+    Triangle::<Normalized>::new(Pos2D::new(0.0, 0.0), Pos2D::new(0.0, 1.0), Pos2D::new(1.0, 1.0)).draw(&mut fb, 2);
+    assert_eq!(lit(&fb).len(), 64);
+    assert_eq!((0..8).filter(|&i| fb.get_pixel(i, i) == 1).count() + (0..8).filter(|&i| fb.get_pixel(i, i) == 2).count(), 8);
+}
+
+#[test]
+fn test_shading() {
+    use graphics::{shading::Varyings, window_minifb::Framebuffer};
+    use util::color::Color;
+
+    let cam = Camera2D::new(Pos2D::ZERO);
+
+    // barycentric weights add up to 1 and give back the point
+    let t: Triangle = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(10.0, 0.0), Pos2D::new(0.0, 10.0));
+    let weights = t.barycentric(&Pos2D::new(2.0, 3.0)).unwrap();
+    assert!(weights.iter().zip([0.5, 0.2, 0.3]).all(|(w, e)| (w - e).abs() < 1e-6));
+    assert_eq!(t.barycentric(&t.a()), Some([1.0, 0.0, 0.0]));
+    assert!(Triangle::<World>::new(t.a(), t.b(), t.a()).barycentric(&t.c()).is_none());
+
+    // shading covers the same pixels as a flat fill and a color that comes back for every pixel matches it
+    let big: Triangle = Triangle::new(Pos2D::new(-30.0, -30.0), Pos2D::new(30.0, -28.0), Pos2D::new(-2.0, 30.0));
+    let (mut flat, mut shaded) = (Framebuffer::new(64, 64), Framebuffer::new(64, 64));
+    big.draw_abs(&mut flat, 0xFFFFFF, &cam);
+    big.draw_shaded_abs(&mut shaded, &Varyings::new(0.0, 0.0, 0.0), &cam, |_| Some(0xFFFFFF));
+    assert_eq!(lit(&flat), lit(&shaded));
+
+    // gouraud shading, each corner's pixel is nearly its own color and the middle is an even mix. either winding works
+    let (red, green, blue) = (Color::new(255, 0, 0, 255), Color::new(0, 255, 0, 255), Color::new(0, 0, 255, 255));
+    for triangle in [big, Triangle::new(big.a(), big.c(), big.b())] {
+        let colors = if triangle.b() == big.b() { Varyings::new(red, green, blue) } else { Varyings::new(red, blue, green) };
+        let mut fb = Framebuffer::new(64, 64);
+        triangle.draw_shaded_abs(&mut fb, &colors, &cam, |f| Some(f.value().to_u32()));
+        // world (-29, -29) is pixel (3, 61) and so on
+        let near = |x: usize, y: usize| Color::from_u32(fb.get_pixel(x, y));
+        assert!(near(3, 61).r() > 230 && near(61, 59).g() > 230 && near(30, 3).b() > 230);
+        let middle = near(31, 41);
+        for channel in [middle.r(), middle.g(), middle.b()] {
+            assert!((channel as i32 - 85).abs() < 12, "{:?}", middle);
+        }
+    }
+
+    // the weights the shader sees match Triangle::barycentric at the pixel middle, and uvs blend the same way
+    let uvs = Varyings::new(Vec2D::new(0.0, 0.0), Vec2D::new(1.0, 0.0), Vec2D::new(0.0, 1.0));
+    let mut checked = 0;
+    big.draw_shaded_abs(&mut Framebuffer::new(64, 64), &uvs, &cam, |f| {
+        let world = Pos2D::new(f.x() as f32 + 0.5 - 32.0, 32.0 - (f.y() as f32 + 0.5));
+        let expected = big.barycentric(&world).unwrap();
+        assert!(f.weights().iter().zip(expected).all(|(w, e)| (w - e).abs() < 1e-4));
+        assert!((f.value().dx() - expected[1]).abs() < 1e-4 && (f.value().dy() - expected[2]).abs() < 1e-4);
+        assert!((f.w() - 1.0).abs() < 1e-5);
+        checked += 1;
+        None
+    });
+    assert_eq!(checked, lit(&flat).len());
+
+    // perspective correct blending leans towards the near corner, a corner 4 times farther away counts for a quarter as much
+    let depth = Varyings::new_perspective(0.0, 1.0, 0.0, [1.0, 4.0, 1.0]);
+    let halfway = depth.fragment(0, 0, [0.5, 0.5, 0.0], 0);
+    assert!((halfway.value() - 0.2).abs() < 1e-6);
+    assert!((halfway.w() - 1.6).abs() < 1e-6);
+    let affine = Varyings::new(0.0, 1.0, 0.0).fragment(0, 0, [0.5, 0.5, 0.0], 0);
+    assert_eq!(affine.value(), 0.5);
+    // 1 / w is what blends evenly across the screen
+    let inverse = Varyings::new(1.0, 0.25, 1.0).fragment(0, 0, [0.5, 0.5, 0.0], 0);
+    assert!((1.0 / inverse.value() - halfway.w()).abs() < 1e-6);
+
+    // several channels at once, and a shader discarding half the pixels keeps what was under them
+    let mut fb = Framebuffer::new(64, 64);
+    fb.clear(0x123456);
+    let channels = Varyings::new((Color::new(0, 0, 0, 255), [0.0, 1.0]), (Color::new(0, 0, 0, 255), [1.0, 1.0]), (Color::new(0, 0, 0, 255), [0.0, 1.0]));
+    big.draw_shaded_abs(&mut fb, &channels, &cam, |f| {
+        assert_eq!(f.under(), 0x123456);
+        assert!((f.value().1[1] - 1.0).abs() < 1e-5);
+        (f.value().1[0] < 0.5).then(|| f.value().0.to_u32())
+    });
+    let covered = lit(&flat);
+    let black = covered.iter().filter(|&&(x, y)| fb.get_pixel(x, y) == 0).count();
+    assert!(black > 0 && black < covered.len());
+    assert!(covered.iter().all(|&(x, y)| fb.get_pixel(x, y) == 0 || fb.get_pixel(x, y) == 0x123456));
+
+    // normalized triangles shade the same way
+    let mut fb = Framebuffer::new(8, 8);
+    let screen: Triangle<Normalized> = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 0.0), Pos2D::new(0.0, 1.0));
+    screen.draw_shaded(&mut fb, &Varyings::new(1.0, 2.0, 3.0), |f| Some(f.value().round() as u32));
+    assert_eq!(fb.get_pixel(0, 0), 1);
+    assert_eq!(fb.get_pixel(6, 0), 2);
+    assert_eq!(fb.get_pixel(0, 6), 3);
+    assert_eq!(fb.get_pixel(7, 7), 0);
+}
+
+#[test]
+fn test_texture() {
+    use std::f32::consts::FRAC_PI_2;
+    use graphics::{texture::{Filter, Texture, Wrap}, window_minifb::Framebuffer};
+    use util::color::Color;
+
+    let cam = Camera2D::new(Pos2D::ZERO);
+    let (red, green, blue, white) = (Color::new(255, 0, 0, 255), Color::new(0, 255, 0, 255), Color::new(0, 0, 255, 255), Color::new(255, 255, 255, 255));
+
+    assert!(Texture::new(2, 2, vec![red; 3]).is_none());
+    assert!(Texture::new(0, 2, vec![]).is_none());
+    let mut texture = Texture::new(2, 2, vec![red, green, blue, white]).unwrap();
+    assert_eq!(texture.get_pixel(1, 0), green);
+    assert_eq!(Texture::from_fn(2, 2, |x, y| [[red, green], [blue, white]][y][x]), Some(texture.clone()));
+
+    // nearest picks the texel the uv is in
+    assert_eq!(texture.sample(Vec2D::new(0.25, 0.25)), red);
+    assert_eq!(texture.sample(Vec2D::new(0.75, 0.25)), green);
+    assert_eq!(texture.sample(Vec2D::new(0.25, 0.99)), blue);
+
+    // outside 0 to 1 wraps
+    let (past, before) = (Vec2D::new(1.25, 0.25), Vec2D::new(-0.25, 0.25));
+    assert_eq!((texture.sample(past), texture.sample(before)), (green, red));
+    texture.set_wrap(Wrap::Repeat);
+    assert_eq!((texture.sample(past), texture.sample(before)), (red, green));
+    texture.set_wrap(Wrap::Mirror);
+    assert_eq!((texture.sample(past), texture.sample(before)), (green, red));
+    assert_eq!((0..8).map(|i| Wrap::Mirror.apply(i - 4, 3)).collect::<Vec<_>>(), vec![2, 2, 1, 0, 0, 1, 2, 2]);
+    assert_eq!((0..6).map(|i| Wrap::Repeat.apply(i - 3, 3)).collect::<Vec<_>>(), vec![0, 1, 2, 0, 1, 2]);
+
+    // bilinear is exact on texel middles and blends evenly between them
+    texture.set_wrap(Wrap::Clamp);
+    texture.set_filter(Filter::Bilinear);
+    assert_eq!(texture.filter(), Filter::Bilinear);
+    assert_eq!(texture.sample(Vec2D::new(0.25, 0.25)), red);
+    assert_eq!(texture.sample(Vec2D::new(0.5, 0.5)), Color::new(128, 128, 128, 255));
+    assert_eq!(texture.sample(Vec2D::new(0.5, 0.25)), Color::new(128, 128, 0, 255));
+    // clamped edges don't blend with the far side
+    assert_eq!(texture.sample(Vec2D::new(0.0, 0.0)), red);
+    // uvs too big for a texel index still sample
+    texture.set_wrap(Wrap::Repeat);
+    assert_eq!(texture.sample(Vec2D::new(1e30, 1e30)), white);
+    texture.sample(Vec2D::new(f32::INFINITY, f32::NEG_INFINITY));
+    texture.set_wrap(Wrap::Clamp);
+    texture.set_filter(Filter::Nearest);
+
+    // alpha blends over the framebuffer
+    assert_eq!(Color::new(255, 255, 255, 0).over(0x102030), 0x102030);
+    assert_eq!(Color::new(255, 255, 255, 255).over(0x102030), 0xFFFFFF);
+    assert_eq!(Color::new(255, 255, 255, 51).over(0x000000), 0x333333);
+
+    // a sprite covers the same pixels as the plain rect, with a quarter of the image in each quarter
+    let sprite: Rect = Rect::new(Pos2D::new(-8.0, 8.0), 16.0, 16.0);
+    let (mut plain, mut fb) = (Framebuffer::new(64, 64), Framebuffer::new(64, 64));
+    sprite.draw_abs(&mut plain, 1, &cam);
+    sprite.draw_textured_abs(&mut fb, &texture, texture.uvs(), &cam);
+    assert_eq!(lit(&plain), lit(&fb));
+    assert_eq!(lit(&fb).len(), 256);
+    assert_eq!([(25, 25), (38, 25), (25, 38), (38, 38)].map(|(x, y)| fb.get_pixel(x, y)), [red, green, blue, white].map(|c| c.to_u32()));
+
+    // one frame of a sprite sheet, transparent texels leave the background showing
+    let sheet = Texture::new(4, 1, vec![red, Color::new(0, 0, 0, 0), blue, green]).unwrap();
+    assert_eq!(sheet.region(2, 0, 1, 1), [Vec2D::new(0.5, 0.0), Vec2D::new(0.75, 0.0), Vec2D::new(0.75, 1.0), Vec2D::new(0.5, 1.0)]);
+    let mut fb = Framebuffer::new(64, 64);
+    sprite.draw_textured_abs(&mut fb, &sheet, sheet.region(2, 0, 1, 1), &cam);
+    assert!(lit(&fb).len() == 256 && lit(&fb).iter().all(|&(x, y)| fb.get_pixel(x, y) == blue.to_u32()));
+    fb.clear(0x123456);
+    sprite.draw_textured_abs(&mut fb, &sheet, sheet.region(1, 0, 1, 1), &cam);
+    assert!((0..64).all(|x| (0..64).all(|y| fb.get_pixel(x, y) == 0x123456)));
+
+    // a quarter turn counter-clockwise carries the top left quarter of the image to the bottom left
+    let mut turned = sprite;
+    turned.rotate(FRAC_PI_2);
+    let mut fb = Framebuffer::new(64, 64);
+    turned.draw_textured_abs(&mut fb, &texture, texture.uvs(), &cam);
+    assert_eq!([(25, 38), (25, 25), (38, 38), (38, 25)].map(|(x, y)| fb.get_pixel(x, y)), [red, green, blue, white].map(|c| c.to_u32()));
+
+    // magnified bilinear fades smoothly across
+    let mut ramp = Texture::new(2, 1, vec![Color::new(0, 0, 0, 255), white]).unwrap();
+    ramp.set_filter(Filter::Bilinear);
+    let mut fb = Framebuffer::new(64, 64);
+    sprite.draw_textured_abs(&mut fb, &ramp, ramp.uvs(), &cam);
+    let row: Vec<u32> = (24..40).map(|x| fb.get_pixel(x, 30) & 0xFF).collect();
+    assert!(row.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(row[0] == 0 && row[15] == 255 && row[7] > 64 && row[8] < 192);
+
+    // uv mapped triangles in normalized space
+    let mut fb = Framebuffer::new(8, 8);
+    let triangle: Triangle<Normalized> = Triangle::new(Pos2D::new(0.0, 0.0), Pos2D::new(1.0, 0.0), Pos2D::new(0.0, 1.0));
+    triangle.draw_textured(&mut fb, &texture, [Vec2D::new(0.0, 0.0), Vec2D::new(1.0, 0.0), Vec2D::new(0.0, 1.0)]);
+    assert_eq!([(1, 1), (5, 1), (1, 5), (7, 7)].map(|(x, y)| fb.get_pixel(x, y)), [red.to_u32(), green.to_u32(), blue.to_u32(), 0]);
+}
+
+#[test]
+fn test_antialias() {
+    use std::f32::consts::PI;
+    use components::shapes::{Antialias, Circle, Polygon};
+    use graphics::window_minifb::Framebuffer;
+
+    let cam = Camera2D::new(Pos2D::ZERO);
+    // how much of the framebuffer is painted, partly painted pixels count for their share
+    let ink = |fb: &Framebuffer| pixels(fb).iter().map(|&p| (p & 0xFF) as f32 / 255.0).sum::<f32>();
+
+    // every pattern has its points inside the pixel with no two in the same row or column
+    for (antialias, count) in [(Antialias::Off, 1), (Antialias::X2, 2), (Antialias::X4, 4), (Antialias::X8, 8)] {
+        let samples = antialias.samples();
+        assert_eq!(samples.len(), count);
+        assert!(samples.iter().all(|&(x, y)| x > 0.0 && x < 1.0 && y > 0.0 && y < 1.0));
+        for (i, a) in samples.iter().enumerate() {
+            assert!(samples[i + 1..].iter().all(|b| a.0 != b.0 && a.1 != b.1));
+        }
+    }
+
+    // edges on pixel edges come out the same as a plain draw
+    let square: Rect = Rect::new(Pos2D::new(-8.0, 8.0), 16.0, 16.0);
+    let mut plain = Framebuffer::new(64, 64);
+    square.draw_abs(&mut plain, 0xFFFFFF, &cam);
+    for antialias in [Antialias::Off, Antialias::X2, Antialias::X4, Antialias::X8] {
+        let mut fb = Framebuffer::new(64, 64);
+        square.draw_antialiased_abs(&mut fb, 0xFFFFFF, antialias, &cam);
+        assert_eq!(pixels(&fb), pixels(&plain));
+    }
+
+    // edges through the middle of pixels half cover them, blended with what's already there
+    let offset: Rect = Rect::new(Pos2D::new(-7.5, 8.0), 15.0, 16.0);
+    for antialias in [Antialias::X2, Antialias::X4, Antialias::X8] {
+        let mut fb = Framebuffer::new(64, 64);
+        offset.draw_antialiased_abs(&mut fb, 0xFFFFFF, antialias, &cam);
+        assert_eq!([23, 24, 30, 39, 40].map(|x| fb.get_pixel(x, 30)), [0, 0x808080, 0xFFFFFF, 0x808080, 0]);
+        fb.clear(0x0000FF);
+        offset.draw_antialiased_abs(&mut fb, 0xFF0000, antialias, &cam);
+        assert_eq!([23, 24, 30].map(|x| fb.get_pixel(x, 30)), [0x0000FF, 0x800080, 0xFF0000]);
+    }
+
+    // a circle's edge pixels are partly covered and the total adds up to its area, more samples get closer
+    let circle: Circle = Circle::new(Pos2D::new(0.3, -0.2), 10.0);
+    let mut hard = Framebuffer::new(64, 64);
+    circle.draw_antialiased_abs(&mut hard, 0xFFFFFF, Antialias::Off, &cam);
+    assert!(pixels(&hard).iter().all(|&p| p == 0 || p == 0xFFFFFF));
+    let mut smooth = Framebuffer::new(64, 64);
+    circle.draw_antialiased_abs(&mut smooth, 0xFFFFFF, Antialias::X8, &cam);
+    assert!(pixels(&smooth).iter().filter(|&&p| p != 0 && p != 0xFFFFFF).count() > 40);
+    assert!((ink(&smooth) - PI * 100.0).abs() < 3.0);
+    // the middle is untouched by antialiasing
+    assert_eq!(smooth.get_pixel(32, 32), 0xFFFFFF);
+
+    // triangles and polygons with holes
+    let triangle: Triangle = Triangle::new(Pos2D::new(-20.0, -15.0), Pos2D::new(17.0, -9.0), Pos2D::new(3.0, 21.0));
+    let area = 0.5 * ((17.0 + 20.0) * (21.0 + 15.0) - (3.0 + 20.0) * (-9.0 + 15.0));
+    let mut fb = Framebuffer::new(64, 64);
+    triangle.draw_antialiased_abs(&mut fb, 0xFFFFFF, Antialias::X8, &cam);
+    assert!((ink(&fb) - area).abs() < 3.0);
+    let frame: Polygon = Polygon::new_with_holes(
+        vec![Pos2D::new(-10.5, -10.5), Pos2D::new(10.5, -10.5), Pos2D::new(10.5, 10.5), Pos2D::new(-10.5, 10.5)],
+        vec![vec![Pos2D::new(-5.0, -5.0), Pos2D::new(5.0, -5.0), Pos2D::new(5.0, 5.0), Pos2D::new(-5.0, 5.0)]],
+    );
+    let mut fb = Framebuffer::new(64, 64);
+    frame.draw_antialiased_abs(&mut fb, 0xFFFFFF, Antialias::X4, &cam);
+    assert_eq!(fb.get_pixel(32, 32), 0);
+    assert_eq!(fb.get_pixel(32, 40), 0xFFFFFF);
+    assert!((ink(&fb) - 341.0).abs() < 2.0);
+
+    // normalized shapes, Off matches draw
+    let screen: Triangle<Normalized> = Triangle::new(Pos2D::new(0.1, 0.1), Pos2D::new(0.9, 0.2), Pos2D::new(0.3, 0.8));
+    let (mut plain, mut off, mut smooth) = (Framebuffer::new(16, 16), Framebuffer::new(16, 16), Framebuffer::new(16, 16));
+    screen.draw(&mut plain, 0xFFFFFF);
+    screen.draw_antialiased(&mut off, 0xFFFFFF, Antialias::Off);
+    screen.draw_antialiased(&mut smooth, 0xFFFFFF, Antialias::X4);
+    assert_eq!(pixels(&plain), pixels(&off));
+    assert_ne!(pixels(&plain), pixels(&smooth));
+}
+
+#[test]
+fn test_tiles() {
+    use graphics::{tiles::{TileRasterizer, TriangleBatch}, window_minifb::Framebuffer};
+    use util::random::Rng;
+
+    let cam = Camera2D::new(Pos2D::new(3.7, -2.2));
+
+    // lots of overlapping triangles of every size and winding, some partly or fully off screen
+    let mut rng = Rng::new(25);
+    let mut batch: TriangleBatch = TriangleBatch::new();
+    for i in 0..1500 {
+        let center = Pos2D::new(rng.range(-130.0..130.0), rng.range(-100.0..100.0));
+        let reach = if i % 100 == 0 { 150.0 } else { rng.range(0.5..20.0) };
+        let corner = |rng: &mut Rng| center + Vec2D::new(rng.range(-reach..reach), rng.range(-reach..reach));
+        batch.push(Triangle::new(corner(&mut rng), corner(&mut rng), corner(&mut rng)), rng.next_u32() & 0xFFFFFF);
+    }
+    // a mesh of shared edges and some that can't be drawn
+    for y in -5..5 {
+        for x in -5..5 {
+            let (x, y) = (x as f32 * 7.3, y as f32 * 6.1);
+            let corners = [Pos2D::new(x, y), Pos2D::new(x + 7.3, y), Pos2D::new(x + 7.3, y + 6.1), Pos2D::new(x, y + 6.1)];
+            batch.push(Triangle::new(corners[0], corners[1], corners[2]), 0x00FF00);
+            batch.push(Triangle::new(corners[2], corners[3], corners[0]), 0x0000FF);
+        }
+    }
+    batch.push(Triangle::new(Pos2D::new(1.0, 1.0), Pos2D::new(5.0, 5.0), Pos2D::new(9.0, 9.0)), 0xFFFFFF);
+    batch.push(Triangle::new(Pos2D::new(f32::NAN, 1.0), Pos2D::new(5.0, 5.0), Pos2D::new(9.0, -9.0)), 0xFFFFFF);
+    assert_eq!(batch.len(), 1702);
+
+    let mut single = Framebuffer::new(203, 157);
+    single.clear(0x123456);
+    batch.draw_abs(&mut single, &cam);
+    assert!(pixels(&single).iter().filter(|&&p| p == 0x123456).count() < 203 * 157 / 4);
+
+    // any tile size and any number of threads, including tiles bigger than the framebuffer
+    let mut rasterizer = TileRasterizer::new();
+    assert!(rasterizer.threads() >= 1 && rasterizer.tile_size() == 64);
+    for (tile_size, threads) in [(64, 4), (1, 3), (7, 1), (16, 8), (300, 2), (32, rasterizer.threads())] {
+        rasterizer.set_tile_size(tile_size);
+        rasterizer.set_threads(threads);
+        let mut tiled = Framebuffer::new(203, 157);
+        tiled.clear(0x123456);
+        batch.draw_tiled_abs(&mut tiled, &rasterizer, &cam);
+        assert_eq!(pixels(&tiled), pixels(&single), "{} pixel tiles on {} threads", tile_size, threads);
+    }
+    rasterizer.set_tile_size(0);
+    assert_eq!(rasterizer.tile_size(), 1);
+
+    // normalized batches, and nothing to draw
+    let mut screen: TriangleBatch<Normalized> = TriangleBatch::new();
+    for _ in 0..200 {
+        let mut corner = || Pos2D::new(rng.range(-0.2..1.2), rng.range(-0.2..1.2));
+        screen.push(Triangle::new(corner(), corner(), corner()), rng.next_u32() & 0xFFFFFF);
+    }
+    let (mut single, mut tiled) = (Framebuffer::new(90, 61), Framebuffer::new(90, 61));
+    screen.draw(&mut single);
+    screen.draw_tiled(&mut tiled, &TileRasterizer::new());
+    assert_eq!(pixels(&tiled), pixels(&single));
+    screen.clear();
+    assert!(screen.is_empty());
+    screen.draw_tiled(&mut tiled, &TileRasterizer::new());
+    assert_eq!(pixels(&tiled), pixels(&single));
+}
+
 #[test]
 fn test_triangle() {
     let tp1: Pos2D = Pos2D::new(-100.0, -140.0);//(-0.5, -0.5)
@@ -150,7 +1927,7 @@ fn test_triangle() {
     let p1 = Pos2D::ZERO;
     let p2 = Pos2D::new(0.6, 0.75);
     let p3 = Pos2D::new(-120.0, -141.0);
-    let p4 = Pos2D::new(120.0, 141.0);
+    let p4: Pos2D = Pos2D::new(120.0, 141.0);
     assert_eq!(t1.inside_eh(&p1), false);
     assert_eq!(t1.inside_eh(&p3), true);
     assert_eq!(t2.inside_eh(&p1), false);
@@ -166,36 +1943,124 @@ fn test_triangle() {
     assert_eq!(t3.render_guard(&cam, width, height), true);
 
     // check our view bounds
-    let mut bl = Pos2D::new_from_other(cam.pos());
-    bl.translate(-(width / 2.0), -(height / 2.0));
-    println!("bl: {}", bl);
-    assert_eq!(Pos2D::new(-256.0, -256.0), bl);
-    let mut tr = Pos2D::new_from_other(cam.pos());
-    tr.translate(width / 2.0, height / 2.0);
-    println!("tr: {}", tr);
-    assert_eq!(Pos2D::new(256.0, 256.0), tr);
-
-    // offset checks
-    let offset = Pos2D::new(-bl.x(), -bl.y());
-    println!("our offset is {}", offset);
-    println!("bl with offset is {}, {}", bl.x() + offset.x(), bl.y() + offset.y());
-    // get the bounding box and and adjust to not care about anything outside of the viewport
-    let mut bounds = t3.bounds();
+    let view = cam.viewport(width, height);
+    println!("view: {}", view);
+    assert_eq!(Pos2D::new(-256.0, -256.0), view.min());
+    assert_eq!(Pos2D::new(256.0, 256.0), view.max());
 
-    bounds.0 = bounds.0.max(bl);
-    println!("bounds.0 {}", bounds.0);
+    // get the bounding box and and adjust to not care about anything outside of the viewport
+    let bounds = t3.bounds().clamp_to(&view).unwrap();
+    println!("bounds {}", bounds);
+    assert_eq!(bounds, t3.bounds());
+    println!("drawing at: {}", cam.world_to_screen(bounds.min(), width, height));
 
-    bounds.1 = bounds.1.min(tr);
-    println!("bounds.1 {}", bounds.1);
-    
-    println!("drawing at: {}", bounds.0.x() + offset.x());
+    // a triangle outside of the view is culled
+    let far: Triangle = Triangle::new(Pos2D::new(1000.0, 0.0), Pos2D::new(1100.0, 0.0), Pos2D::new(1000.0, 100.0));
+    assert_eq!(far.render_guard(&cam, width, height), false);
+    assert_eq!(far.bounds().clamp_to(&view), None);
 
 }
 
 #[test]
 fn test_rect() {
-    let r1 = Rect::new(Pos2D::new(100.0, 100.0), 100.0, 200.0);
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+    use graphics::window_minifb::Framebuffer;
+
+    let r1: Rect = Rect::new(Pos2D::new(100.0, 100.0), 100.0, 200.0);
     println!("a: {},\nb: {},\nc: {},\nd: {}", r1.a(), r1.b(), r1.c(), r1.d());
+
+    // containment, the a corner and the edges leaving it count, the far edges don't
+    assert!(r1.inside_eh(&Pos2D::new(150.0, 0.0)));
+    assert!(r1.inside_eh(&Pos2D::new(100.0, 100.0)));
+    assert!(!r1.inside_eh(&Pos2D::new(200.0, 0.0)));
+    assert!(!r1.inside_eh(&Pos2D::new(150.0, -100.0)));
+    assert!(!r1.inside_eh(&Pos2D::new(250.0, 0.0)));
+    assert!(r1.is_axis_aligned());
+    assert_eq!(r1.angle(), 0.0);
+    assert_eq!(r1.center(), Pos2D::new(150.0, 0.0));
+
+    // rotating about a pivot
+    let mut r2 = r1;
+    r2.rotate_about(r1.a(), FRAC_PI_2);
+    assert_approx_eq!(r2, Rect::new_rotated(Pos2D::new(100.0, 100.0), 100.0, 200.0, FRAC_PI_2, Pos2D::new(100.0, 100.0)));
+    assert_approx_eq!(r2.b(), Pos2D::new(100.0, 200.0));
+    assert_approx_eq!(r2.d(), Pos2D::new(300.0, 100.0));
+    assert_approx_eq!(r2.angle(), FRAC_PI_2);
+    assert_approx_eq!((r2.length(), r2.height()), (100.0, 200.0));
+    assert!(r2.inside_eh(&Pos2D::new(200.0, 150.0)));
+    assert!(!r2.inside_eh(&Pos2D::new(150.0, 0.0)));
+    assert_approx_eq!(r2.bounds().min(), Pos2D::new(100.0, 100.0));
+    assert_approx_eq!(r2.bounds().max(), Pos2D::new(300.0, 200.0));
+    r2.set_angle_about(r1.a(), 0.0);
+    assert_approx_eq!(r2, r1, epsilon = 1e-4);
+
+    // a diamond, the old corners fall outside but the middle of each edge stays in
+    let mut diamond: Rect = Rect::new(Pos2D::new(-10.0, 10.0), 20.0, 20.0);
+    diamond.rotate(FRAC_PI_4);
+    assert!(!diamond.is_axis_aligned());
+    assert_approx_eq!(diamond.center(), Pos2D::ZERO);
+    assert!(diamond.inside_eh(&Pos2D::new(0.0, 0.0)));
+    assert!(diamond.inside_eh(&Pos2D::new(13.0, 0.0)));
+    assert!(!diamond.inside_eh(&Pos2D::new(9.0, 9.0)));
+    assert_approx_eq!(diamond.bounds().max(), Pos2D::new(200f32.sqrt(), 200f32.sqrt()));
+    let hit = diamond.intersect_ray(&Ray2D::new(Pos2D::new(-50.0, 0.0), Vec2D::new(1.0, 0.0)).unwrap()).unwrap();
+    assert_approx_eq!(hit.distance(), 50.0 - 200f32.sqrt());
+
+    // moving
+    let mut r3: Rect = Rect::new(Pos2D::new(0.0, 10.0), 10.0, 10.0);
+    r3.translate(5.0, -5.0);
+    assert_eq!(r3, Rect::new(Pos2D::new(5.0, 5.0), 10.0, 10.0));
+    r3.lerp_translate(10.0, 0.0, 0.5);
+    assert_eq!(r3.a(), Pos2D::new(10.0, 5.0));
+    r3.lerp_steps_translate(0.0, 8.0, 4);
+    assert_eq!(r3.a(), Pos2D::new(10.0, 7.0));
+    let target: Rect = Rect::new(Pos2D::new(0.0, 40.0), 40.0, 40.0);
+    let corners = vec![target.a(), target.b(), target.c(), target.d()];
+    r3.lerp_transform(0.5, corners.clone());
+    assert_eq!(r3.a(), Pos2D::new(5.0, 23.5));
+    r3.transform(corners);
+    assert_eq!(r3, target);
+    r3.apply_transform(&Mat3::scale(0.5, 0.25));
+    assert_eq!((r3.length(), r3.height()), (20.0, 10.0));
+
+    // drawing through the camera fills exactly the rect's pixels
+    let cam = Camera2D::new(Pos2D::new(0.0, 0.0));
+    let mut fb = Framebuffer::new(64, 64);
+    let square: Rect = Rect::new(Pos2D::new(-8.0, 8.0), 16.0, 16.0);
+    assert!(square.render_guard(&cam, 64.0, 64.0));
+    square.draw_abs(&mut fb, 1, &cam);
+    assert_eq!(lit(&fb).len(), 256);
+    assert_eq!(fb.get_pixel(24, 24), 1);
+    assert_eq!(fb.get_pixel(40, 40), 0);
+
+    // rotated ones cover about their area and nothing outside
+    let mut fb = Framebuffer::new(64, 64);
+    let mut tilted = square;
+    tilted.rotate(0.3);
+    tilted.draw_abs(&mut fb, 1, &cam);
+    assert!((240..=272).contains(&lit(&fb).len()));
+    for x in 0..64 {
+        for y in 0..64 {
+            if fb.get_pixel(x, y) != 0 {
+                assert!(tilted.inside_eh(&cam.screen_to_world(Pos2D::new(x as f32 + 0.5, y as f32 + 0.5), 64.0, 64.0)));
+            }
+        }
+    }
+
+    // culling
+    let far: Rect = Rect::new(Pos2D::new(1000.0, 0.0), 10.0, 10.0);
+    assert!(!far.render_guard(&cam, 64.0, 64.0));
+    let mut fb = Framebuffer::new(64, 64);
+    far.draw_abs(&mut fb, 1, &cam);
+    assert_eq!(lit(&fb).len(), 0);
+
+    // normalized rects draw relative to the framebuffer size
+    let mut fb = Framebuffer::new(64, 64);
+    let half: Rect<Normalized> = Rect::new(Pos2D::new(0.0, 1.0), 0.5, 0.5);
+    half.draw(&mut fb, 1);
+    assert_eq!(lit(&fb).len(), 32 * 32);
+    assert_eq!(fb.get_pixel(10, 40), 1);
+    assert_eq!(fb.get_pixel(10, 10), 0);
 }
 
 #[test]
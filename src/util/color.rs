@@ -1,6 +1,89 @@
-#[allow(unused)]
+use crate::util::easing::Interpolate;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Color(u8, u8, u8, u8); // rgba
 
+impl Color {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color(r, g, b, a)
+    }
+
+    pub fn r(&self) -> u8 {
+        self.0
+    }
+
+    pub fn g(&self) -> u8 {
+        self.1
+    }
+
+    pub fn b(&self) -> u8 {
+        self.2
+    }
+
+    pub fn a(&self) -> u8 {
+        self.3
+    }
+
+    /// back from the rgb value the framebuffer uses, fully opaque since the framebuffer has no alpha
+    pub fn from_u32(value: u32) -> Color {
+        Color((value >> 16) as u8, (value >> 8) as u8, value as u8, 255)
+    }
+
+    /// the rgb value the framebuffer uses, alpha is dropped
+    pub fn to_u32(&self) -> u32 {
+        from_u8_rgb(self.0, self.1, self.2)
+    }
+
+    /// this color painted over a framebuffer pixel using its alpha, 0 leaves the pixel alone and 255 replaces it
+    pub fn over(&self, under: u32) -> u32 {
+        match self.3 {
+            0 => under,
+            255 => self.to_u32(),
+            alpha => Color::interpolate(Color::from_u32(under), *self, alpha as f32 / 255.0).to_u32(),
+        }
+    }
+}
+
+/// ## Gradient
+/// colors pinned to values along a line, anything between two stops is blended and anything past the ends takes the end color
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// stops can come in any order, None without any
+    pub fn new(stops: &[(f32, Color)]) -> Option<Gradient> {
+        if stops.is_empty() {
+            return None;
+        }
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(Gradient { stops })
+    }
+
+    /// from one color at 0 to another at 1
+    pub fn between(start: Color, end: Color) -> Gradient {
+        Gradient { stops: vec![(0.0, start), (1.0, end)] }
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    pub fn at(&self, value: f32) -> Color {
+        let next = self.stops.partition_point(|stop| stop.0 <= value);
+        if next == 0 {
+            return self.stops[0].1;
+        }
+        if next == self.stops.len() {
+            return self.stops[next - 1].1;
+        }
+        let (from, to) = (self.stops[next - 1], self.stops[next]);
+        Color::interpolate(from.1, to.1, (value - from.0) / (to.0 - from.0))
+    }
+}
+
 /// creates a single rbg from a separated rbg
 /// u8 restricted the size to 0-255 to keep the values save
 /// converting these to u32 lets us create a value the framebuffer can use rrrbbbggg
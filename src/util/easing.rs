@@ -0,0 +1,262 @@
+//! Easing
+//! Timing curves layered on top of lerp so motion can speed up and slow down instead of moving at one flat speed.
+//!
+//! curves and constants from https://easings.net
+
+use std::f32::consts::PI;
+
+use crate::util::color::Color;
+use crate::util::linear::{Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+/// ## Easing
+/// maps a linear % completion t (0..1) to an eased one. back and elastic overshoot past 0 and 1 on purpose.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    /// css style cubic-bezier(x1, y1, x2, y2) with the end points fixed at (0, 0) and (1, 1)
+    CubicBezier(f32, f32, f32, f32),
+}
+
+// how far back and elastic overshoot
+const BACK: f32 = 1.70158;
+const BACK_IN_OUT: f32 = BACK * 1.525;
+const ELASTIC: f32 = (2.0 * PI) / 3.0;
+const ELASTIC_IN_OUT: f32 = (2.0 * PI) / 4.5;
+
+impl Easing {
+    /// eased % completion for t, t is clamped to 0..1 first
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => poly_in(t, 2),
+            Easing::QuadOut => poly_out(t, 2),
+            Easing::QuadInOut => poly_in_out(t, 2),
+            Easing::CubicIn => poly_in(t, 3),
+            Easing::CubicOut => poly_out(t, 3),
+            Easing::CubicInOut => poly_in_out(t, 3),
+            Easing::QuartIn => poly_in(t, 4),
+            Easing::QuartOut => poly_out(t, 4),
+            Easing::QuartInOut => poly_in_out(t, 4),
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::ExpoIn => if t == 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) },
+            Easing::ExpoOut => if t == 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) },
+            Easing::ExpoInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    2f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    (2.0 - 2f32.powf(-20.0 * t + 10.0)) / 2.0
+                }
+            }
+            Easing::BackIn => (BACK + 1.0) * t * t * t - BACK * t * t,
+            Easing::BackOut => 1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2),
+            Easing::BackInOut => {
+                if t < 0.5 {
+                    ((2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT)) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2) * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT) + 2.0) / 2.0
+                }
+            }
+            Easing::ElasticIn => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    -2f32.powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * ELASTIC).sin()
+                }
+            }
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC).sin() + 1.0
+                }
+            }
+            Easing::ElasticInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0
+                } else {
+                    (2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()) / 2.0 + 1.0
+                }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 {
+                    (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(t, x1, y1, x2, y2),
+        }
+    }
+}
+
+fn poly_in(t: f32, n: i32) -> f32 {
+    t.powi(n)
+}
+
+fn poly_out(t: f32, n: i32) -> f32 {
+    1.0 - (1.0 - t).powi(n)
+}
+
+fn poly_in_out(t: f32, n: i32) -> f32 {
+    if t < 0.5 {
+        2f32.powi(n - 1) * t.powi(n)
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(n) / 2.0
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+// one axis of a bezier with end points 0 and 1
+fn bezier_axis(s: f32, p1: f32, p2: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+}
+
+fn bezier_axis_slope(s: f32, p1: f32, p2: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * p1 + 6.0 * inv * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+}
+
+// t is the x of the curve so we solve x(s) = t for the curve parameter s and hand back y(s)
+// newton's method usually gets there in a few steps, bisection catches the flat spots where it can't
+fn cubic_bezier(t: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    let mut s = t;
+    for _ in 0..8 {
+        let err = bezier_axis(s, x1, x2) - t;
+        if err.abs() < 1e-6 {
+            return bezier_axis(s, y1, y2);
+        }
+        let slope = bezier_axis_slope(s, x1, x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= err / slope;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = t;
+    for _ in 0..32 {
+        let x = bezier_axis(s, x1, x2);
+        if (x - t).abs() < 1e-6 {
+            break;
+        }
+        if x < t {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier_axis(s, y1, y2)
+}
+
+/// ## Interpolate
+/// anything that can be lerped from a start to an end, which is all easing needs
+pub trait Interpolate: Copy {
+    /// plain linear interpolation where t is the % completion
+    fn interpolate(start: Self, end: Self, t: f32) -> Self;
+}
+
+/// eases from start to end where t is the linear % completion
+pub fn ease<T: Interpolate>(start: T, end: T, t: f32, easing: Easing) -> T {
+    T::interpolate(start, end, easing.apply(t))
+}
+
+/// eased version of lerp_steps, the returned Vec will contain the start and end and steps is inclusive to the end
+pub fn ease_steps<T: Interpolate>(start: T, end: T, steps: u32, easing: Easing) -> Vec<T> {
+    (0..=steps)
+        .map(|i| ease(start, end, i as f32 / steps as f32, easing))
+        .collect()
+}
+
+impl Interpolate for f32 {
+    fn interpolate(start: f32, end: f32, t: f32) -> f32 {
+        start + (end - start) * t
+    }
+}
+
+impl<S: Space> Interpolate for Pos2D<S> {
+    fn interpolate(start: Pos2D<S>, end: Pos2D<S>, t: f32) -> Pos2D<S> {
+        Pos2D::lerp(start, end, t)
+    }
+}
+
+impl Interpolate for Pos3D {
+    fn interpolate(start: Pos3D, end: Pos3D, t: f32) -> Pos3D {
+        Pos3D::lerp(start, end, t)
+    }
+}
+
+impl Interpolate for Vec2D {
+    fn interpolate(start: Vec2D, end: Vec2D, t: f32) -> Vec2D {
+        start + (end - start) * t
+    }
+}
+
+impl Interpolate for Vec3D {
+    fn interpolate(start: Vec3D, end: Vec3D, t: f32) -> Vec3D {
+        start + (end - start) * t
+    }
+}
+
+/// each channel is lerped on its own and rounded, overshooting curves are clamped to 0-255
+impl Interpolate for Color {
+    fn interpolate(start: Color, end: Color, t: f32) -> Color {
+        let channel = |a: u8, b: u8| f32::interpolate(a as f32, b as f32, t).round().clamp(0.0, 255.0) as u8;
+        Color::new(
+            channel(start.r(), end.r()),
+            channel(start.g(), end.g()),
+            channel(start.b(), end.b()),
+            channel(start.a(), end.a()),
+        )
+    }
+}
@@ -0,0 +1,361 @@
+//! Geometry
+//! Computational geometry over plain point lists so levels can be authored as outlines and turned into drawable triangles.
+//! A polygon is a slice of Pos2Ds in order with the closing edge from the last point back to the first implied.
+//!
+//! Windings are in the y-up math sense: counter-clockwise outlines have a positive signed area.
+//! Screen space points y down so the same outline looks clockwise on screen.
+
+use crate::components::shapes::Triangle;
+use crate::util::linear::{Pos2D, Space, Vec2D};
+
+/// ## Winding
+/// which way a polygon's outline turns
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Winding {
+    /// positive signed area
+    CounterClockwise,
+    /// negative signed area
+    Clockwise,
+}
+
+/// ## FillRule
+/// how overlapping or self-intersecting outlines decide what counts as inside
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FillRule {
+    /// inside when a ray from the point crosses the outline an odd number of times, nested loops punch holes
+    EvenOdd,
+    /// inside when the outline winds around the point at all, loops going the same way stay filled
+    NonZero,
+}
+
+impl FillRule {
+    /// does a point the outlines wind around winding times count as inside
+    pub fn inside(self, winding: i32) -> bool {
+        match self {
+            // every crossing flips the winding by one so odd windings are odd crossings
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// ## SegmentIntersection
+/// where two segments meet
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SegmentIntersection<S: Space> {
+    /// the segments cross or touch at one point
+    Point(Pos2D<S>),
+    /// the segments are collinear and share the stretch between the two points
+    Overlap(Pos2D<S>, Pos2D<S>),
+}
+
+// twice the signed area of o, a, b. positive when b is to the left of o -> a
+fn cross<S: Space>(o: Pos2D<S>, a: Pos2D<S>, b: Pos2D<S>) -> f32 {
+    (a - o).perp_dot(b - o)
+}
+
+// twice the signed area of the polygon with the shoelace formula, we keep it doubled since sign is what most callers want
+fn doubled_area<S: Space>(polygon: &[Pos2D<S>]) -> f32 {
+    let Some(&last) = polygon.last() else {
+        return 0.0;
+    };
+    let mut prev = last;
+    let mut sum = 0.0;
+    for &p in polygon {
+        sum += prev.x() * p.y() - p.x() * prev.y();
+        prev = p;
+    }
+    sum
+}
+
+/// area of the polygon, positive for counter-clockwise and negative for clockwise
+pub fn signed_area<S: Space>(polygon: &[Pos2D<S>]) -> f32 {
+    doubled_area(polygon) / 2.0
+}
+
+/// area of the polygon regardless of winding
+pub fn area<S: Space>(polygon: &[Pos2D<S>]) -> f32 {
+    signed_area(polygon).abs()
+}
+
+/// None when the polygon has no area to turn around
+pub fn winding<S: Space>(polygon: &[Pos2D<S>]) -> Option<Winding> {
+    let doubled = doubled_area(polygon);
+    if doubled > 0.0 {
+        Some(Winding::CounterClockwise)
+    } else if doubled < 0.0 {
+        Some(Winding::Clockwise)
+    } else {
+        None
+    }
+}
+
+/// center of mass of the polygon's area, None when it has no area
+pub fn centroid<S: Space>(polygon: &[Pos2D<S>]) -> Option<Pos2D<S>> {
+    let doubled = doubled_area(polygon);
+    if doubled == 0.0 {
+        return None;
+    }
+    // measure from the first point so big coordinates don't eat the precision
+    let origin = polygon[0];
+    let (mut cx, mut cy) = (0.0, 0.0);
+    let mut prev = *polygon.last()? - origin;
+    for &p in polygon {
+        let p = p - origin;
+        let step = prev.perp_dot(p);
+        cx += (prev.dx() + p.dx()) * step;
+        cy += (prev.dy() + p.dy()) * step;
+        prev = p;
+    }
+    Some(origin + Vec2D::new(cx, cy) / (3.0 * doubled))
+}
+
+/// smallest convex polygon holding every point, monotone chain
+///
+/// comes back counter-clockwise starting from the lowest x (then lowest y) with duplicates and collinear points dropped
+pub fn convex_hull<S: Space>(points: &[Pos2D<S>]) -> Vec<Pos2D<S>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x().total_cmp(&b.x()).then(a.y().total_cmp(&b.y())));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Pos2D<S>> = Vec::with_capacity(sorted.len() + 1);
+    // lower chain left to right
+    for &p in &sorted {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // upper chain right to left, never popping back into the lower chain
+    let lower = hull.len() + 1;
+    for &p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // the upper chain ends back on the first point. if everything was collinear only the two ends are left
+    hull.pop();
+    hull
+}
+
+/// the point on the segment a -> b closest to p
+pub fn closest_point_on_segment<S: Space>(p: Pos2D<S>, a: Pos2D<S>, b: Pos2D<S>) -> Pos2D<S> {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// where the segment a0 -> a1 meets the segment b0 -> b1, None if they don't
+pub fn segment_intersection<S: Space>(a0: Pos2D<S>, a1: Pos2D<S>, b0: Pos2D<S>, b1: Pos2D<S>) -> Option<SegmentIntersection<S>> {
+    let r = a1 - a0;
+    let s = b1 - b0;
+    let to_b = b0 - a0;
+    let denom = r.perp_dot(s);
+
+    // scale the parallel test by the lengths so it doesn't depend on the units the level is in
+    if denom.abs() > f32::EPSILON * r.length() * s.length() {
+        let t = to_b.perp_dot(s) / denom;
+        let u = to_b.perp_dot(r) / denom;
+        if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        return Some(SegmentIntersection::Point(a0 + r * t));
+    }
+
+    // parallel, the segments only meet if they're on the same line
+    let r_length_squared = r.length_squared();
+    if r_length_squared == 0.0 {
+        // a is a single point
+        let on_b = closest_point_on_segment(a0, b0, b1) == a0;
+        return on_b.then_some(SegmentIntersection::Point(a0));
+    }
+    if to_b.perp_dot(r).abs() > f32::EPSILON * r_length_squared.max(to_b.length_squared()) {
+        return None;
+    }
+    // overlap of b projected onto a, in a's t
+    let t0 = to_b.dot(r) / r_length_squared;
+    let t1 = (b1 - a0).dot(r) / r_length_squared;
+    let lo = t0.min(t1).max(0.0);
+    let hi = t0.max(t1).min(1.0);
+    if lo > hi {
+        None
+    } else if lo == hi {
+        Some(SegmentIntersection::Point(a0 + r * lo))
+    } else {
+        Some(SegmentIntersection::Overlap(a0 + r * lo, a0 + r * hi))
+    }
+}
+
+/// how many times the outline winds counter-clockwise around p, clockwise turns count down
+pub fn winding_number<S: Space>(p: Pos2D<S>, polygon: &[Pos2D<S>]) -> i32 {
+    let Some(&last) = polygon.last() else {
+        return 0;
+    };
+    let mut prev = last;
+    let mut winding = 0;
+    for &next in polygon {
+        // half open on y so a vertex sitting exactly on the scanline isn't counted twice
+        if prev.y() <= p.y() {
+            if next.y() > p.y() && cross(prev, next, p) > 0.0 {
+                winding += 1;
+            }
+        } else if next.y() <= p.y() && cross(prev, next, p) < 0.0 {
+            winding -= 1;
+        }
+        prev = next;
+    }
+    winding
+}
+
+/// is p inside the polygon, points exactly on an edge can land either way
+pub fn point_in_polygon<S: Space>(p: Pos2D<S>, polygon: &[Pos2D<S>], rule: FillRule) -> bool {
+    rule.inside(winding_number(p, polygon))
+}
+
+/// splits a simple polygon with optional holes into triangles by ear clipping
+///
+/// the outline and holes can wind either way, the triangles always come out counter-clockwise.
+/// holes have to sit inside the outline without touching each other. a self-intersecting outline stops early with the triangles it managed.
+pub fn triangulate<S: Space>(outline: &[Pos2D<S>], holes: &[Vec<Pos2D<S>>]) -> Vec<Triangle<S>> {
+    let mut polygon = clean_ring(outline);
+    if polygon.len() < 3 {
+        return Vec::new();
+    }
+    if doubled_area(&polygon) < 0.0 {
+        polygon.reverse();
+    }
+
+    // holes go in opposite the outline. the rightmost hole is bridged first so the holes left of it can still see the outline
+    let mut holes: Vec<Vec<Pos2D<S>>> = holes.iter().map(|h| clean_ring(h)).filter(|h| h.len() >= 3).collect();
+    for hole in &mut holes {
+        if doubled_area(hole) > 0.0 {
+            hole.reverse();
+        }
+    }
+    holes.sort_by(|a, b| rightmost(b).1.x().total_cmp(&rightmost(a).1.x()));
+    for i in 0..holes.len() {
+        bridge_hole(&mut polygon, &holes[i], &holes[i + 1..]);
+    }
+
+    clip_ears(polygon)
+}
+
+// drops repeated points and a closing point that repeats the first
+fn clean_ring<S: Space>(ring: &[Pos2D<S>]) -> Vec<Pos2D<S>> {
+    let mut cleaned = ring.to_vec();
+    cleaned.dedup();
+    while cleaned.len() > 1 && cleaned.first() == cleaned.last() {
+        cleaned.pop();
+    }
+    cleaned
+}
+
+// index and position of the point furthest right
+fn rightmost<S: Space>(ring: &[Pos2D<S>]) -> (usize, Pos2D<S>) {
+    ring.iter().copied().enumerate()
+        .max_by(|a, b| a.1.x().total_cmp(&b.1.x()))
+        .expect("rings are never empty")
+}
+
+// true when the segment a -> b passes through an edge of the ring, sharing an end point doesn't count
+fn crosses_ring<S: Space>(a: Pos2D<S>, b: Pos2D<S>, ring: &[Pos2D<S>]) -> bool {
+    (0..ring.len()).any(|i| {
+        let p = ring[i];
+        let q = ring[(i + 1) % ring.len()];
+        if p == a || p == b || q == a || q == b {
+            return false;
+        }
+        segment_intersection(a, b, p, q).is_some()
+    })
+}
+
+// is p in the inside corner of the counter-clockwise turn prev -> v -> next
+fn in_corner<S: Space>(prev: Pos2D<S>, v: Pos2D<S>, next: Pos2D<S>, p: Pos2D<S>) -> bool {
+    let after_prev = cross(prev, v, p) > 0.0;
+    let before_next = cross(v, next, p) > 0.0;
+    if cross(prev, v, next) >= 0.0 {
+        after_prev && before_next
+    } else {
+        after_prev || before_next
+    }
+}
+
+// cuts a slit from the hole's rightmost point to the nearest outline point it can see and walks the hole into the outline through it
+fn bridge_hole<S: Space>(polygon: &mut Vec<Pos2D<S>>, hole: &[Pos2D<S>], rest: &[Vec<Pos2D<S>>]) {
+    let (m, from) = rightmost(hole);
+    let len = polygon.len();
+    let mut candidates: Vec<usize> = (0..len).collect();
+    candidates.sort_by(|&a, &b| (polygon[a] - from).length_squared().total_cmp(&(polygon[b] - from).length_squared()));
+
+    let visible = candidates.into_iter().find(|&i| {
+        let to = polygon[i];
+        // earlier bridges repeat points so make sure the slit leaves from the copy whose corner it is in
+        in_corner(polygon[(i + len - 1) % len], to, polygon[(i + 1) % len], from)
+            && !crosses_ring(from, to, polygon)
+            && !crosses_ring(from, to, hole)
+            && !rest.iter().any(|other| crosses_ring(from, to, other))
+    });
+    // a hole outside the outline has nothing to bridge to
+    let Some(i) = visible else {
+        return;
+    };
+
+    let mut bridged = Vec::with_capacity(len + hole.len() + 2);
+    bridged.extend_from_slice(&polygon[..=i]);
+    bridged.extend(hole[m..].iter().chain(&hole[..=m]));
+    bridged.extend_from_slice(&polygon[i..]);
+    *polygon = bridged;
+}
+
+// ear clipping over a counter-clockwise ring, bridged holes are just more ring
+fn clip_ears<S: Space>(mut ring: Vec<Pos2D<S>>) -> Vec<Triangle<S>> {
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    let mut i = 0;
+    // how many corners in a row we've looked at without clipping, a whole lap means we're stuck
+    let mut stalled = 0;
+    while ring.len() > 3 && stalled < ring.len() {
+        let len = ring.len();
+        let (prev, v, next) = (ring[(i + len - 1) % len], ring[i % len], ring[(i + 1) % len]);
+        let turn = cross(prev, v, next);
+        if turn == 0.0 {
+            // a straight through point or a spike, either way it covers no area
+            ring.remove(i % len);
+            stalled = 0;
+        } else if turn > 0.0 && is_ear(&ring, prev, v, next) {
+            triangles.push(Triangle::new(prev, v, next));
+            ring.remove(i % len);
+            stalled = 0;
+        } else {
+            i += 1;
+            stalled += 1;
+            continue;
+        }
+        // step back so the corner before the clipped one gets another look
+        i = (i % len + len - 2) % (len - 1);
+    }
+    if ring.len() == 3 && cross(ring[0], ring[1], ring[2]) > 0.0 {
+        triangles.push(Triangle::new(ring[0], ring[1], ring[2]));
+    }
+    triangles
+}
+
+// only reflex points can poke into an ear, a point at one of the ear's corners is a bridge copy and can't
+fn is_ear<S: Space>(ring: &[Pos2D<S>], a: Pos2D<S>, b: Pos2D<S>, c: Pos2D<S>) -> bool {
+    let len = ring.len();
+    !(0..len).any(|j| {
+        let p = ring[j];
+        if p == a || p == b || p == c {
+            return false;
+        }
+        let reflex = cross(ring[(j + len - 1) % len], p, ring[(j + 1) % len]) <= 0.0;
+        reflex && cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+    })
+}
@@ -0,0 +1,125 @@
+//! Integrate
+//! Steps a position and velocity forward in time under some acceleration so objects stop hand rolling translate calls.
+//! The acceleration is a closure of the current position and velocity, gravity, springs, drag, whatever the game needs.
+//!
+//! Which integrator to pick:
+//! - Euler is the textbook one, it gains energy every step so orbits spiral out and springs blow up. mostly here for comparison
+//! - SemiImplicitEuler costs the same but keeps energy bounded, the usual pick for game physics
+//! - Verlet is second order and also keeps energy bounded, exact for constant acceleration like gravity
+//! - Rk4 is fourth order and the most accurate per step, at four acceleration calls a step
+
+use std::ops::{Add, Mul};
+
+use crate::util::linear::{Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+/// ## Motion
+/// a position that can be moved by its own vector type
+pub trait Motion: Copy + Add<Self::Vector, Output = Self> {
+    type Vector: Copy + Add<Output = Self::Vector> + Mul<f32, Output = Self::Vector>;
+}
+
+impl<S: Space> Motion for Pos2D<S> {
+    type Vector = Vec2D;
+}
+
+impl Motion for Pos3D {
+    type Vector = Vec3D;
+}
+
+/// ## Body
+/// a position and velocity moving together
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Body<P: Motion> {
+    position: P,
+    velocity: P::Vector,
+}
+
+/// ## Integrator
+/// the method used to move a Body forward one step
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Integrator {
+    /// explicit Euler, moves with the old velocity then updates it
+    Euler,
+    /// symplectic Euler, updates the velocity first then moves with the new one
+    SemiImplicitEuler,
+    /// velocity Verlet, moves with the average of this step's and the next step's acceleration
+    Verlet,
+    /// classic 4th order Runge-Kutta
+    Rk4,
+}
+
+impl<P: Motion> Body<P> {
+    pub fn new(position: P, velocity: P::Vector) -> Body<P> {
+        Body { position, velocity }
+    }
+
+    pub fn position(&self) -> P {
+        self.position
+    }
+
+    pub fn velocity(&self) -> P::Vector {
+        self.velocity
+    }
+
+    pub fn set_position(&mut self, position: P) {
+        self.position = position;
+    }
+
+    pub fn set_velocity(&mut self, velocity: P::Vector) {
+        self.velocity = velocity;
+    }
+
+    /// moves the body forward by dt seconds, see Integrator::step
+    pub fn step(&mut self, integrator: Integrator, dt: f32, acceleration: impl Fn(P, P::Vector) -> P::Vector) {
+        *self = integrator.step(*self, dt, acceleration);
+    }
+}
+
+impl Integrator {
+    /// the body dt seconds later, acceleration gets the position and velocity it should be evaluated at
+    ///
+    /// Verlet assumes the acceleration mostly depends on position, velocity based forces like drag see an estimate of the next velocity
+    pub fn step<P: Motion>(self, body: Body<P>, dt: f32, acceleration: impl Fn(P, P::Vector) -> P::Vector) -> Body<P> {
+        let (x, v) = (body.position, body.velocity);
+        match self {
+            Integrator::Euler => {
+                let a = acceleration(x, v);
+                Body::new(x + v * dt, v + a * dt)
+            }
+            Integrator::SemiImplicitEuler => {
+                let v1 = v + acceleration(x, v) * dt;
+                Body::new(x + v1 * dt, v1)
+            }
+            Integrator::Verlet => {
+                let a = acceleration(x, v);
+                let x1 = x + v * dt + a * (0.5 * dt * dt);
+                let a1 = acceleration(x1, v + a * dt);
+                Body::new(x1, v + (a + a1) * (0.5 * dt))
+            }
+            Integrator::Rk4 => {
+                // each k is a (velocity, acceleration) slope sampled across the step
+                let half = 0.5 * dt;
+                let (k1x, k1v) = (v, acceleration(x, v));
+                let (k2x, k2v) = (v + k1v * half, acceleration(x + k1x * half, v + k1v * half));
+                let (k3x, k3v) = (v + k2v * half, acceleration(x + k2x * half, v + k2v * half));
+                let (k4x, k4v) = (v + k3v * dt, acceleration(x + k3x * dt, v + k3v * dt));
+                let sixth = dt / 6.0;
+                Body::new(
+                    x + (k1x + k2x * 2.0 + k3x * 2.0 + k4x) * sixth,
+                    v + (k1v + k2v * 2.0 + k3v * 2.0 + k4v) * sixth,
+                )
+            }
+        }
+    }
+
+    /// every state from the start through steps steps of dt, steps + 1 bodies in all
+    pub fn trajectory<P: Motion>(self, body: Body<P>, dt: f32, steps: usize, acceleration: impl Fn(P, P::Vector) -> P::Vector) -> Vec<Body<P>> {
+        let mut states = Vec::with_capacity(steps + 1);
+        states.push(body);
+        for _ in 0..steps {
+            let last = states[states.len() - 1];
+            states.push(self.step(last, dt, &acceleration));
+        }
+        states
+    }
+}
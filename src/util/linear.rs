@@ -1,28 +1,49 @@
-use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::util::easing::{self, Easing};
+
+mod aabb;
+mod angle;
+mod approx;
+mod matrix;
+mod polar;
+mod quat;
+mod ray;
+mod scalar;
+mod space;
+mod spline;
+pub use aabb::*;
+pub use angle::*;
+pub use approx::*;
+pub use matrix::*;
+pub use polar::*;
+pub use quat::*;
+pub use ray::*;
+pub use scalar::*;
+pub use space::*;
+pub use spline::*;
+
 /// Linear
 /// Linear Algebra utility structs for both internal engine use and for games dumbed down to handle only what we need.
 /// Could we just use someone else's library for some of these things? Yes, but we're trying to learn.
 /// 
 
-/// ## Position
-/// Primarily for UI/HUD element use. This is for when we only need to deal with integers and don't need incredibly specific coords.
-#[derive(Debug, Copy, Clone)]
-#[allow(unused)]
-pub struct Postion {
-    x: u32,
-    y: u32,
-}
-
 /// ## Pos2D
 /// Primary struct for giving nodes accurate positons and movement in 2D games.
+///
+/// S is the coordinate space the position is in (World, Screen or Normalized), World if left out.
+/// T is the number type, f32 if left out.
 #[derive(Debug, Copy, Clone)]
-pub struct Pos2D {
-    x: f32,
-    y: f32,
+pub struct Pos2D<S = World, T = f32> {
+    x: T,
+    y: T,
+    space: PhantomData<S>,
 }
 
-pub const fn pos2d(x: f32, y: f32) -> Pos2D {
+pub const fn pos2d<S: Space>(x: f32, y: f32) -> Pos2D<S> {
     Pos2D::new(x, y)
 }
 
@@ -30,73 +51,105 @@ pub const fn pos2d(x: f32, y: f32) -> Pos2D {
 /// Primary struct for giving nodes accurate positons and movement in 3D games.
 #[derive(Debug, Copy, Clone)]
 #[allow(unused)]
-pub struct Pos3D {
-    x: f32,
-    y: f32,
-    z: f32,
+pub struct Pos3D<T = f32> {
+    x: T,
+    y: T,
+    z: T,
 }
 
 /// ## Vec2D
 /// struct to manipulate a pos in a 2D space
 #[derive(Debug, Copy, Clone)]
-pub struct Vec2D {
-    dx: f32,
-    dy: f32,
+pub struct Vec2D<T = f32> {
+    dx: T,
+    dy: T,
 }
 
 /// ## Vec3D
 /// struct to manipulate a pos in a 3D same 
 #[allow(unused)]
 #[derive(Debug, Copy, Clone)]
-pub struct Vec3D {
-    dx: f32,
-    dy: f32,
-    dz: f32,
+pub struct Vec3D<T = f32> {
+    dx: T,
+    dy: T,
+    dz: T,
 }
 
+/// f64 versions for big worlds where f32 starts to lose precision far from the origin
+pub type DPos2D<S = World> = Pos2D<S, f64>;
+pub type DPos3D = Pos3D<f64>;
+pub type DVec2D = Vec2D<f64>;
+pub type DVec3D = Vec3D<f64>;
+
+/// integer versions for tiles, grids and UI/HUD elements where we don't need incredibly specific coords
+pub type IPos2D<S = World> = Pos2D<S, i32>;
+pub type IPos3D = Pos3D<i32>;
+pub type IVec2D = Vec2D<i32>;
+pub type IVec3D = Vec3D<i32>;
+
 /// ## Impl blocks for Pos2D and Vec2D
 /// 
 
 /// translate a Pos2D by adding a vector
-impl Add<Vec2D> for Pos2D {
-    type Output = Pos2D;
+impl<S: Space, T: Scalar> Add<Vec2D<T>> for Pos2D<S, T> {
+    type Output = Pos2D<S, T>;
 
-    fn add(self, rhs: Vec2D) -> Self {
-        Pos2D {
-            x: self.x + rhs.dx,
-            y: self.y + rhs.dy,
-        }
+    fn add(self, rhs: Vec2D<T>) -> Self {
+        Pos2D::new(self.x + rhs.dx, self.y + rhs.dy)
     }
 }
 
 /// translate and assign a Pos2D by adding a vector
-impl AddAssign<Vec2D> for Pos2D {
-    fn add_assign(&mut self, rhs: Vec2D) {
-        *self = Self {
-            x: self.x + rhs.dx(),
-            y: self.y + rhs.dy()
+impl<S: Space, T: Scalar> AddAssign<Vec2D<T>> for Pos2D<S, T> {
+    fn add_assign(&mut self, rhs: Vec2D<T>) {
+        self.x += rhs.dx();
+        self.y += rhs.dy();
+    }
+}
+
+/// translate a Pos2D backwards by subtracting a vector
+impl<S: Space, T: Scalar> Sub<Vec2D<T>> for Pos2D<S, T> {
+    type Output = Pos2D<S, T>;
+
+    fn sub(self, rhs: Vec2D<T>) -> Self {
+        Pos2D::new(self.x - rhs.dx, self.y - rhs.dy)
+    }
+}
+
+impl<S: Space, T: Scalar> SubAssign<Vec2D<T>> for Pos2D<S, T> {
+    fn sub_assign(&mut self, rhs: Vec2D<T>) {
+        self.x -= rhs.dx;
+        self.y -= rhs.dy;
+    }
+}
+
+/// the vector that takes rhs to self
+impl<S: Space, T: Scalar> Sub<Pos2D<S, T>> for Pos2D<S, T> {
+    type Output = Vec2D<T>;
+
+    fn sub(self, rhs: Pos2D<S, T>) -> Vec2D<T> {
+        Vec2D {
+            dx: self.x - rhs.x,
+            dy: self.y - rhs.y,
         }
     }
 }
 
 /// multiplies two points
-impl Mul<Pos2D> for Pos2D {
-    type Output = Pos2D;
+impl<S: Space, T: Scalar> Mul<Pos2D<S, T>> for Pos2D<S, T> {
+    type Output = Pos2D<S, T>;
 
-    fn mul(self, rhs: Pos2D) -> Self{
-        Pos2D {
-            x: self.x.mul(rhs.x),
-            y: self.y.mul(rhs.y),
-        }
+    fn mul(self, rhs: Pos2D<S, T>) -> Self{
+        Pos2D::new(self.x.mul(rhs.x), self.y.mul(rhs.y))
     }
 }
 
-impl Pos2D {
-    pub const ZERO: Pos2D = Pos2D {x: 0.0, y: 0.0};
-    pub const ONE: Pos2D = Pos2D {x: 1.0, y: 1.0};
+impl<S: Space, T: Scalar> Pos2D<S, T> {
+    pub const ZERO: Pos2D<S, T> = Pos2D::new(T::ZERO, T::ZERO);
+    pub const ONE: Pos2D<S, T> = Pos2D::new(T::ONE, T::ONE);
 
-    pub const fn new(x:f32 , y: f32) -> Self {
-        Self { x, y }
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y, space: PhantomData }
     }
 
     // sorry attempt at a deref
@@ -104,73 +157,76 @@ impl Pos2D {
         Self::new(self.x, self.y)
     }
 
-    pub fn new_from_other(other: Pos2D) -> Pos2D {
-        Pos2D { x: other.x(), y: other.y() }
+    pub fn new_from_other(other: Pos2D<S, T>) -> Pos2D<S, T> {
+        Pos2D::new(other.x(), other.y())
     }
     /// getter for the x data member
-    pub fn x(&self) -> f32 {
+    pub fn x(&self) -> T {
         self.x
     }
 
     /// getter for the y data member
-    pub fn y(&self) -> f32 {
+    pub fn y(&self) -> T {
         self.y
     }
 
     /// assign a new x value
-    pub fn set_x(&mut self, x: f32) {
+    pub fn set_x(&mut self, x: T) {
         self.x = x
     }
 
     /// assign a new y value
-    pub fn set_y(&mut self, y: f32) {
+    pub fn set_y(&mut self, y: T) {
         self.y = y
     }
 
     /// assign a new x and y value
-    pub fn set(&mut self, x: f32, y: f32) {
+    pub fn set(&mut self, x: T, y: T) {
         self.x = x;
         self.y = y;
     }
 
     /// returns a new point with the min x and min y between self and another point
-    pub fn min(self, rhs: Pos2D) -> Pos2D {
-        Self {
-            x: self.x.min(rhs.x),
-            y: self.y.min(rhs.y),
-        }
+    pub fn min(self, rhs: Pos2D<S, T>) -> Pos2D<S, T> {
+        Self::new(self.x.min(rhs.x), self.y.min(rhs.y))
     }
 
     /// returns a new point with the max x and max y between self and another point
-    pub fn max(self, rhs: Pos2D) -> Pos2D {
-        Self {
-            x: self.x.max(rhs.x),
-            y: self.y.max(rhs.y),
-        }
+    pub fn max(self, rhs: Pos2D<S, T>) -> Pos2D<S, T> {
+        Self::new(self.x.max(rhs.x), self.y.max(rhs.y))
     }
 
     /// translates a point instantly given an x distance (tx)
-    pub fn translate_x(&mut self, tx: f32) {
+    pub fn translate_x(&mut self, tx: T) {
         self.x += tx
         // while this method is possible it is not necessary
         //*self = *self + Vec2D::new(tx, 0.0);
     }
 
     /// translates a point instantly given a y distance (ty)
-    pub fn translate_y(&mut self, ty: f32) {
+    pub fn translate_y(&mut self, ty: T) {
         self.y += ty
     }
 
     /// translates a point instantly given an x and y distance (tx, ty)
-    pub fn translate(&mut self, tx: f32, ty: f32) {
+    pub fn translate(&mut self, tx: T, ty: T) {
         self.x += tx;
         self.y += ty;
     }
 
-    pub fn add_scalar(&mut self, scalar: f32) {
+    pub fn add_scalar(&mut self, scalar: T) {
         self.translate(scalar, scalar)
     }
 
+    /// lossy conversion to another number type, works like `as` on each coordinate
+    ///
+    /// floats going to integers truncate towards zero, use floor or round first to pick a different rounding
+    pub fn cast<U: Scalar>(self) -> Pos2D<S, U> {
+        Pos2D::new(U::from_f64(self.x.to_f64()), U::from_f64(self.y.to_f64()))
+    }
+}
+
+impl<S: Space, T: Float> Pos2D<S, T> {
     /// lerp covers the states from start to end of the distance between a start point and and end point where t is the % completion 
     /// 
     /// lerp function can be simplified to
@@ -178,11 +234,11 @@ impl Pos2D {
     /// lerp(a, b, t) = a + (b - a) * t
     /// 
     /// lerp pseudo-code and resource: https://docs.godotengine.org/en/stable/tutorials/math/interpolation.html
-    pub fn lerp(start: Pos2D, end: Pos2D, t: f32) -> Pos2D {
-        Pos2D {
-            x: start.x() + (end.x() - start.x()) * t,
-            y: start.y() + (end.y() - start.y()) * t,
-        }
+    pub fn lerp(start: Pos2D<S, T>, end: Pos2D<S, T>, t: T) -> Pos2D<S, T> {
+        Pos2D::new(
+            start.x() + (end.x() - start.x()) * t,
+            start.y() + (end.y() - start.y()) * t,
+        )
     }
 
     /// debug method for lerp
@@ -192,57 +248,115 @@ impl Pos2D {
     /// the returned Vec will contain the start and end pos 
     /// 
     /// steps is inclusive to the end
-    pub fn lerp_steps(start: Pos2D, end: Pos2D, steps: u32) -> Vec<Pos2D> {
-        let mut v: Vec<Pos2D> = Vec::new();
+    pub fn lerp_steps(start: Pos2D<S, T>, end: Pos2D<S, T>, steps: u32) -> Vec<Pos2D<S, T>> {
+        let mut v: Vec<Pos2D<S, T>> = Vec::new();
         for i in 0..steps + 1 {
-            v.push(Pos2D::lerp(start, end, i as f32 / steps as f32));
+            v.push(Pos2D::lerp(start, end, T::from_f64(i as f64 / steps as f64)));
         }
         v
     }
 
-    /// 2D cartesian to polar coords
-    pub fn cartesian_to_polar(self) -> (f32, f32) {
-        let r = (self.x().powi(2) + self.y().powi(2)).sqrt();
-        let theta = f32::atan2(self.y(), self.x());
-        (r, theta)
+    /// rounds both coordinates down, floor then cast to get the tile a point is in
+    pub fn floor(self) -> Pos2D<S, T> {
+        Pos2D::new(self.x.floor(), self.y.floor())
+    }
+
+    pub fn ceil(self) -> Pos2D<S, T> {
+        Pos2D::new(self.x.ceil(), self.y.ceil())
+    }
+
+    pub fn round(self) -> Pos2D<S, T> {
+        Pos2D::new(self.x.round(), self.y.round())
     }
 }
 
-impl Pos3D {
-    pub const ZERO: Pos3D = Pos3D {x: 0.0, y: 0.0, z: 0.0};
-    pub const ONE: Pos3D = Pos3D {x: 1.0, y: 1.0, z: 1.0};
+impl<S: Space> Pos2D<S> {
+    /// lerp with the % completion run through an easing curve first
+    pub fn ease(start: Pos2D<S>, end: Pos2D<S>, t: f32, easing: Easing) -> Pos2D<S> {
+        easing::ease(start, end, t, easing)
+    }
+
+    /// eased version of lerp_steps, contains the start and end pos and steps is inclusive to the end
+    pub fn ease_steps(start: Pos2D<S>, end: Pos2D<S>, steps: u32, easing: Easing) -> Vec<Pos2D<S>> {
+        easing::ease_steps(start, end, steps, easing)
+    }
+}
+
+/// grid helpers, a grid position is the tile it names
+impl<S: Space, T: Integer> Pos2D<S, T> {
+    /// number of straight steps between two tiles when we can't move diagonally
+    pub fn manhattan_distance(self, other: Pos2D<S, T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// number of steps between two tiles when diagonal moves are allowed
+    pub fn chebyshev_distance(self, other: Pos2D<S, T>) -> T {
+        Scalar::max((self.x - other.x).abs(), (self.y - other.y).abs())
+    }
+
+    /// the tiles sharing an edge with this one, +x, -x, +y then -y
+    pub fn neighbours_4(self) -> [Pos2D<S, T>; 4] {
+        let (one, zero) = (T::ONE, T::ZERO);
+        [
+            self + Vec2D::new(one, zero),
+            self + Vec2D::new(-one, zero),
+            self + Vec2D::new(zero, one),
+            self + Vec2D::new(zero, -one),
+        ]
+    }
+
+    /// the tiles sharing an edge or a corner with this one, the edge neighbours come first
+    pub fn neighbours_8(self) -> [Pos2D<S, T>; 8] {
+        let one = T::ONE;
+        let [a, b, c, d] = self.neighbours_4();
+        [
+            a,
+            b,
+            c,
+            d,
+            self + Vec2D::new(one, one),
+            self + Vec2D::new(-one, one),
+            self + Vec2D::new(one, -one),
+            self + Vec2D::new(-one, -one),
+        ]
+    }
+}
 
-    pub const fn new(x:f32 , y: f32, z: f32) -> Self {
+impl<T: Scalar> Pos3D<T> {
+    pub const ZERO: Pos3D<T> = Pos3D {x: T::ZERO, y: T::ZERO, z: T::ZERO};
+    pub const ONE: Pos3D<T> = Pos3D {x: T::ONE, y: T::ONE, z: T::ONE};
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
-    pub fn x(&self) -> f32 {
+    pub fn x(&self) -> T {
         self.x
     }
 
-    pub fn y(&self) -> f32 {
+    pub fn y(&self) -> T {
         self.y
     }
 
-    pub fn z(&self) -> f32 {
+    pub fn z(&self) -> T {
         self.z
     }
 
     /// assign a new x value
-    pub fn set_x(&mut self, x: f32) {
+    pub fn set_x(&mut self, x: T) {
         self.x = x
     }
 
     /// assign a new y value
-    pub fn set_y(&mut self, y: f32) {
+    pub fn set_y(&mut self, y: T) {
         self.y = y
     }
 
-    pub fn set_z(&mut self, z: f32) {
+    pub fn set_z(&mut self, z: T) {
         self.z = z
     }
 
     /// assign a new x and y value
-    pub fn set(&mut self, x: f32, y: f32, z: f32) {
+    pub fn set(&mut self, x: T, y: T, z: T) {
         *self = Self {
             x: x,
             y: y,
@@ -251,7 +365,7 @@ impl Pos3D {
     }
 
     /// returns a new point with the min x and min y between the two points
-    pub fn min(self, rhs: Pos3D) -> Pos3D {
+    pub fn min(self, rhs: Pos3D<T>) -> Pos3D<T> {
         Self {
             x: self.x.min(rhs.x),
             y: self.y.min(rhs.y),
@@ -259,7 +373,7 @@ impl Pos3D {
         }
     }
 
-    pub fn max(self, rhs: Pos3D) -> Pos3D {
+    pub fn max(self, rhs: Pos3D<T>) -> Pos3D<T> {
         Self {
             x: self.x.max(rhs.x),
             y: self.y.max(rhs.y),
@@ -267,28 +381,35 @@ impl Pos3D {
         }
     }
 
-    pub fn translate_x(&mut self, tx: f32) {
+    pub fn translate_x(&mut self, tx: T) {
         self.x += tx
     }
 
-    pub fn translate_y(&mut self, ty: f32) {
+    pub fn translate_y(&mut self, ty: T) {
         self.y += ty
     }
 
-    pub fn translate_z(&mut self, tz: f32) {
+    pub fn translate_z(&mut self, tz: T) {
         self.z += tz
     }
 
-    pub fn translate(&mut self, tx: f32, ty: f32, tz: f32) {
+    pub fn translate(&mut self, tx: T, ty: T, tz: T) {
         *self = *self + Vec3D::new(tx, ty, tz)
     }
 
+    /// lossy conversion to another number type, works like `as` on each coordinate
+    pub fn cast<U: Scalar>(self) -> Pos3D<U> {
+        Pos3D::new(U::from_f64(self.x.to_f64()), U::from_f64(self.y.to_f64()), U::from_f64(self.z.to_f64()))
+    }
+}
+
+impl<T: Float> Pos3D<T> {
     /// lerp covers the states from start to end of the distance between a start point and and end point where t is the % completion 
     /// 
     /// lerp function can be simplified to
     /// 
     /// lerp(a, b, t) = a + (b - a) * t 
-    pub fn lerp(start: Pos3D, end: Pos3D, t: f32) -> Pos3D {
+    pub fn lerp(start: Pos3D<T>, end: Pos3D<T>, t: T) -> Pos3D<T> {
         Pos3D {
             x: start.x() + (end.x() - start.x()) * t,
             y: start.y() + (end.y() - start.y()) * t,
@@ -296,19 +417,65 @@ impl Pos3D {
         }
     }
     
-    pub fn lerp_steps(start: Pos3D, end: Pos3D, steps: u32) -> Vec<Pos3D> {
-        let mut v: Vec<Pos3D> = Vec::new();
+    pub fn lerp_steps(start: Pos3D<T>, end: Pos3D<T>, steps: u32) -> Vec<Pos3D<T>> {
+        let mut v: Vec<Pos3D<T>> = Vec::new();
         for i in 0..steps + 1 {
-            v.push(Pos3D::lerp(start, end, i as f32 / steps as f32));
+            v.push(Pos3D::lerp(start, end, T::from_f64(i as f64 / steps as f64)));
         }
         v
     }
+
+    /// rounds every coordinate down, floor then cast to get the cell a point is in
+    pub fn floor(self) -> Pos3D<T> {
+        Pos3D::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    pub fn ceil(self) -> Pos3D<T> {
+        Pos3D::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+
+    pub fn round(self) -> Pos3D<T> {
+        Pos3D::new(self.x.round(), self.y.round(), self.z.round())
+    }
+}
+
+impl Pos3D {
+    /// lerp with the % completion run through an easing curve first
+    pub fn ease(start: Pos3D, end: Pos3D, t: f32, easing: Easing) -> Pos3D {
+        easing::ease(start, end, t, easing)
+    }
+
+    /// eased version of lerp_steps, contains the start and end pos and steps is inclusive to the end
+    pub fn ease_steps(start: Pos3D, end: Pos3D, steps: u32, easing: Easing) -> Vec<Pos3D> {
+        easing::ease_steps(start, end, steps, easing)
+    }
+}
+
+/// grid helpers for voxels and layered tile maps
+impl<T: Integer> Pos3D<T> {
+    /// number of straight steps between two cells when we can't move diagonally
+    pub fn manhattan_distance(self, other: Pos3D<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// the cells sharing a face with this one, +x, -x, +y, -y, +z then -z
+    pub fn neighbours_6(self) -> [Pos3D<T>; 6] {
+        let (one, zero) = (T::ONE, T::ZERO);
+        [
+            self + Vec3D::new(one, zero, zero),
+            self + Vec3D::new(-one, zero, zero),
+            self + Vec3D::new(zero, one, zero),
+            self + Vec3D::new(zero, -one, zero),
+            self + Vec3D::new(zero, zero, one),
+            self + Vec3D::new(zero, zero, -one),
+        ]
+    }
 }
 
-impl Add<Vec3D> for Pos3D {
-    type Output = Pos3D;
+impl<T: Scalar> Add<Vec3D<T>> for Pos3D<T> {
+    type Output = Pos3D<T>;
 
-    fn add(self, rhs: Vec3D) -> Self {
+    fn add(self, rhs: Vec3D<T>) -> Self {
         Pos3D {
             x: self.x + rhs.dx,
             y: self.y + rhs.dy,
@@ -317,19 +484,52 @@ impl Add<Vec3D> for Pos3D {
     }
 }
 
-impl AddAssign<Vec3D> for Pos3D {
-    fn add_assign(&mut self, rhs: Vec3D) {
+impl<T: Scalar> AddAssign<Vec3D<T>> for Pos3D<T> {
+    fn add_assign(&mut self, rhs: Vec3D<T>) {
         self.x += rhs.dx;
         self.y += rhs.dy;
         self.z += rhs.dz;
     }
 }
 
+impl<T: Scalar> Sub<Vec3D<T>> for Pos3D<T> {
+    type Output = Pos3D<T>;
+
+    fn sub(self, rhs: Vec3D<T>) -> Self {
+        Pos3D {
+            x: self.x - rhs.dx,
+            y: self.y - rhs.dy,
+            z: self.z - rhs.dz,
+        }
+    }
+}
+
+impl<T: Scalar> SubAssign<Vec3D<T>> for Pos3D<T> {
+    fn sub_assign(&mut self, rhs: Vec3D<T>) {
+        self.x -= rhs.dx;
+        self.y -= rhs.dy;
+        self.z -= rhs.dz;
+    }
+}
+
+/// the vector that takes rhs to self
+impl<T: Scalar> Sub<Pos3D<T>> for Pos3D<T> {
+    type Output = Vec3D<T>;
+
+    fn sub(self, rhs: Pos3D<T>) -> Vec3D<T> {
+        Vec3D {
+            dx: self.x - rhs.x,
+            dy: self.y - rhs.y,
+            dz: self.z - rhs.z,
+        }
+    }
+}
+
 /// Scalar multiplication for Vec2D
-impl Mul<f32> for Vec2D {
-    type Output = Vec2D;
+impl<T: Scalar> Mul<T> for Vec2D<T> {
+    type Output = Vec2D<T>;
     
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self {
             dx: self.dx * rhs,
             dy: self.dy * rhs,
@@ -338,10 +538,10 @@ impl Mul<f32> for Vec2D {
 }
 
 /// Vector addition for Vec2D
-impl Add<Vec2D> for Vec2D {
-    type Output = Vec2D;
+impl<T: Scalar> Add<Vec2D<T>> for Vec2D<T> {
+    type Output = Vec2D<T>;
 
-    fn add(self, rhs: Vec2D) -> Self::Output {
+    fn add(self, rhs: Vec2D<T>) -> Self::Output {
         Self {
             dx: self.dx + rhs.dx,
             dy: self.dy + rhs.dy
@@ -350,17 +550,17 @@ impl Add<Vec2D> for Vec2D {
 }
 
 /// Vector addition for Vec2D with assignment
-impl AddAssign<Vec2D> for Vec2D {
-    fn add_assign(&mut self, rhs: Vec2D) {
+impl<T: Scalar> AddAssign<Vec2D<T>> for Vec2D<T> {
+    fn add_assign(&mut self, rhs: Vec2D<T>) {
         self.dx += rhs.dx;
         self.dy += rhs.dy;
     }
 }
 
-impl Sub<Vec2D> for Vec2D {
-    type Output = Vec2D;
+impl<T: Scalar> Sub<Vec2D<T>> for Vec2D<T> {
+    type Output = Vec2D<T>;
 
-    fn sub(self, rhs: Vec2D) -> Self::Output {
+    fn sub(self, rhs: Vec2D<T>) -> Self::Output {
         Self {
             dx: self.dx - rhs.dx,
             dy: self.dy - rhs.dy
@@ -368,57 +568,201 @@ impl Sub<Vec2D> for Vec2D {
     }
 }
 
-impl SubAssign<Vec2D> for Vec2D {
-    fn sub_assign(&mut self, rhs: Vec2D) {
+impl<T: Scalar> SubAssign<Vec2D<T>> for Vec2D<T> {
+    fn sub_assign(&mut self, rhs: Vec2D<T>) {
         self.dx -= rhs.dx;
         self.dy -= rhs.dy;
     }
 }
 
-impl Vec2D {
-    pub fn new(dx: f32, dy: f32) -> Vec2D {
+impl<T: Scalar> MulAssign<T> for Vec2D<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.dx *= rhs;
+        self.dy *= rhs;
+    }
+}
+
+/// Scalar division for Vec2D
+impl<T: Scalar> Div<T> for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            dx: self.dx / rhs,
+            dy: self.dy / rhs,
+        }
+    }
+}
+
+impl<T: Scalar> DivAssign<T> for Vec2D<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.dx /= rhs;
+        self.dy /= rhs;
+    }
+}
+
+/// flips the direction of the vector
+impl<T: Scalar> Neg for Vec2D<T> {
+    type Output = Vec2D<T>;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            dx: -self.dx,
+            dy: -self.dy,
+        }
+    }
+}
+
+impl<T: Scalar> Vec2D<T> {
+    pub const ZERO: Vec2D<T> = Vec2D {dx: T::ZERO, dy: T::ZERO};
+    pub const ONE: Vec2D<T> = Vec2D {dx: T::ONE, dy: T::ONE};
+
+    pub fn new(dx: T, dy: T) -> Vec2D<T> {
         Vec2D {
             dx: dx,
             dy: dy,
         }
     }
 
-    pub fn dx(&self) -> f32 {
+    pub fn dx(&self) -> T {
         self.dx
     }
 
-    pub fn dy(&self) -> f32 {
+    pub fn dy(&self) -> T {
         self.dy
     }
 
-    pub fn set_dx(&mut self, dx: f32) {
+    pub fn set_dx(&mut self, dx: T) {
         self.dx = dx
     }
 
-    pub fn set_dy(&mut self, dy: f32) {
+    pub fn set_dy(&mut self, dy: T) {
         self.dy = dy
     }
 
-    pub fn set(&mut self, dx: f32, dy: f32) {
+    pub fn set(&mut self, dx: T, dy: T) {
         self.dx = dx;
         self.dy = dy;
     }
 
     /// Convert Vec2D to Vec3D with a given dz
-    pub fn to_vec3d(self, dz: f32) -> Vec3D {
+    pub fn to_vec3d(self, dz: T) -> Vec3D<T> {
         Vec3D {
             dx: self.dx,
             dy: self.dy,
             dz: dz
         }
     }
+
+    pub fn dot(self, rhs: Vec2D<T>) -> T {
+        self.dx * rhs.dx + self.dy * rhs.dy
+    }
+
+    /// 2D stand in for the cross product, the z of the 3D cross product of the two vectors
+    ///
+    /// positive if rhs is counter-clockwise from self, negative if clockwise and 0 if they're parallel
+    pub fn perp_dot(self, rhs: Vec2D<T>) -> T {
+        self.dx * rhs.dy - self.dy * rhs.dx
+    }
+
+    /// the vector rotated a quarter turn counter-clockwise
+    pub fn perp(self) -> Vec2D<T> {
+        Vec2D::new(-self.dy, self.dx)
+    }
+
+    /// cheaper than length when we only need to compare distances
+    pub fn length_squared(self) -> T {
+        self.dot(self)
+    }
+
+    /// component-wise multiplication
+    pub fn mul_elem(self, rhs: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.dx * rhs.dx, self.dy * rhs.dy)
+    }
+
+    /// component-wise division
+    pub fn div_elem(self, rhs: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.dx / rhs.dx, self.dy / rhs.dy)
+    }
+
+    /// returns a new vector with the min dx and min dy of the two vectors
+    pub fn min(self, rhs: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.dx.min(rhs.dx), self.dy.min(rhs.dy))
+    }
+
+    /// returns a new vector with the max dx and max dy of the two vectors
+    pub fn max(self, rhs: Vec2D<T>) -> Vec2D<T> {
+        Vec2D::new(self.dx.max(rhs.dx), self.dy.max(rhs.dy))
+    }
+
+    pub fn abs(self) -> Vec2D<T> {
+        Vec2D::new(self.dx.abs(), self.dy.abs())
+    }
+
+    /// lossy conversion to another number type, works like `as` on each component
+    pub fn cast<U: Scalar>(self) -> Vec2D<U> {
+        Vec2D::new(U::from_f64(self.dx.to_f64()), U::from_f64(self.dy.to_f64()))
+    }
 }
 
-impl Vec3D {
-    pub const ZERO: Pos3D = Pos3D {x: 0.0, y: 0.0, z: 0.0};
-    pub const ONE: Pos3D = Pos3D {x: 1.0, y: 1.0, z: 1.0};
+impl<T: Float> Vec2D<T> {
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    /// returns a unit vector in the same direction or ZERO if the vector has no length
+    pub fn normalize(self) -> Vec2D<T> {
+        self.try_normalize().unwrap_or(Vec2D::ZERO)
+    }
 
-    pub fn new(dx: f32, dy: f32, dz: f32) -> Self {
+    /// returns a unit vector in the same direction or None if the vector has no length
+    pub fn try_normalize(self) -> Option<Vec2D<T>> {
+        let len = self.length();
+        if len == T::ZERO || !len.is_finite() {
+            return None;
+        }
+        Some(self / len)
+    }
+
+    /// unsigned angle between the two vectors in radians (0..PI)
+    pub fn angle_between(self, rhs: Vec2D<T>) -> T {
+        // atan2 stays accurate for nearly parallel vectors where acos(dot) doesn't
+        self.perp_dot(rhs).abs().atan2(self.dot(rhs))
+    }
+
+    /// the part of self that points along onto
+    pub fn project(self, onto: Vec2D<T>) -> Vec2D<T> {
+        let len_sq = onto.length_squared();
+        if len_sq == T::ZERO {
+            return Vec2D::ZERO;
+        }
+        onto * (self.dot(onto) / len_sq)
+    }
+
+    /// the part of self that is perpendicular to from, self = project + reject
+    pub fn reject(self, from: Vec2D<T>) -> Vec2D<T> {
+        self - self.project(from)
+    }
+
+    /// bounces the vector off a surface with the given unit normal
+    pub fn reflect(self, normal: Vec2D<T>) -> Vec2D<T> {
+        self - normal * ((T::ONE + T::ONE) * self.dot(normal))
+    }
+}
+
+/// grid helpers for integer offsets
+impl<T: Integer> Vec2D<T> {
+    /// total number of straight steps the offset covers
+    pub fn manhattan_length(self) -> T {
+        self.dx.abs() + self.dy.abs()
+    }
+}
+
+impl<T: Scalar> Vec3D<T> {
+    pub const ZERO: Vec3D<T> = Vec3D {dx: T::ZERO, dy: T::ZERO, dz: T::ZERO};
+    pub const ONE: Vec3D<T> = Vec3D {dx: T::ONE, dy: T::ONE, dz: T::ONE};
+
+    pub fn new(dx: T, dy: T, dz: T) -> Self {
         Self { 
             dx: dx,
             dy: dy,
@@ -426,39 +770,285 @@ impl Vec3D {
         }
     }
 
-    pub fn dx(&self) -> f32 {
+    pub fn dx(&self) -> T {
         self.dx
     }
 
-    pub fn dy(&self) -> f32 {
+    pub fn dy(&self) -> T {
         self.dy
     }
 
-    pub fn dz(&self) -> f32 {
+    pub fn dz(&self) -> T {
         self.dz
     }
 
-    pub fn set_dx(&mut self, dx: f32) {
+    pub fn set_dx(&mut self, dx: T) {
         self.dx = dx
     }
 
-    pub fn set_dy(&mut self, dy: f32) {
+    pub fn set_dy(&mut self, dy: T) {
         self.dy = dy
     }
 
-    pub fn set_dz(&mut self, dz: f32) {
+    pub fn set_dz(&mut self, dz: T) {
         self.dz = dz
     }
 
-    pub fn set(&mut self, dx: f32, dy: f32, dz: f32) {
+    pub fn set(&mut self, dx: T, dy: T, dz: T) {
         self.dx = dx;
         self.dy = dy;
         self.dz = dz;
     }
+
+    pub fn dot(self, rhs: Vec3D<T>) -> T {
+        self.dx * rhs.dx + self.dy * rhs.dy + self.dz * rhs.dz
+    }
+
+    /// vector perpendicular to both self and rhs following the right hand rule
+    pub fn cross(self, rhs: Vec3D<T>) -> Vec3D<T> {
+        Vec3D::new(
+            self.dy * rhs.dz - self.dz * rhs.dy,
+            self.dz * rhs.dx - self.dx * rhs.dz,
+            self.dx * rhs.dy - self.dy * rhs.dx,
+        )
+    }
+
+    /// cheaper than length when we only need to compare distances
+    pub fn length_squared(self) -> T {
+        self.dot(self)
+    }
+
+    /// component-wise multiplication
+    pub fn mul_elem(self, rhs: Vec3D<T>) -> Vec3D<T> {
+        Vec3D::new(self.dx * rhs.dx, self.dy * rhs.dy, self.dz * rhs.dz)
+    }
+
+    /// component-wise division
+    pub fn div_elem(self, rhs: Vec3D<T>) -> Vec3D<T> {
+        Vec3D::new(self.dx / rhs.dx, self.dy / rhs.dy, self.dz / rhs.dz)
+    }
+
+    /// returns a new vector with the min of each component of the two vectors
+    pub fn min(self, rhs: Vec3D<T>) -> Vec3D<T> {
+        Vec3D::new(self.dx.min(rhs.dx), self.dy.min(rhs.dy), self.dz.min(rhs.dz))
+    }
+
+    /// returns a new vector with the max of each component of the two vectors
+    pub fn max(self, rhs: Vec3D<T>) -> Vec3D<T> {
+        Vec3D::new(self.dx.max(rhs.dx), self.dy.max(rhs.dy), self.dz.max(rhs.dz))
+    }
+
+    pub fn abs(self) -> Vec3D<T> {
+        Vec3D::new(self.dx.abs(), self.dy.abs(), self.dz.abs())
+    }
+
+    /// drops dz
+    pub fn to_vec2d(self) -> Vec2D<T> {
+        Vec2D::new(self.dx, self.dy)
+    }
+
+    /// lossy conversion to another number type, works like `as` on each component
+    pub fn cast<U: Scalar>(self) -> Vec3D<U> {
+        Vec3D::new(U::from_f64(self.dx.to_f64()), U::from_f64(self.dy.to_f64()), U::from_f64(self.dz.to_f64()))
+    }
+}
+
+impl<T: Float> Vec3D<T> {
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    /// returns a unit vector in the same direction or ZERO if the vector has no length
+    pub fn normalize(self) -> Vec3D<T> {
+        self.try_normalize().unwrap_or(Vec3D::ZERO)
+    }
+
+    /// returns a unit vector in the same direction or None if the vector has no length
+    pub fn try_normalize(self) -> Option<Vec3D<T>> {
+        let len = self.length();
+        if len == T::ZERO || !len.is_finite() {
+            return None;
+        }
+        Some(self / len)
+    }
+
+    /// unsigned angle between the two vectors in radians (0..PI)
+    pub fn angle_between(self, rhs: Vec3D<T>) -> T {
+        self.cross(rhs).length().atan2(self.dot(rhs))
+    }
+
+    /// the part of self that points along onto
+    pub fn project(self, onto: Vec3D<T>) -> Vec3D<T> {
+        let len_sq = onto.length_squared();
+        if len_sq == T::ZERO {
+            return Vec3D::ZERO;
+        }
+        onto * (self.dot(onto) / len_sq)
+    }
+
+    /// the part of self that is perpendicular to from, self = project + reject
+    pub fn reject(self, from: Vec3D<T>) -> Vec3D<T> {
+        self - self.project(from)
+    }
+
+    /// bounces the vector off a surface with the given unit normal
+    pub fn reflect(self, normal: Vec3D<T>) -> Vec3D<T> {
+        self - normal * ((T::ONE + T::ONE) * self.dot(normal))
+    }
+}
+
+/// Vector addition for Vec3D
+impl<T: Scalar> Add<Vec3D<T>> for Vec3D<T> {
+    type Output = Vec3D<T>;
+
+    fn add(self, rhs: Vec3D<T>) -> Self::Output {
+        Self {
+            dx: self.dx + rhs.dx,
+            dy: self.dy + rhs.dy,
+            dz: self.dz + rhs.dz,
+        }
+    }
+}
+
+impl<T: Scalar> AddAssign<Vec3D<T>> for Vec3D<T> {
+    fn add_assign(&mut self, rhs: Vec3D<T>) {
+        self.dx += rhs.dx;
+        self.dy += rhs.dy;
+        self.dz += rhs.dz;
+    }
 }
 
+impl<T: Scalar> Sub<Vec3D<T>> for Vec3D<T> {
+    type Output = Vec3D<T>;
+
+    fn sub(self, rhs: Vec3D<T>) -> Self::Output {
+        Self {
+            dx: self.dx - rhs.dx,
+            dy: self.dy - rhs.dy,
+            dz: self.dz - rhs.dz,
+        }
+    }
+}
+
+impl<T: Scalar> SubAssign<Vec3D<T>> for Vec3D<T> {
+    fn sub_assign(&mut self, rhs: Vec3D<T>) {
+        self.dx -= rhs.dx;
+        self.dy -= rhs.dy;
+        self.dz -= rhs.dz;
+    }
+}
+
+/// Scalar multiplication for Vec3D
+impl<T: Scalar> Mul<T> for Vec3D<T> {
+    type Output = Vec3D<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            dx: self.dx * rhs,
+            dy: self.dy * rhs,
+            dz: self.dz * rhs,
+        }
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Vec3D<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.dx *= rhs;
+        self.dy *= rhs;
+        self.dz *= rhs;
+    }
+}
+
+/// Scalar division for Vec3D
+impl<T: Scalar> Div<T> for Vec3D<T> {
+    type Output = Vec3D<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            dx: self.dx / rhs,
+            dy: self.dy / rhs,
+            dz: self.dz / rhs,
+        }
+    }
+}
+
+impl<T: Scalar> DivAssign<T> for Vec3D<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.dx /= rhs;
+        self.dy /= rhs;
+        self.dz /= rhs;
+    }
+}
+
+/// flips the direction of the vector
+impl<T: Scalar> Neg for Vec3D<T> {
+    type Output = Vec3D<T>;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            dx: -self.dx,
+            dy: -self.dy,
+            dz: -self.dz,
+        }
+    }
+}
+
+/// Scalar multiplication with the scalar on the left, these have to be spelled out per number type
+macro_rules! impl_scalar_lhs_mul {
+    ($($t:ty),*) => {$(
+        impl Mul<Vec2D<$t>> for $t {
+            type Output = Vec2D<$t>;
+
+            fn mul(self, rhs: Vec2D<$t>) -> Self::Output {
+                rhs * self
+            }
+        }
+
+        impl Mul<Vec3D<$t>> for $t {
+            type Output = Vec3D<$t>;
+
+            fn mul(self, rhs: Vec3D<$t>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs_mul!(f32, f64, i32, i64);
+
+/// Lossless conversions, everything else goes through cast
+macro_rules! impl_lossless_from {
+    ($($from:ty => $to:ty),*) => {$(
+        impl<S: Space> From<Pos2D<S, $from>> for Pos2D<S, $to> {
+            fn from(p: Pos2D<S, $from>) -> Self {
+                Pos2D::new(p.x.into(), p.y.into())
+            }
+        }
+
+        impl From<Pos3D<$from>> for Pos3D<$to> {
+            fn from(p: Pos3D<$from>) -> Self {
+                Pos3D::new(p.x.into(), p.y.into(), p.z.into())
+            }
+        }
+
+        impl From<Vec2D<$from>> for Vec2D<$to> {
+            fn from(v: Vec2D<$from>) -> Self {
+                Vec2D::new(v.dx.into(), v.dy.into())
+            }
+        }
+
+        impl From<Vec3D<$from>> for Vec3D<$to> {
+            fn from(v: Vec3D<$from>) -> Self {
+                Vec3D::new(v.dx.into(), v.dy.into(), v.dz.into())
+            }
+        }
+    )*};
+}
+
+impl_lossless_from!(f32 => f64, i32 => f64, i32 => i64);
+
 // Equality blocks we probably should've just derived them but it's too late now.
-impl PartialEq for Pos2D {
+impl<S: Space, T: Scalar> PartialEq for Pos2D<S, T> {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x && self.y == other.y
     }
@@ -468,7 +1058,7 @@ impl PartialEq for Pos2D {
     }
 }
 
-impl PartialEq for Pos3D {
+impl<T: Scalar> PartialEq for Pos3D<T> {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x && self.y == other.y && self.z == other.z
     }
@@ -478,7 +1068,7 @@ impl PartialEq for Pos3D {
     }
 }
 
-impl PartialEq for Vec2D {
+impl<T: Scalar> PartialEq for Vec2D<T> {
     fn eq(&self, other: &Self) -> bool {
         self.dx == other.dx && self.dy == other.dy
     }
@@ -488,7 +1078,7 @@ impl PartialEq for Vec2D {
     }
 }
 
-impl PartialEq for Vec3D {
+impl<T: Scalar> PartialEq for Vec3D<T> {
     fn eq(&self, other: &Self) -> bool {
         self.dx == other.dx && self.dy == other.dy && self.dz == other.dz
     }
@@ -498,26 +1088,47 @@ impl PartialEq for Vec3D {
     }
 }
 
+// integer positions compare exactly so they can be used as map keys
+impl<S: Space, T: Integer> Eq for Pos2D<S, T> {}
+impl<T: Integer> Eq for Pos3D<T> {}
+impl<T: Integer> Eq for Vec2D<T> {}
+impl<T: Integer> Eq for Vec3D<T> {}
+
+impl<S: Space, T: Integer> Hash for Pos2D<S, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+impl<T: Integer> Hash for Pos3D<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+        self.z.hash(state);
+    }
+}
+
 // Formatting blocks
-impl fmt::Display for Pos2D {
+impl<S: Space, T: Scalar> fmt::Display for Pos2D<S, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Pos2D: {{ x: {}, y: {} }}", self.x, self.y)
+        write!(f, "Pos2D<{}>: {{ x: {}, y: {} }}", S::NAME, self.x, self.y)
     }
 }
 
-impl fmt::Display for Pos3D {
+impl<T: Scalar> fmt::Display for Pos3D<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Pos3D: {{ x: {}, y: {}, z: {} }}", self.x, self.y, self.z)
     }
 }
 
-impl fmt::Display for Vec2D {
+impl<T: Scalar> fmt::Display for Vec2D<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Vec2D: {{ dx: {}, dy: {} }}", self.dx, self.dy)
     }
 }
 
-impl fmt::Display for Vec3D {
+impl<T: Scalar> fmt::Display for Vec3D<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Vec3D: {{ dx: {}, dy: {}, dz: {} }}", self.dx, self.dy, self.dz)
     }
@@ -529,4 +1140,4 @@ impl fmt::Display for Vec3D {
 //             writeln!(f, "Step {}: {}", v, self.get(v))
 //         }
 //     }
-// }
\ No newline at end of file
+// }
@@ -0,0 +1,255 @@
+//! Axis-aligned bounding boxes
+//! Boxes lined up with the axes so culling and clipping only need min/max checks.
+
+use std::fmt;
+
+use super::{Pos2D, Pos3D, Space, Vec2D, Vec3D, World};
+
+/// ## Aabb2D
+/// 2D box from min (smallest x and y) to max (largest x and y) in the coordinate space S.
+/// edges count as inside.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb2D<S: Space = World> {
+    min: Pos2D<S>,
+    max: Pos2D<S>,
+}
+
+/// ## Aabb3D
+/// 3D box from min to max, edges count as inside.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb3D {
+    min: Pos3D,
+    max: Pos3D,
+}
+
+impl<S: Space> Aabb2D<S> {
+    /// box between two corners, the corners can be given in any order
+    pub fn new(a: Pos2D<S>, b: Pos2D<S>) -> Aabb2D<S> {
+        Aabb2D { min: a.min(b), max: a.max(b) }
+    }
+
+    /// box from a center point and half the width and height
+    pub fn from_center(center: Pos2D<S>, half_extents: Vec2D) -> Aabb2D<S> {
+        let half = half_extents.abs();
+        Aabb2D { min: center - half, max: center + half }
+    }
+
+    /// smallest box around every point or None if there aren't any points
+    pub fn from_points(points: &[Pos2D<S>]) -> Option<Aabb2D<S>> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Aabb2D { min: first, max: first }, |b, p| b.expand_to(*p)))
+    }
+
+    pub fn min(&self) -> Pos2D<S> {
+        self.min
+    }
+
+    pub fn max(&self) -> Pos2D<S> {
+        self.max
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x() - self.min.x()
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y() - self.min.y()
+    }
+
+    /// full width and height of the box
+    pub fn size(&self) -> Vec2D {
+        self.max - self.min
+    }
+
+    /// half of the width and height, the distance from the center to an edge
+    pub fn extents(&self) -> Vec2D {
+        self.size() / 2.0
+    }
+
+    pub fn center(&self) -> Pos2D<S> {
+        Pos2D::lerp(self.min, self.max, 0.5)
+    }
+
+    /// true if the box has no area
+    pub fn is_empty(&self) -> bool {
+        self.width() <= 0.0 || self.height() <= 0.0
+    }
+
+    pub fn contains(&self, p: &Pos2D<S>) -> bool {
+        p.x() >= self.min.x() && p.x() <= self.max.x() && p.y() >= self.min.y() && p.y() <= self.max.y()
+    }
+
+    /// true if other is completely inside self
+    pub fn contains_aabb(&self, other: &Aabb2D<S>) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// true if the two boxes share any area, touching edges count
+    pub fn overlaps(&self, other: &Aabb2D<S>) -> bool {
+        self.min.x() <= other.max.x() && self.max.x() >= other.min.x()
+            && self.min.y() <= other.max.y() && self.max.y() >= other.min.y()
+    }
+
+    /// smallest box around both boxes
+    pub fn union(&self, other: &Aabb2D<S>) -> Aabb2D<S> {
+        Aabb2D { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// the shared area of both boxes or None if they don't overlap
+    pub fn intersection(&self, other: &Aabb2D<S>) -> Option<Aabb2D<S>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Aabb2D { min: self.min.max(other.min), max: self.max.min(other.max) })
+    }
+
+    /// the part of the box that is inside the viewport, None if it is fully outside
+    pub fn clamp_to(&self, viewport: &Aabb2D<S>) -> Option<Aabb2D<S>> {
+        self.intersection(viewport)
+    }
+
+    /// grows the box by amount on every side, a negative amount shrinks it.
+    /// shrinking a side past the center flattens it onto the center instead of turning the box inside out
+    pub fn expand(&self, amount: f32) -> Aabb2D<S> {
+        let v = Vec2D::new(amount, amount);
+        let center = self.center();
+        Aabb2D { min: (self.min - v).min(center), max: (self.max + v).max(center) }
+    }
+
+    /// smallest box that holds self and the point p
+    pub fn expand_to(&self, p: Pos2D<S>) -> Aabb2D<S> {
+        Aabb2D { min: self.min.min(p), max: self.max.max(p) }
+    }
+
+    /// the closest point inside the box to p
+    pub fn clamp_point(&self, p: Pos2D<S>) -> Pos2D<S> {
+        p.max(self.min).min(self.max)
+    }
+
+    /// the four corners starting at min and going counter-clockwise (with y up)
+    pub fn corners(&self) -> [Pos2D<S>; 4] {
+        [
+            self.min,
+            Pos2D::new(self.max.x(), self.min.y()),
+            self.max,
+            Pos2D::new(self.min.x(), self.max.y()),
+        ]
+    }
+}
+
+impl Aabb3D {
+    /// box between two corners, the corners can be given in any order
+    pub fn new(a: Pos3D, b: Pos3D) -> Aabb3D {
+        Aabb3D { min: a.min(b), max: a.max(b) }
+    }
+
+    /// box from a center point and half the size on each axis
+    pub fn from_center(center: Pos3D, half_extents: Vec3D) -> Aabb3D {
+        let half = half_extents.abs();
+        Aabb3D { min: center - half, max: center + half }
+    }
+
+    /// smallest box around every point or None if there aren't any points
+    pub fn from_points(points: &[Pos3D]) -> Option<Aabb3D> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Aabb3D { min: first, max: first }, |b, p| b.expand_to(*p)))
+    }
+
+    pub fn min(&self) -> Pos3D {
+        self.min
+    }
+
+    pub fn max(&self) -> Pos3D {
+        self.max
+    }
+
+    /// full size of the box on each axis
+    pub fn size(&self) -> Vec3D {
+        self.max - self.min
+    }
+
+    /// half the size on each axis, the distance from the center to a face
+    pub fn extents(&self) -> Vec3D {
+        self.size() / 2.0
+    }
+
+    pub fn center(&self) -> Pos3D {
+        Pos3D::lerp(self.min, self.max, 0.5)
+    }
+
+    /// true if the box has no volume
+    pub fn is_empty(&self) -> bool {
+        let size = self.size();
+        size.dx() <= 0.0 || size.dy() <= 0.0 || size.dz() <= 0.0
+    }
+
+    pub fn contains(&self, p: &Pos3D) -> bool {
+        p.x() >= self.min.x() && p.x() <= self.max.x()
+            && p.y() >= self.min.y() && p.y() <= self.max.y()
+            && p.z() >= self.min.z() && p.z() <= self.max.z()
+    }
+
+    /// true if other is completely inside self
+    pub fn contains_aabb(&self, other: &Aabb3D) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// true if the two boxes share any volume, touching faces count
+    pub fn overlaps(&self, other: &Aabb3D) -> bool {
+        self.min.x() <= other.max.x() && self.max.x() >= other.min.x()
+            && self.min.y() <= other.max.y() && self.max.y() >= other.min.y()
+            && self.min.z() <= other.max.z() && self.max.z() >= other.min.z()
+    }
+
+    /// smallest box around both boxes
+    pub fn union(&self, other: &Aabb3D) -> Aabb3D {
+        Aabb3D { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// the shared volume of both boxes or None if they don't overlap
+    pub fn intersection(&self, other: &Aabb3D) -> Option<Aabb3D> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Aabb3D { min: self.min.max(other.min), max: self.max.min(other.max) })
+    }
+
+    /// the part of the box that is inside the viewing volume, None if it is fully outside
+    pub fn clamp_to(&self, viewport: &Aabb3D) -> Option<Aabb3D> {
+        self.intersection(viewport)
+    }
+
+    /// grows the box by amount on every side, a negative amount shrinks it.
+    /// shrinking a side past the center flattens it onto the center instead of turning the box inside out
+    pub fn expand(&self, amount: f32) -> Aabb3D {
+        let v = Vec3D::new(amount, amount, amount);
+        let center = self.center();
+        Aabb3D { min: (self.min - v).min(center), max: (self.max + v).max(center) }
+    }
+
+    /// smallest box that holds self and the point p
+    pub fn expand_to(&self, p: Pos3D) -> Aabb3D {
+        Aabb3D { min: self.min.min(p), max: self.max.max(p) }
+    }
+
+    /// the closest point inside the box to p
+    pub fn clamp_point(&self, p: Pos3D) -> Pos3D {
+        p.max(self.min).min(self.max)
+    }
+}
+
+impl<S: Space> fmt::Display for Aabb2D<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Aabb2D<{}>: {{ min: ({}, {}), max: ({}, {}) }}", S::NAME, self.min.x(), self.min.y(), self.max.x(), self.max.y())
+    }
+}
+
+impl fmt::Display for Aabb3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Aabb3D: {{ min: ({}, {}, {}), max: ({}, {}, {}) }}",
+            self.min.x(), self.min.y(), self.min.z(), self.max.x(), self.max.y(), self.max.z()
+        )
+    }
+}
@@ -0,0 +1,186 @@
+//! Angles
+//! Radians and Degrees wrap a bare number so a function can say which unit it wants and the compiler holds us to it.
+//! Everything in the engine works in radians, Degrees is for people typing angles in.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use super::Float;
+
+/// ## Radians
+/// an angle in radians, a full turn is 2 * PI
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Radians<T = f32>(T);
+
+/// ## Degrees
+/// an angle in degrees, a full turn is 360
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Degrees<T = f32>(T);
+
+impl<T: Float> Radians<T> {
+    pub const ZERO: Radians<T> = Radians(T::ZERO);
+
+    pub fn new(radians: T) -> Radians<T> {
+        Radians(radians)
+    }
+
+    /// a full turn
+    pub fn tau() -> Radians<T> {
+        Radians(T::PI + T::PI)
+    }
+
+    /// half a turn
+    pub fn pi() -> Radians<T> {
+        Radians(T::PI)
+    }
+
+    /// the raw number of radians
+    pub fn value(self) -> T {
+        self.0
+    }
+
+    pub fn to_degrees(self) -> Degrees<T> {
+        Degrees(self.0 * T::from_f64(180.0) / T::PI)
+    }
+
+    pub fn sin(self) -> T {
+        self.0.sin()
+    }
+
+    pub fn cos(self) -> T {
+        self.0.cos()
+    }
+
+    /// the same angle in -PI..PI
+    pub fn wrap_signed(self) -> Radians<T> {
+        let tau = Self::tau().0;
+        Radians(self.0 - tau * ((self.0 + T::PI) / tau).floor())
+    }
+
+    /// the same angle in 0..2PI
+    pub fn wrap_positive(self) -> Radians<T> {
+        let tau = Self::tau().0;
+        let wrapped = self.0 - tau * (self.0 / tau).floor();
+        // a tiny negative angle can round up to a whole turn
+        Radians(if wrapped >= tau { T::ZERO } else { wrapped })
+    }
+
+    /// the signed turn that takes self to other the short way around, in -PI..PI
+    pub fn shortest_to(self, other: Radians<T>) -> Radians<T> {
+        (other - self).wrap_signed()
+    }
+
+    /// lerp between two angles the short way around where t is the % completion
+    pub fn lerp(start: Radians<T>, end: Radians<T>, t: T) -> Radians<T> {
+        start + start.shortest_to(end) * t
+    }
+}
+
+impl<T: Float> Degrees<T> {
+    pub const ZERO: Degrees<T> = Degrees(T::ZERO);
+
+    pub fn new(degrees: T) -> Degrees<T> {
+        Degrees(degrees)
+    }
+
+    /// the raw number of degrees
+    pub fn value(self) -> T {
+        self.0
+    }
+
+    pub fn to_radians(self) -> Radians<T> {
+        Radians(self.0 * T::PI / T::from_f64(180.0))
+    }
+
+    /// the same angle in -180..180
+    pub fn wrap_signed(self) -> Degrees<T> {
+        self.to_radians().wrap_signed().to_degrees()
+    }
+
+    /// the same angle in 0..360
+    pub fn wrap_positive(self) -> Degrees<T> {
+        self.to_radians().wrap_positive().to_degrees()
+    }
+}
+
+impl<T: Float> From<Degrees<T>> for Radians<T> {
+    fn from(d: Degrees<T>) -> Radians<T> {
+        d.to_radians()
+    }
+}
+
+impl<T: Float> From<Radians<T>> for Degrees<T> {
+    fn from(r: Radians<T>) -> Degrees<T> {
+        r.to_degrees()
+    }
+}
+
+macro_rules! impl_angle_ops {
+    ($($angle:ident),*) => {$(
+        impl<T: Float> Add for $angle<T> {
+            type Output = $angle<T>;
+
+            fn add(self, rhs: $angle<T>) -> $angle<T> {
+                $angle(self.0 + rhs.0)
+            }
+        }
+
+        impl<T: Float> AddAssign for $angle<T> {
+            fn add_assign(&mut self, rhs: $angle<T>) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl<T: Float> Sub for $angle<T> {
+            type Output = $angle<T>;
+
+            fn sub(self, rhs: $angle<T>) -> $angle<T> {
+                $angle(self.0 - rhs.0)
+            }
+        }
+
+        impl<T: Float> SubAssign for $angle<T> {
+            fn sub_assign(&mut self, rhs: $angle<T>) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl<T: Float> Mul<T> for $angle<T> {
+            type Output = $angle<T>;
+
+            fn mul(self, rhs: T) -> $angle<T> {
+                $angle(self.0 * rhs)
+            }
+        }
+
+        impl<T: Float> Div<T> for $angle<T> {
+            type Output = $angle<T>;
+
+            fn div(self, rhs: T) -> $angle<T> {
+                $angle(self.0 / rhs)
+            }
+        }
+
+        impl<T: Float> Neg for $angle<T> {
+            type Output = $angle<T>;
+
+            fn neg(self) -> $angle<T> {
+                $angle(-self.0)
+            }
+        }
+    )*};
+}
+
+impl_angle_ops!(Radians, Degrees);
+
+impl<T: Float> fmt::Display for Radians<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rad", self.0)
+    }
+}
+
+impl<T: Float> fmt::Display for Degrees<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°", self.0)
+    }
+}
@@ -0,0 +1,288 @@
+//! Approximate equality
+//! Float math picks up rounding error, so after a rotation or a normalize two values that should be equal rarely are.
+//! ApproxEq compares within a tolerance instead, component by component for the compound types.
+//!
+//! - abs_diff_eq: |a - b| <= epsilon, good near zero
+//! - relative_eq: |a - b| <= max_relative * the larger of |a| and |b|, good for large values
+//! - ulps_eq: a and b are at most max_ulps representable floats apart
+//!
+//! relative_eq and ulps_eq fall back to abs_diff_eq first so values around zero still compare equal.
+//! resource: https://randomascii.wordpress.com/2012/02/25/comparing-floating-point-numbers-2012-edition/
+
+use super::{
+    Aabb2D, Aabb3D, CatmullRom, CubicBezier, Cylindrical, Degrees, Float, Mat3, Mat4, Pos2D, Pos3D, QuadraticBezier,
+    Quat, Radians, Ray2D, Ray3D, RayHit2D, RayHit3D, Scalar, Space, Spherical, SplinePoint, Vec2D, Vec3D,
+};
+
+/// ## ApproxEq
+/// equality within a tolerance
+pub trait ApproxEq {
+    /// the type tolerances are given in
+    type Epsilon: Copy;
+
+    /// absolute tolerance used when none is given
+    fn default_epsilon() -> Self::Epsilon;
+
+    /// relative tolerance used when none is given
+    fn default_max_relative() -> Self::Epsilon;
+
+    /// ulps tolerance used when none is given
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool;
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+    /// relative_eq with the default tolerances, what most comparisons want
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.relative_eq(other, Self::default_epsilon(), Self::default_max_relative())
+    }
+}
+
+macro_rules! impl_approx_eq_float {
+    ($($t:ty, $bits:ty, $epsilon:expr);*) => {$(
+        impl ApproxEq for $t {
+            type Epsilon = $t;
+
+            fn default_epsilon() -> $t {
+                $epsilon
+            }
+
+            fn default_max_relative() -> $t {
+                $epsilon
+            }
+
+            fn abs_diff_eq(&self, other: &$t, epsilon: $t) -> bool {
+                // exact check first so infinities equal themselves
+                self == other || (self - other).abs() <= epsilon
+            }
+
+            fn relative_eq(&self, other: &$t, epsilon: $t, max_relative: $t) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                if self.is_infinite() || other.is_infinite() {
+                    return false;
+                }
+                (self - other).abs() <= self.abs().max(other.abs()) * max_relative
+            }
+
+            fn ulps_eq(&self, other: &$t, epsilon: $t, max_ulps: u32) -> bool {
+                if self.abs_diff_eq(other, epsilon) {
+                    return true;
+                }
+                // floats on either side of zero are never a few ulps apart, and NaN equals nothing
+                if self.is_sign_negative() != other.is_sign_negative() || self.is_nan() || other.is_nan() {
+                    return false;
+                }
+                // same sign floats sort the same way as their bit patterns
+                let (a, b) = (self.to_bits(), other.to_bits());
+                let ulps: $bits = if a > b { a - b } else { b - a };
+                ulps <= max_ulps as $bits
+            }
+        }
+    )*};
+}
+
+// loose enough to absorb the error of a few rotations or a normalize
+impl_approx_eq_float!(f32, u32, 1e-5; f64, u64, 1e-12);
+
+/// implements ApproxEq for a type by comparing a list of its parts, written like
+///
+/// `impl_approx_eq_by_parts!([generics] Type, PartType, |s| [s.a(), s.b()]);`
+///
+/// every part has to share the part type's Epsilon, a tuple works when the parts aren't all the same type
+macro_rules! impl_approx_eq_by_parts {
+    ([$($gen:tt)*] $ty:ty, $part:ty, |$s:ident| $parts:expr) => {
+        impl<$($gen)*> $crate::util::linear::ApproxEq for $ty {
+            type Epsilon = <$part as $crate::util::linear::ApproxEq>::Epsilon;
+
+            fn default_epsilon() -> Self::Epsilon {
+                <$part as $crate::util::linear::ApproxEq>::default_epsilon()
+            }
+
+            fn default_max_relative() -> Self::Epsilon {
+                <$part as $crate::util::linear::ApproxEq>::default_max_relative()
+            }
+
+            fn default_max_ulps() -> u32 {
+                <$part as $crate::util::linear::ApproxEq>::default_max_ulps()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let $s = self;
+                let ours = $parts;
+                let $s = other;
+                ours.abs_diff_eq(&$parts, epsilon)
+            }
+
+            fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+                let $s = self;
+                let ours = $parts;
+                let $s = other;
+                ours.relative_eq(&$parts, epsilon, max_relative)
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                let $s = self;
+                let ours = $parts;
+                let $s = other;
+                ours.ulps_eq(&$parts, epsilon, max_ulps)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_approx_eq_by_parts;
+
+/// element by element, slices of different lengths are never equal
+impl<T: ApproxEq> ApproxEq for [T] {
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn abs_diff_eq(&self, other: &[T], epsilon: T::Epsilon) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(&self, other: &[T], epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &[T], epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+impl_approx_eq_by_parts!([T: ApproxEq, const N: usize] [T; N], T, |s| &s[..]);
+impl_approx_eq_by_parts!([T: ApproxEq] Vec<T>, T, |s| &s[..]);
+
+/// pairs of parts with different types, both have to take the same Epsilon
+impl<A: ApproxEq, B: ApproxEq<Epsilon = A::Epsilon>> ApproxEq for (A, B) {
+    type Epsilon = A::Epsilon;
+
+    fn default_epsilon() -> A::Epsilon {
+        A::default_epsilon()
+    }
+
+    fn default_max_relative() -> A::Epsilon {
+        A::default_max_relative()
+    }
+
+    fn default_max_ulps() -> u32 {
+        A::default_max_ulps()
+    }
+
+    fn abs_diff_eq(&self, other: &(A, B), epsilon: A::Epsilon) -> bool {
+        self.0.abs_diff_eq(&other.0, epsilon) && self.1.abs_diff_eq(&other.1, epsilon)
+    }
+
+    fn relative_eq(&self, other: &(A, B), epsilon: A::Epsilon, max_relative: A::Epsilon) -> bool {
+        self.0.relative_eq(&other.0, epsilon, max_relative) && self.1.relative_eq(&other.1, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &(A, B), epsilon: A::Epsilon, max_ulps: u32) -> bool {
+        self.0.ulps_eq(&other.0, epsilon, max_ulps) && self.1.ulps_eq(&other.1, epsilon, max_ulps)
+    }
+}
+
+impl_approx_eq_by_parts!([S: Space, T: Scalar + ApproxEq] Pos2D<S, T>, T, |s| [s.x, s.y]);
+impl_approx_eq_by_parts!([T: Scalar + ApproxEq] Pos3D<T>, T, |s| [s.x, s.y, s.z]);
+impl_approx_eq_by_parts!([T: Scalar + ApproxEq] Vec2D<T>, T, |s| [s.dx, s.dy]);
+impl_approx_eq_by_parts!([T: Scalar + ApproxEq] Vec3D<T>, T, |s| [s.dx, s.dy, s.dz]);
+
+// angles compare their raw values, wrap them first if a whole turn apart should count as equal
+impl_approx_eq_by_parts!([T: Float + ApproxEq] Radians<T>, T, |s| [s.value()]);
+impl_approx_eq_by_parts!([T: Float + ApproxEq] Degrees<T>, T, |s| [s.value()]);
+impl_approx_eq_by_parts!([T: Float + ApproxEq] Spherical<T>, T, |s| [s.radius(), s.polar().value(), s.azimuth().value()]);
+impl_approx_eq_by_parts!([T: Float + ApproxEq] Cylindrical<T>, T, |s| [s.radius(), s.azimuth().value(), s.height()]);
+
+impl_approx_eq_by_parts!([] Mat3, f32, |s| s.rows());
+impl_approx_eq_by_parts!([] Mat4, f32, |s| s.rows());
+// component wise, q and -q are the same rotation but they don't compare equal
+impl_approx_eq_by_parts!([] Quat, f32, |s| [s.w(), s.x(), s.y(), s.z()]);
+
+impl_approx_eq_by_parts!([S: Space] Aabb2D<S>, f32, |s| [s.min(), s.max()]);
+impl_approx_eq_by_parts!([] Aabb3D, f32, |s| [s.min(), s.max()]);
+
+impl_approx_eq_by_parts!([S: Space] Ray2D<S>, f32, |s| (s.origin(), s.direction()));
+impl_approx_eq_by_parts!([] Ray3D, f32, |s| (s.origin(), s.direction()));
+impl_approx_eq_by_parts!([S: Space] RayHit2D<S>, f32, |s| (s.distance(), (s.point(), s.normal())));
+impl_approx_eq_by_parts!([] RayHit3D, f32, |s| (s.distance(), (s.point(), s.normal())));
+
+impl_approx_eq_by_parts!([P: SplinePoint + ApproxEq] QuadraticBezier<P>, P, |s| [s.p0(), s.p1(), s.p2()]);
+impl_approx_eq_by_parts!([P: SplinePoint + ApproxEq] CubicBezier<P>, P, |s| [s.p0(), s.p1(), s.p2(), s.p3()]);
+impl_approx_eq_by_parts!([P: SplinePoint + ApproxEq] CatmullRom<P>, P, |s| s.points());
+
+/// the default epsilon of whatever type value is, lets the assert macros fill in the tolerance they weren't given
+#[doc(hidden)]
+pub fn default_epsilon_of<T: ApproxEq + ?Sized>(_value: &T) -> T::Epsilon {
+    T::default_epsilon()
+}
+
+/// asserts two values are equal within a tolerance, see ApproxEq
+///
+/// - `assert_approx_eq!(a, b)` uses relative_eq with the default tolerances
+/// - `assert_approx_eq!(a, b, epsilon = e)` uses abs_diff_eq
+/// - `assert_approx_eq!(a, b, max_relative = r)` uses relative_eq
+/// - `assert_approx_eq!(a, b, ulps = n)` uses ulps_eq
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(, $tolerance:ident = $value:expr)? $(,)?) => {
+        $crate::__assert_approx!(true, $left, $right $(, $tolerance = $value)?)
+    };
+}
+
+/// asserts two values are not equal within a tolerance, takes the same forms as assert_approx_eq
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($left:expr, $right:expr $(, $tolerance:ident = $value:expr)? $(,)?) => {
+        $crate::__assert_approx!(false, $left, $right $(, $tolerance = $value)?)
+    };
+}
+
+// shared body of the assert macros, not part of the public api
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_approx {
+    (@cmp $a:ident, $b:ident) => {
+        $crate::util::linear::ApproxEq::approx_eq($a, $b)
+    };
+    (@cmp $a:ident, $b:ident, epsilon = $eps:expr) => {
+        $crate::util::linear::ApproxEq::abs_diff_eq($a, $b, $eps)
+    };
+    (@cmp $a:ident, $b:ident, max_relative = $rel:expr) => {
+        $crate::util::linear::ApproxEq::relative_eq($a, $b, $crate::util::linear::default_epsilon_of($a), $rel)
+    };
+    (@cmp $a:ident, $b:ident, ulps = $ulps:expr) => {
+        $crate::util::linear::ApproxEq::ulps_eq($a, $b, $crate::util::linear::default_epsilon_of($a), $ulps)
+    };
+    ($expect:expr, $left:expr, $right:expr $(, $tolerance:ident = $value:expr)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if $crate::__assert_approx!(@cmp left, right $(, $tolerance = $value)?) != $expect {
+                    panic!(
+                        "assertion `left {} right` failed\n  left: {:?}\n right: {:?}",
+                        if $expect { "~=" } else { "!~=" },
+                        left,
+                        right
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,540 @@
+//! Matrix
+//! Matrix types for moving whole shapes and cameras around in one go instead of hand-rolling offsets.
+//! We use column vectors so a transform reads right to left, i.e. (translation * rotation) * p rotates p first.
+
+use std::ops::{Mul, MulAssign};
+use std::fmt;
+
+use super::{Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+/// ## Mat3
+/// 3x3 matrix for 2D affine transforms (translation, rotation, scale and shear).
+/// stored row-major so m[row][col] matches how you'd write it on paper.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat3 {
+    m: [[f32; 3]; 3],
+}
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3 {
+        m: [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const ZERO: Mat3 = Mat3 { m: [[0.0; 3]; 3] };
+
+    /// create a matrix from its rows
+    pub const fn new(m: [[f32; 3]; 3]) -> Mat3 {
+        Mat3 { m }
+    }
+
+    /// moves points by tx and ty, vectors are left alone
+    pub const fn translation(tx: f32, ty: f32) -> Mat3 {
+        Mat3 {
+            m: [
+                [1.0, 0.0, tx],
+                [0.0, 1.0, ty],
+                [0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// counter-clockwise rotation about the origin by theta radians
+    pub fn rotation(theta: f32) -> Mat3 {
+        let (sin, cos) = theta.sin_cos();
+        Mat3 {
+            m: [
+                [cos, -sin, 0.0],
+                [sin, cos, 0.0],
+                [0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// scales about the origin
+    pub const fn scale(sx: f32, sy: f32) -> Mat3 {
+        Mat3 {
+            m: [
+                [sx, 0.0, 0.0],
+                [0.0, sy, 0.0],
+                [0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// shears x by shx * y and y by shy * x
+    pub const fn shear(shx: f32, shy: f32) -> Mat3 {
+        Mat3 {
+            m: [
+                [1.0, shx, 0.0],
+                [shy, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// rotation of theta radians about a pivot point instead of the origin
+    pub fn rotation_about<S: Space>(pivot: Pos2D<S>, theta: f32) -> Mat3 {
+        Mat3::translation(pivot.x(), pivot.y())
+            * Mat3::rotation(theta)
+            * Mat3::translation(-pivot.x(), -pivot.y())
+    }
+
+    /// getter for a single element
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        self.m[row][col]
+    }
+
+    /// assign a single element
+    pub fn set(&mut self, row: usize, col: usize, value: f32) {
+        self.m[row][col] = value
+    }
+
+    /// getter for the rows of the matrix
+    pub fn rows(&self) -> [[f32; 3]; 3] {
+        self.m
+    }
+
+    /// returns the translation part of the matrix
+    pub fn translation_part(&self) -> Vec2D {
+        Vec2D::new(self.m[0][2], self.m[1][2])
+    }
+
+    /// applies self and then other, same as other * self
+    pub fn then(self, other: Mat3) -> Mat3 {
+        other * self
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        let m = &self.m;
+        Mat3 {
+            m: [
+                [m[0][0], m[1][0], m[2][0]],
+                [m[0][1], m[1][1], m[2][1]],
+                [m[0][2], m[1][2], m[2][2]],
+            ],
+        }
+    }
+
+    /// determinant via cofactor expansion along the first row
+    pub fn determinant(&self) -> f32 {
+        let m = &self.m;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// returns the inverse or None if the matrix is singular (i.e. a scale of 0)
+    ///
+    /// inverse = adjugate / determinant
+    pub fn inverse(&self) -> Option<Mat3> {
+        let m = &self.m;
+        let det = self.determinant();
+        // the determinant can't be bigger than the product of the column lengths (hadamard's inequality),
+        // so measuring it against that keeps tiny but healthy matrices like a scale of 1e-4 invertible
+        let bound: f32 = (0..3).map(|col| (0..3).map(|row| m[row][col] * m[row][col]).sum::<f32>().sqrt()).product();
+        if !det.is_finite() || det.abs() <= bound * f32::EPSILON {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        Some(Mat3 {
+            m: [
+                [
+                    (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+                    (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+                    (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+                ],
+                [
+                    (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+                    (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+                    (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+                ],
+                [
+                    (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+                    (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+                    (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+                ],
+            ],
+        })
+    }
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Mat3::IDENTITY
+    }
+}
+
+/// matrix composition
+impl Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, rhs: Mat3) -> Self::Output {
+        let mut out = Mat3::ZERO;
+        for row in 0..3 {
+            for col in 0..3 {
+                out.m[row][col] = (0..3).map(|k| self.m[row][k] * rhs.m[k][col]).sum();
+            }
+        }
+        out
+    }
+}
+
+impl MulAssign<Mat3> for Mat3 {
+    fn mul_assign(&mut self, rhs: Mat3) {
+        *self = *self * rhs
+    }
+}
+
+/// transforms a point, points are affected by translation (w = 1)
+///
+/// the point stays in the space it started in, use the Camera2D conversions to change spaces
+impl<S: Space> Mul<Pos2D<S>> for Mat3 {
+    type Output = Pos2D<S>;
+
+    fn mul(self, rhs: Pos2D<S>) -> Self::Output {
+        let m = &self.m;
+        Pos2D::new(
+            m[0][0] * rhs.x() + m[0][1] * rhs.y() + m[0][2],
+            m[1][0] * rhs.x() + m[1][1] * rhs.y() + m[1][2],
+        )
+    }
+}
+
+/// transforms a vector, vectors are NOT affected by translation (w = 0)
+impl Mul<Vec2D> for Mat3 {
+    type Output = Vec2D;
+
+    fn mul(self, rhs: Vec2D) -> Self::Output {
+        let m = &self.m;
+        Vec2D::new(
+            m[0][0] * rhs.dx() + m[0][1] * rhs.dy(),
+            m[1][0] * rhs.dx() + m[1][1] * rhs.dy(),
+        )
+    }
+}
+
+impl fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Mat3: {{")?;
+        for row in self.m.iter() {
+            writeln!(f, "  [{}, {}, {}]", row[0], row[1], row[2])?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// ## Mat4
+/// 4x4 matrix for 3D transforms, views and projections.
+/// right-handed with OpenGL style clip space, so after the divide everything visible lands in -1..1 on every axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Mat4 {
+    m: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        m: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const ZERO: Mat4 = Mat4 { m: [[0.0; 4]; 4] };
+
+    /// create a matrix from its rows
+    pub const fn new(m: [[f32; 4]; 4]) -> Mat4 {
+        Mat4 { m }
+    }
+
+    /// moves points by tx, ty and tz, vectors are left alone
+    pub const fn translation(tx: f32, ty: f32, tz: f32) -> Mat4 {
+        Mat4 {
+            m: [
+                [1.0, 0.0, 0.0, tx],
+                [0.0, 1.0, 0.0, ty],
+                [0.0, 0.0, 1.0, tz],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// scales about the origin
+    pub const fn scale(sx: f32, sy: f32, sz: f32) -> Mat4 {
+        Mat4 {
+            m: [
+                [sx, 0.0, 0.0, 0.0],
+                [0.0, sy, 0.0, 0.0],
+                [0.0, 0.0, sz, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// counter-clockwise rotation about the x axis by theta radians
+    pub fn rotation_x(theta: f32) -> Mat4 {
+        let (sin, cos) = theta.sin_cos();
+        Mat4 {
+            m: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, cos, -sin, 0.0],
+                [0.0, sin, cos, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// counter-clockwise rotation about the y axis by theta radians
+    pub fn rotation_y(theta: f32) -> Mat4 {
+        let (sin, cos) = theta.sin_cos();
+        Mat4 {
+            m: [
+                [cos, 0.0, sin, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [-sin, 0.0, cos, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// counter-clockwise rotation about the z axis by theta radians
+    pub fn rotation_z(theta: f32) -> Mat4 {
+        let (sin, cos) = theta.sin_cos();
+        Mat4 {
+            m: [
+                [cos, -sin, 0.0, 0.0],
+                [sin, cos, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// perspective projection
+    ///
+    /// fov is the vertical field of view in radians and aspect is width / height.
+    /// the camera looks down -z so near and far are positive distances in front of it.
+    pub fn perspective(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        let f = 1.0 / (fov / 2.0).tan();
+        let depth = near - far;
+        Mat4 {
+            m: [
+                [f / aspect, 0.0, 0.0, 0.0],
+                [0.0, f, 0.0, 0.0],
+                [0.0, 0.0, (far + near) / depth, (2.0 * far * near) / depth],
+                [0.0, 0.0, -1.0, 0.0],
+            ],
+        }
+    }
+
+    /// orthographic projection of the box left..right, bottom..top, -near..-far
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+        let (w, h, d) = (right - left, top - bottom, far - near);
+        Mat4 {
+            m: [
+                [2.0 / w, 0.0, 0.0, -(right + left) / w],
+                [0.0, 2.0 / h, 0.0, -(top + bottom) / h],
+                [0.0, 0.0, -2.0 / d, -(far + near) / d],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// view matrix for a camera at eye looking at target
+    ///
+    /// up only needs to be roughly up, it gets straightened out against the view direction.
+    pub fn look_at(eye: Pos3D, target: Pos3D, up: Vec3D) -> Mat4 {
+        let forward = (target - eye).normalize();
+        let side = forward.cross(up).normalize();
+        let true_up = side.cross(forward);
+        let e = eye - Pos3D::ZERO;
+        Mat4 {
+            m: [
+                [side.dx(), side.dy(), side.dz(), -side.dot(e)],
+                [true_up.dx(), true_up.dy(), true_up.dz(), -true_up.dot(e)],
+                [-forward.dx(), -forward.dy(), -forward.dz(), forward.dot(e)],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// getter for a single element
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        self.m[row][col]
+    }
+
+    /// assign a single element
+    pub fn set(&mut self, row: usize, col: usize, value: f32) {
+        self.m[row][col] = value
+    }
+
+    /// getter for the rows of the matrix
+    pub fn rows(&self) -> [[f32; 4]; 4] {
+        self.m
+    }
+
+    /// applies self and then other, same as other * self
+    pub fn then(self, other: Mat4) -> Mat4 {
+        other * self
+    }
+
+    /// multiplies a raw homogeneous coordinate without any divide
+    pub fn transform4(&self, v: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        for (row, o) in out.iter_mut().enumerate() {
+            *o = (0..4).map(|k| self.m[row][k] * v[k]).sum();
+        }
+        out
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        let mut out = Mat4::ZERO;
+        for row in 0..4 {
+            for col in 0..4 {
+                out.m[row][col] = self.m[col][row];
+            }
+        }
+        out
+    }
+
+    /// determinant via gaussian elimination
+    pub fn determinant(&self) -> f32 {
+        let mut m = self.m;
+        let mut det = 1.0;
+        for col in 0..4 {
+            let pivot = Mat4::pivot_row(&m, col);
+            if m[pivot][col] == 0.0 {
+                return 0.0;
+            }
+            if pivot != col {
+                m.swap(pivot, col);
+                det = -det;
+            }
+            det *= m[col][col];
+            let pivot_row = m[col];
+            for row in m.iter_mut().skip(col + 1) {
+                let factor = row[col] / pivot_row[col];
+                for k in col..4 {
+                    row[k] -= factor * pivot_row[k];
+                }
+            }
+        }
+        det
+    }
+
+    /// returns the inverse or None if the matrix is singular
+    ///
+    /// gauss-jordan elimination with partial pivoting, we row reduce self to the identity and apply the same steps to the identity.
+    pub fn inverse(&self) -> Option<Mat4> {
+        let mut m = self.m;
+        let mut inv = Mat4::IDENTITY.m;
+        // row operations scale with the columns, so each pivot is measured against its column's biggest entry
+        // rather than a fixed epsilon that would call a scale of 1e-4 singular
+        let columns = [0, 1, 2, 3].map(|col| (0..4).map(|row| self.m[row][col].abs()).fold(0.0, f32::max));
+        for col in 0..4 {
+            let pivot = Mat4::pivot_row(&m, col);
+            if !m[pivot][col].is_finite() || m[pivot][col].abs() <= columns[col] * f32::EPSILON {
+                return None;
+            }
+            m.swap(pivot, col);
+            inv.swap(pivot, col);
+
+            let scale = 1.0 / m[col][col];
+            for k in 0..4 {
+                m[col][k] *= scale;
+                inv[col][k] *= scale;
+            }
+
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = m[row][col];
+                for k in 0..4 {
+                    m[row][k] -= factor * m[col][k];
+                    inv[row][k] -= factor * inv[col][k];
+                }
+            }
+        }
+        Some(Mat4 { m: inv })
+    }
+
+    // row at or below col with the biggest magnitude in col, keeps the elimination stable
+    fn pivot_row(m: &[[f32; 4]; 4], col: usize) -> usize {
+        (col..4)
+            .max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))
+            .unwrap_or(col)
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Mat4::IDENTITY
+    }
+}
+
+/// matrix composition
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Self::Output {
+        let mut out = Mat4::ZERO;
+        for row in 0..4 {
+            for col in 0..4 {
+                out.m[row][col] = (0..4).map(|k| self.m[row][k] * rhs.m[k][col]).sum();
+            }
+        }
+        out
+    }
+}
+
+impl MulAssign<Mat4> for Mat4 {
+    fn mul_assign(&mut self, rhs: Mat4) {
+        *self = *self * rhs
+    }
+}
+
+/// transforms a point (w = 1) and divides by the resulting w so projections land in clip space
+impl Mul<Pos3D> for Mat4 {
+    type Output = Pos3D;
+
+    fn mul(self, rhs: Pos3D) -> Self::Output {
+        let [x, y, z, w] = self.transform4([rhs.x(), rhs.y(), rhs.z(), 1.0]);
+        if w != 0.0 && w != 1.0 {
+            Pos3D::new(x / w, y / w, z / w)
+        } else {
+            Pos3D::new(x, y, z)
+        }
+    }
+}
+
+/// transforms a vector (w = 0), translation is ignored
+///
+/// affine matrices leave w at 0, a projection can give us a w to divide by so we do.
+impl Mul<Vec3D> for Mat4 {
+    type Output = Vec3D;
+
+    fn mul(self, rhs: Vec3D) -> Self::Output {
+        let [dx, dy, dz, w] = self.transform4([rhs.dx(), rhs.dy(), rhs.dz(), 0.0]);
+        if w != 0.0 && w != 1.0 {
+            Vec3D::new(dx / w, dy / w, dz / w)
+        } else {
+            Vec3D::new(dx, dy, dz)
+        }
+    }
+}
+
+impl fmt::Display for Mat4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Mat4: {{")?;
+        for row in self.m.iter() {
+            writeln!(f, "  [{}, {}, {}, {}]", row[0], row[1], row[2], row[3])?;
+        }
+        write!(f, "}}")
+    }
+}
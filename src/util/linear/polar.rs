@@ -0,0 +1,161 @@
+//! Polar, spherical and cylindrical coordinates
+//! Conversions to and from cartesian for orbit cameras, radial menus and anything else that thinks in angles.
+//! Every conversion round trips, at the origin (and on the poles for the azimuth) the angles come back as 0.
+//!
+//! 2D polar angles start at +x and go counter-clockwise (towards +y).
+//! 3D angles follow our y-up world:
+//! - azimuth turns around +y starting at +z and going towards +x, the same way a yaw does
+//! - polar is measured down from +y, 0 is straight up and PI is straight down
+
+use std::fmt;
+
+use super::{Float, Pos2D, Pos3D, Radians, Space, Vec2D, Vec3D};
+
+/// ## Spherical
+/// a 3D point as a distance from the origin and two angles
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spherical<T = f32> {
+    radius: T,
+    polar: Radians<T>,
+    azimuth: Radians<T>,
+}
+
+/// ## Cylindrical
+/// a 3D point as a distance from the y axis, an angle around it and a height along it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cylindrical<T = f32> {
+    radius: T,
+    azimuth: Radians<T>,
+    height: T,
+}
+
+impl<T: Float> Spherical<T> {
+    pub fn new(radius: T, polar: Radians<T>, azimuth: Radians<T>) -> Spherical<T> {
+        Spherical { radius, polar, azimuth }
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// angle down from +y
+    pub fn polar(&self) -> Radians<T> {
+        self.polar
+    }
+
+    /// angle around +y from +z towards +x
+    pub fn azimuth(&self) -> Radians<T> {
+        self.azimuth
+    }
+}
+
+impl<T: Float> Cylindrical<T> {
+    pub fn new(radius: T, azimuth: Radians<T>, height: T) -> Cylindrical<T> {
+        Cylindrical { radius, azimuth, height }
+    }
+
+    /// distance from the y axis
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// angle around +y from +z towards +x
+    pub fn azimuth(&self) -> Radians<T> {
+        self.azimuth
+    }
+
+    pub fn height(&self) -> T {
+        self.height
+    }
+}
+
+impl<T: Float> Vec2D<T> {
+    /// unit vector pointing at angle
+    pub fn from_angle(angle: Radians<T>) -> Vec2D<T> {
+        Vec2D::new(angle.cos(), angle.sin())
+    }
+
+    /// direction of the vector, ZERO for a vector with no length
+    pub fn angle(self) -> Radians<T> {
+        Radians::new(self.dy().atan2(self.dx()))
+    }
+
+    /// (length, angle)
+    pub fn to_polar(self) -> (T, Radians<T>) {
+        (self.length(), self.angle())
+    }
+
+    pub fn from_polar(length: T, angle: Radians<T>) -> Vec2D<T> {
+        Vec2D::from_angle(angle) * length
+    }
+}
+
+impl<S: Space, T: Float> Pos2D<S, T> {
+    /// 2D cartesian to polar coords around the origin, (r, theta)
+    pub fn cartesian_to_polar(self) -> (T, Radians<T>) {
+        (self - Pos2D::ZERO).to_polar()
+    }
+
+    /// polar coords around the origin to 2D cartesian
+    pub fn polar_to_cartesian(r: T, theta: Radians<T>) -> Pos2D<S, T> {
+        Pos2D::ZERO + Vec2D::from_polar(r, theta)
+    }
+}
+
+impl<T: Float> Vec3D<T> {
+    pub fn to_spherical(self) -> Spherical<T> {
+        let radius = self.length();
+        if radius == T::ZERO {
+            return Spherical::new(T::ZERO, Radians::ZERO, Radians::ZERO);
+        }
+        // clamped since rounding can push the ratio just past 1 and acos would give NaN
+        let cos_polar = (self.dy() / radius).max(-T::ONE).min(T::ONE);
+        Spherical::new(radius, Radians::new(cos_polar.acos()), Radians::new(self.dx().atan2(self.dz())))
+    }
+
+    pub fn from_spherical(s: Spherical<T>) -> Vec3D<T> {
+        let ring = s.radius * s.polar.sin();
+        Vec3D::new(ring * s.azimuth.sin(), s.radius * s.polar.cos(), ring * s.azimuth.cos())
+    }
+
+    pub fn to_cylindrical(self) -> Cylindrical<T> {
+        let radius = (self.dx() * self.dx() + self.dz() * self.dz()).sqrt();
+        Cylindrical::new(radius, Radians::new(self.dx().atan2(self.dz())), self.dy())
+    }
+
+    pub fn from_cylindrical(c: Cylindrical<T>) -> Vec3D<T> {
+        Vec3D::new(c.radius * c.azimuth.sin(), c.height, c.radius * c.azimuth.cos())
+    }
+}
+
+impl<T: Float> Pos3D<T> {
+    /// spherical coords around the origin
+    pub fn to_spherical(self) -> Spherical<T> {
+        (self - Pos3D::ZERO).to_spherical()
+    }
+
+    pub fn from_spherical(s: Spherical<T>) -> Pos3D<T> {
+        Pos3D::ZERO + Vec3D::from_spherical(s)
+    }
+
+    /// cylindrical coords around the y axis
+    pub fn to_cylindrical(self) -> Cylindrical<T> {
+        (self - Pos3D::ZERO).to_cylindrical()
+    }
+
+    pub fn from_cylindrical(c: Cylindrical<T>) -> Pos3D<T> {
+        Pos3D::ZERO + Vec3D::from_cylindrical(c)
+    }
+}
+
+impl<T: Float> fmt::Display for Spherical<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Spherical: {{ radius: {}, polar: {}, azimuth: {} }}", self.radius, self.polar, self.azimuth)
+    }
+}
+
+impl<T: Float> fmt::Display for Cylindrical<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cylindrical: {{ radius: {}, azimuth: {}, height: {} }}", self.radius, self.azimuth, self.height)
+    }
+}
@@ -0,0 +1,237 @@
+//! Quaternion
+//! Orientation type for 3D nodes. Unlike Direction3D it knows about roll and can blend smoothly between two orientations.
+//! resource: https://www.3dgep.com/understanding-quaternions/
+
+use std::ops::{Mul, MulAssign, Neg};
+use std::fmt;
+
+use super::{Mat4, Pos3D, Vec3D};
+
+/// ## Quat
+/// unit quaternion w + xi + yj + zk representing a rotation.
+/// constructors always hand back unit quaternions, if you build one by hand call normalize before rotating with it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quat {
+    w: f32,
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat { w: 1.0, x: 0.0, y: 0.0, z: 0.0 };
+
+    /// raw constructor, does not normalize
+    pub const fn new(w: f32, x: f32, y: f32, z: f32) -> Quat {
+        Quat { w, x, y, z }
+    }
+
+    /// counter-clockwise rotation of angle radians about axis
+    pub fn from_axis_angle(axis: Vec3D, angle: f32) -> Quat {
+        let axis = axis.normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        Quat { w: cos, x: axis.dx() * sin, y: axis.dy() * sin, z: axis.dz() * sin }
+    }
+
+    /// euler angles in radians, yaw about y, pitch about x, roll about z
+    ///
+    /// roll is applied first, then pitch, then yaw which is what you want for a camera.
+    pub fn from_euler(yaw: f32, pitch: f32, roll: f32) -> Quat {
+        Quat::from_axis_angle(Vec3D::new(0.0, 1.0, 0.0), yaw)
+            * Quat::from_axis_angle(Vec3D::new(1.0, 0.0, 0.0), pitch)
+            * Quat::from_axis_angle(Vec3D::new(0.0, 0.0, 1.0), roll)
+    }
+
+    /// shortest rotation that turns the direction from into the direction to
+    pub fn from_rotation_arc(from: Vec3D, to: Vec3D) -> Quat {
+        let a = from.normalize();
+        let b = to.normalize();
+        let d = a.dot(b);
+        if d < -0.999_999 {
+            // opposite directions, any axis perpendicular to a will do
+            let mut axis = Vec3D::new(1.0, 0.0, 0.0).cross(a);
+            if axis.length_squared() < 1e-6 {
+                axis = Vec3D::new(0.0, 1.0, 0.0).cross(a);
+            }
+            return Quat::from_axis_angle(axis, std::f32::consts::PI);
+        }
+        let c = a.cross(b);
+        Quat { w: 1.0 + d, x: c.dx(), y: c.dy(), z: c.dz() }.normalize()
+    }
+
+    pub fn w(&self) -> f32 {
+        self.w
+    }
+
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    pub fn dot(self, rhs: Quat) -> f32 {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// scales the quaternion back to unit length, a zero quaternion becomes the identity
+    pub fn normalize(self) -> Quat {
+        let len = self.length();
+        if len == 0.0 {
+            return Quat::IDENTITY;
+        }
+        Quat { w: self.w / len, x: self.x / len, y: self.y / len, z: self.z / len }
+    }
+
+    /// flips the axis, for unit quaternions this is the opposite rotation
+    pub fn conjugate(self) -> Quat {
+        Quat { w: self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+
+    /// undoes the rotation, works for non-unit quaternions too
+    pub fn inverse(self) -> Quat {
+        let len_sq = self.dot(self);
+        if len_sq == 0.0 {
+            return Quat::IDENTITY;
+        }
+        let c = self.conjugate();
+        Quat { w: c.w / len_sq, x: c.x / len_sq, y: c.y / len_sq, z: c.z / len_sq }
+    }
+
+    /// returns the (axis, angle) this quaternion rotates by
+    pub fn to_axis_angle(self) -> (Vec3D, f32) {
+        let q = self.normalize();
+        let angle = 2.0 * q.w.clamp(-1.0, 1.0).acos();
+        let s = (1.0 - q.w * q.w).sqrt();
+        if s < 1e-6 {
+            // no rotation so the axis doesn't matter
+            return (Vec3D::new(1.0, 0.0, 0.0), angle);
+        }
+        (Vec3D::new(q.x / s, q.y / s, q.z / s), angle)
+    }
+
+    /// rotation matrix for the quaternion
+    pub fn to_mat4(self) -> Mat4 {
+        let Quat { w, x, y, z } = self;
+        Mat4::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y), 0.0],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x), 0.0],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// rotates a vector
+    ///
+    /// v' = v + 2w(q x v) + 2(q x (q x v)) which skips building the full q * v * q^-1 product
+    pub fn rotate(self, v: Vec3D) -> Vec3D {
+        let q = Vec3D::new(self.x, self.y, self.z);
+        let t = q.cross(v) * 2.0;
+        v + t * self.w + q.cross(t)
+    }
+
+    /// normalized lerp, cheaper than slerp but the speed isn't constant across t
+    pub fn nlerp(start: Quat, end: Quat, t: f32) -> Quat {
+        // go the short way around
+        let end = if start.dot(end) < 0.0 { -end } else { end };
+        Quat {
+            w: start.w + (end.w - start.w) * t,
+            x: start.x + (end.x - start.x) * t,
+            y: start.y + (end.y - start.y) * t,
+            z: start.z + (end.z - start.z) * t,
+        }
+        .normalize()
+    }
+
+    /// spherical lerp, rotates at a constant speed from start to end where t is the % completion
+    pub fn slerp(start: Quat, end: Quat, t: f32) -> Quat {
+        let mut d = start.dot(end);
+        let mut end = end;
+        if d < 0.0 {
+            end = -end;
+            d = -d;
+        }
+        // nearly the same orientation, sin(theta) gets too small to divide by
+        if d > 0.9995 {
+            return Quat::nlerp(start, end, t);
+        }
+        let theta = d.clamp(-1.0, 1.0).acos();
+        let sin_theta = theta.sin();
+        let a = ((1.0 - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        Quat {
+            w: start.w * a + end.w * b,
+            x: start.x * a + end.x * b,
+            y: start.y * a + end.y * b,
+            z: start.z * a + end.z * b,
+        }
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::IDENTITY
+    }
+}
+
+/// composition, (a * b) rotates by b first and then a
+impl Mul<Quat> for Quat {
+    type Output = Quat;
+
+    fn mul(self, rhs: Quat) -> Self::Output {
+        Quat {
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        }
+    }
+}
+
+impl MulAssign<Quat> for Quat {
+    fn mul_assign(&mut self, rhs: Quat) {
+        *self = *self * rhs
+    }
+}
+
+/// -q is the same rotation as q, handy for picking the short path when blending
+impl Neg for Quat {
+    type Output = Quat;
+
+    fn neg(self) -> Self::Output {
+        Quat { w: -self.w, x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+/// rotates a vector
+impl Mul<Vec3D> for Quat {
+    type Output = Vec3D;
+
+    fn mul(self, rhs: Vec3D) -> Self::Output {
+        self.rotate(rhs)
+    }
+}
+
+/// rotates a point about the origin
+impl Mul<Pos3D> for Quat {
+    type Output = Pos3D;
+
+    fn mul(self, rhs: Pos3D) -> Self::Output {
+        Pos3D::ZERO + self.rotate(rhs - Pos3D::ZERO)
+    }
+}
+
+impl fmt::Display for Quat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quat: {{ w: {}, x: {}, y: {}, z: {} }}", self.w, self.x, self.y, self.z)
+    }
+}
@@ -0,0 +1,338 @@
+//! Rays
+//! Half lines for mouse picking and line of sight checks.
+//! Every intersection hands back the first hit at or in front of the origin, a ray that starts inside something hits its far side.
+//! Triangle and Rect have their own ray routines in the shapes module.
+
+use std::fmt;
+
+use super::{Aabb2D, Aabb3D, Pos2D, Pos3D, Space, Vec2D, Vec3D, World};
+
+/// ## Ray2D
+/// starts at origin and goes on forever in direction, direction is always unit length
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray2D<S: Space = World> {
+    origin: Pos2D<S>,
+    direction: Vec2D,
+}
+
+/// ## Ray3D
+/// starts at origin and goes on forever in direction, direction is always unit length
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray3D {
+    origin: Pos3D,
+    direction: Vec3D,
+}
+
+/// ## RayHit2D
+/// where a Ray2D hit something
+///
+/// distance is along the ray from its origin and normal is the unit surface normal facing out of what was hit
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit2D<S: Space = World> {
+    distance: f32,
+    point: Pos2D<S>,
+    normal: Vec2D,
+}
+
+/// ## RayHit3D
+/// where a Ray3D hit something
+///
+/// distance is along the ray from its origin and normal is the unit surface normal facing out of what was hit
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit3D {
+    distance: f32,
+    point: Pos3D,
+    normal: Vec3D,
+}
+
+impl<S: Space> RayHit2D<S> {
+    pub fn new(distance: f32, point: Pos2D<S>, normal: Vec2D) -> RayHit2D<S> {
+        RayHit2D { distance, point, normal }
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn point(&self) -> Pos2D<S> {
+        self.point
+    }
+
+    pub fn normal(&self) -> Vec2D {
+        self.normal
+    }
+
+    /// the closer of two optional hits
+    pub fn closest(a: Option<RayHit2D<S>>, b: Option<RayHit2D<S>>) -> Option<RayHit2D<S>> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(if b.distance < a.distance { b } else { a }),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl RayHit3D {
+    pub fn new(distance: f32, point: Pos3D, normal: Vec3D) -> RayHit3D {
+        RayHit3D { distance, point, normal }
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn point(&self) -> Pos3D {
+        self.point
+    }
+
+    pub fn normal(&self) -> Vec3D {
+        self.normal
+    }
+}
+
+impl<S: Space> Ray2D<S> {
+    /// direction gets normalized, None if it has no length
+    pub fn new(origin: Pos2D<S>, direction: Vec2D) -> Option<Ray2D<S>> {
+        Some(Ray2D { origin, direction: direction.try_normalize()? })
+    }
+
+    /// ray from one point through another, None if they're the same point
+    pub fn between(from: Pos2D<S>, to: Pos2D<S>) -> Option<Ray2D<S>> {
+        Ray2D::new(from, to - from)
+    }
+
+    pub fn origin(&self) -> Pos2D<S> {
+        self.origin
+    }
+
+    pub fn direction(&self) -> Vec2D {
+        self.direction
+    }
+
+    /// the point t units along the ray
+    pub fn at(&self, t: f32) -> Pos2D<S> {
+        self.origin + self.direction * t
+    }
+
+    /// hit against the line segment from a to b
+    ///
+    /// the segment has no inside so the normal faces back towards the ray's origin
+    pub fn intersect_segment(&self, a: Pos2D<S>, b: Pos2D<S>) -> Option<RayHit2D<S>> {
+        let edge = b - a;
+        let denom = self.direction.perp_dot(edge);
+        // parallel rays never cross the segment
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+        let to_a = a - self.origin;
+        let t = to_a.perp_dot(edge) / denom;
+        let u = to_a.perp_dot(self.direction) / denom;
+        if t < 0.0 || !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let mut normal = edge.perp().normalize();
+        if normal.dot(self.direction) > 0.0 {
+            normal = -normal;
+        }
+        Some(RayHit2D::new(t, self.at(t), normal))
+    }
+
+    /// hit against a circle
+    pub fn intersect_circle(&self, center: Pos2D<S>, radius: f32) -> Option<RayHit2D<S>> {
+        let offset = self.origin - center;
+        let t = nearest_root(self.direction.dot(offset), offset.length_squared() - radius * radius)?;
+        let point = self.at(t);
+        Some(RayHit2D::new(t, point, (point - center).normalize()))
+    }
+
+    /// hit against a box using the slab method
+    pub fn intersect_aabb(&self, aabb: &Aabb2D<S>) -> Option<RayHit2D<S>> {
+        let (min, max) = (aabb.min(), aabb.max());
+        let slabs = [
+            slab(self.origin.x(), self.direction.dx(), min.x(), max.x())?,
+            slab(self.origin.y(), self.direction.dy(), min.y(), max.y())?,
+        ];
+        let (axis, t) = pick_slab_hit(&slabs)?;
+        let (near, _, side) = slabs[axis];
+        let sign = if t == near { -side } else { side };
+        let normal = if axis == 0 { Vec2D::new(sign, 0.0) } else { Vec2D::new(0.0, sign) };
+        Some(RayHit2D::new(t, self.at(t), normal))
+    }
+}
+
+impl Ray3D {
+    /// direction gets normalized, None if it has no length
+    pub fn new(origin: Pos3D, direction: Vec3D) -> Option<Ray3D> {
+        Some(Ray3D { origin, direction: direction.try_normalize()? })
+    }
+
+    /// ray from one point through another, None if they're the same point
+    pub fn between(from: Pos3D, to: Pos3D) -> Option<Ray3D> {
+        Ray3D::new(from, to - from)
+    }
+
+    pub fn origin(&self) -> Pos3D {
+        self.origin
+    }
+
+    pub fn direction(&self) -> Vec3D {
+        self.direction
+    }
+
+    /// the point t units along the ray
+    pub fn at(&self, t: f32) -> Pos3D {
+        self.origin + self.direction * t
+    }
+
+    /// hit against the infinite plane through point with the given normal
+    ///
+    /// planes are two sided so the hit normal faces back towards the ray's origin
+    pub fn intersect_plane(&self, point: Pos3D, normal: Vec3D) -> Option<RayHit3D> {
+        let normal = normal.try_normalize()?;
+        let denom = normal.dot(self.direction);
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+        let t = (point - self.origin).dot(normal) / denom;
+        if t < 0.0 {
+            return None;
+        }
+        let normal = if denom > 0.0 { -normal } else { normal };
+        Some(RayHit3D::new(t, self.at(t), normal))
+    }
+
+    /// hit against a sphere
+    pub fn intersect_sphere(&self, center: Pos3D, radius: f32) -> Option<RayHit3D> {
+        let offset = self.origin - center;
+        let t = nearest_root(self.direction.dot(offset), offset.length_squared() - radius * radius)?;
+        let point = self.at(t);
+        Some(RayHit3D::new(t, point, (point - center).normalize()))
+    }
+
+    /// hit against a box using the slab method
+    pub fn intersect_aabb(&self, aabb: &Aabb3D) -> Option<RayHit3D> {
+        let (min, max) = (aabb.min(), aabb.max());
+        let slabs = [
+            slab(self.origin.x(), self.direction.dx(), min.x(), max.x())?,
+            slab(self.origin.y(), self.direction.dy(), min.y(), max.y())?,
+            slab(self.origin.z(), self.direction.dz(), min.z(), max.z())?,
+        ];
+        let (axis, t) = pick_slab_hit(&slabs)?;
+        let (near, _, side) = slabs[axis];
+        let sign = if t == near { -side } else { side };
+        let normal = match axis {
+            0 => Vec3D::new(sign, 0.0, 0.0),
+            1 => Vec3D::new(0.0, sign, 0.0),
+            _ => Vec3D::new(0.0, 0.0, sign),
+        };
+        Some(RayHit3D::new(t, self.at(t), normal))
+    }
+
+    /// hit against the triangle abc using Möller–Trumbore
+    ///
+    /// triangles are two sided so the hit normal faces back towards the ray's origin
+    /// resource: https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm
+    pub fn intersect_triangle(&self, a: Pos3D, b: Pos3D, c: Pos3D) -> Option<RayHit3D> {
+        let e1 = b - a;
+        let e2 = c - a;
+        let p = self.direction.cross(e2);
+        let det = e1.dot(p);
+        // the ray runs along the triangle's plane
+        if det.abs() <= f32::EPSILON {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(e1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = e2.dot(q) * inv_det;
+        if t < 0.0 {
+            return None;
+        }
+        let mut normal = e1.cross(e2).normalize();
+        if normal.dot(self.direction) > 0.0 {
+            normal = -normal;
+        }
+        Some(RayHit3D::new(t, self.at(t), normal))
+    }
+}
+
+// one axis of the slab test, returns (t entering, t leaving, +1 if we enter through the min side)
+fn slab(origin: f32, dir: f32, min: f32, max: f32) -> Option<(f32, f32, f32)> {
+    if dir == 0.0 {
+        // parallel to this slab so we're either always inside it or never
+        if origin < min || origin > max {
+            return None;
+        }
+        return Some((f32::NEG_INFINITY, f32::INFINITY, 1.0));
+    }
+    let t1 = (min - origin) / dir;
+    let t2 = (max - origin) / dir;
+    if t1 <= t2 {
+        Some((t1, t2, 1.0))
+    } else {
+        Some((t2, t1, -1.0))
+    }
+}
+
+// combines the slabs into (axis the hit happened on, distance)
+fn pick_slab_hit(slabs: &[(f32, f32, f32)]) -> Option<(usize, f32)> {
+    let (mut near_axis, mut near) = (0, f32::NEG_INFINITY);
+    let (mut far_axis, mut far) = (0, f32::INFINITY);
+    for (axis, &(t_near, t_far, _)) in slabs.iter().enumerate() {
+        if t_near > near {
+            near = t_near;
+            near_axis = axis;
+        }
+        if t_far < far {
+            far = t_far;
+            far_axis = axis;
+        }
+    }
+    if near > far || far < 0.0 {
+        return None;
+    }
+    // starting inside the box means the first thing we hit is the way out
+    if near >= 0.0 {
+        Some((near_axis, near))
+    } else {
+        Some((far_axis, far))
+    }
+}
+
+// smallest t >= 0 solving t^2 + 2bt + c = 0, which is where a unit ray meets a circle or sphere
+// with b = direction . (origin - center) and c = |origin - center|^2 - radius^2
+fn nearest_root(b: f32, c: f32) -> Option<f32> {
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let (t1, t2) = (-b - root, -b + root);
+    if t1 >= 0.0 {
+        Some(t1)
+    } else if t2 >= 0.0 {
+        Some(t2)
+    } else {
+        None
+    }
+}
+
+impl<S: Space> fmt::Display for Ray2D<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ray2D: {{ origin: {}, direction: {} }}", self.origin, self.direction)
+    }
+}
+
+impl fmt::Display for Ray3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ray3D: {{ origin: {}, direction: {} }}", self.origin, self.direction)
+    }
+}
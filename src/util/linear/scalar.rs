@@ -0,0 +1,147 @@
+//! Scalars
+//! The number types the linear structs can be built from.
+//! f32 is the default everywhere, f64 is for big worlds where f32 loses precision far from the origin,
+//! and i32/i64 are for tiles, grids and UI layout.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// ## Scalar
+/// a signed number type a Pos or Vec can hold
+pub trait Scalar:
+    Copy + Debug + Display + Default + PartialEq + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+    + AddAssign + SubAssign + MulAssign + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn abs(self) -> Self;
+
+    /// widens to f64, lossless for everything but very large i64s
+    fn to_f64(self) -> f64;
+
+    /// narrows from f64 the same way `as` does, floats round to the nearest value and integers truncate towards zero and saturate
+    fn from_f64(v: f64) -> Self;
+}
+
+/// ## Float
+/// scalars with a fractional part, needed for lengths, angles and lerp
+pub trait Float: Scalar {
+    const EPSILON: Self;
+    const PI: Self;
+
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn is_finite(self) -> bool;
+}
+
+/// ## Integer
+/// whole number scalars, these get exact equality and hashing so grid positions can key a map
+pub trait Integer: Scalar + Eq + Hash {}
+
+// floats forward min and max to std so a NaN loses to the other value, integers just compare
+macro_rules! impl_scalar {
+    (floats: $($t:ty),*) => {$(
+        impl_scalar!(@impl $t {
+            fn min(self, other: $t) -> $t {
+                <$t>::min(self, other)
+            }
+
+            fn max(self, other: $t) -> $t {
+                <$t>::max(self, other)
+            }
+        });
+    )*};
+    (integers: $($t:ty),*) => {$(
+        impl_scalar!(@impl $t {
+            fn min(self, other: $t) -> $t {
+                if other < self { other } else { self }
+            }
+
+            fn max(self, other: $t) -> $t {
+                if other > self { other } else { self }
+            }
+        });
+    )*};
+    (@impl $t:ty { $($min_max:tt)* }) => {
+        impl Scalar for $t {
+            const ZERO: $t = 0 as $t;
+            const ONE: $t = 1 as $t;
+
+            $($min_max)*
+
+            fn abs(self) -> $t {
+                <$t>::abs(self)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(v: f64) -> $t {
+                v as $t
+            }
+        }
+    };
+}
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {$(
+        impl Float for $t {
+            const EPSILON: $t = $t::EPSILON;
+            const PI: $t = std::$t::consts::PI;
+
+            fn sqrt(self) -> $t {
+                $t::sqrt(self)
+            }
+
+            fn sin(self) -> $t {
+                $t::sin(self)
+            }
+
+            fn cos(self) -> $t {
+                $t::cos(self)
+            }
+
+            fn acos(self) -> $t {
+                $t::acos(self)
+            }
+
+            fn atan2(self, other: $t) -> $t {
+                $t::atan2(self, other)
+            }
+
+            fn floor(self) -> $t {
+                $t::floor(self)
+            }
+
+            fn ceil(self) -> $t {
+                $t::ceil(self)
+            }
+
+            fn round(self) -> $t {
+                $t::round(self)
+            }
+
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+        }
+    )*};
+}
+
+impl_scalar!(floats: f32, f64);
+impl_scalar!(integers: i32, i64);
+impl_float!(f32, f64);
+impl Integer for i32 {}
+impl Integer for i64 {}
@@ -0,0 +1,71 @@
+//! Coordinate spaces
+//! Zero sized markers that tag a Pos2D with the space its numbers live in.
+//! A Pos2D<Normalized> can't be handed to something that wants a Pos2D<World>, you have to go through one of the conversions.
+//!
+//! - World: game units centred on a Camera2D, y points up
+//! - Screen: framebuffer pixels, (0, 0) is the top left and y points down
+//! - Normalized: 0..1 across the framebuffer, (0, 0) is the top left and y points down
+
+use std::fmt::Debug;
+
+use super::{Aabb2D, Pos2D};
+
+/// ## Space
+/// marker trait for the coordinate spaces a Pos2D can be in
+pub trait Space: Debug + Copy + Clone + Default + PartialEq {
+    /// name used when printing
+    const NAME: &'static str;
+    /// true if y grows upwards, false if it grows down the screen
+    const Y_UP: bool;
+}
+
+/// ## World
+/// game units, what nodes and cameras are positioned in
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct World;
+
+/// ## Screen
+/// framebuffer pixels
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Screen;
+
+/// ## Normalized
+/// fractions of the framebuffer size
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Normalized;
+
+impl Space for World {
+    const NAME: &'static str = "World";
+    const Y_UP: bool = true;
+}
+
+impl Space for Screen {
+    const NAME: &'static str = "Screen";
+    const Y_UP: bool = false;
+}
+
+impl Space for Normalized {
+    const NAME: &'static str = "Normalized";
+    const Y_UP: bool = false;
+}
+
+impl Pos2D<Normalized> {
+    /// scales a normalized position up to pixels for a framebuffer of width x height
+    pub fn to_screen(self, width: f32, height: f32) -> Pos2D<Screen> {
+        Pos2D::new(self.x() * width, self.y() * height)
+    }
+}
+
+impl Pos2D<Screen> {
+    /// scales pixels down to a fraction of a framebuffer of width x height
+    pub fn to_normalized(self, width: f32, height: f32) -> Pos2D<Normalized> {
+        Pos2D::new(self.x() / width, self.y() / height)
+    }
+}
+
+impl Aabb2D<Normalized> {
+    /// scales a normalized box up to pixels for a framebuffer of width x height
+    pub fn to_screen(self, width: f32, height: f32) -> Aabb2D<Screen> {
+        Aabb2D::new(self.min().to_screen(width, height), self.max().to_screen(width, height))
+    }
+}
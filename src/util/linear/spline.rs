@@ -0,0 +1,518 @@
+//! Splines
+//! Curved paths for cameras, enemies and anything else that shouldn't move in straight lerp_steps lines.
+//! Every curve works over Pos2D and Pos3D through SplinePoint and runs from t = 0 at its start to t = 1 at its end.
+//!
+//! t doesn't move along the curve at a constant speed, use an ArcLength table for that.
+
+use std::fmt;
+use std::ops::Mul;
+
+use super::{Aabb2D, Aabb3D, Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+// flatten always splits at least this many times so s-bends whose midpoint sits on the chord aren't mistaken for lines
+const FLATTEN_MIN_DEPTH: u32 = 2;
+const FLATTEN_MAX_DEPTH: u32 = 16;
+
+/// ## SplinePoint
+/// a position a curve can be built from
+pub trait SplinePoint: Copy + PartialEq {
+    /// what tangents come back as
+    type Vector: Copy + PartialEq + Mul<f32, Output = Self::Vector>;
+    /// what bounds come back as
+    type Bounds: Copy;
+    /// number of axes, 2 or 3
+    const AXES: usize;
+
+    /// the coordinate on axis 0 (x), 1 (y) or 2 (z)
+    fn axis(&self, i: usize) -> f32;
+
+    /// sum of weight * point where the weights add up to 1
+    fn affine(points: &[Self], weights: &[f32]) -> Self;
+
+    /// sum of weight * point where the weights add up to 0, which leaves a direction
+    fn direction(points: &[Self], weights: &[f32]) -> Self::Vector;
+
+    fn distance(&self, other: &Self) -> f32;
+
+    /// distance to the closest point of the line segment from a to b
+    fn distance_to_segment(&self, a: &Self, b: &Self) -> f32;
+
+    /// smallest box around the points, points can't be empty
+    fn bounds_of(points: &[Self]) -> Self::Bounds;
+}
+
+/// ## Curve
+/// shared behaviour for every spline type
+pub trait Curve {
+    type Point: SplinePoint;
+
+    /// the point at t, t is clamped to 0..1
+    fn at(&self, t: f32) -> Self::Point;
+
+    /// the derivative at t, it points along the curve and its length is the speed t moves at
+    fn tangent(&self, t: f32) -> <Self::Point as SplinePoint>::Vector;
+
+    /// tight box around the curve itself, not just its control points
+    fn bounds(&self) -> <Self::Point as SplinePoint>::Bounds;
+
+    /// the curve as a polyline from start to end where no point of the curve is more than tolerance off a line
+    fn flatten(&self, tolerance: f32) -> Vec<Self::Point> {
+        let start = self.at(0.0);
+        let mut points = vec![start];
+        flatten_range(self, 0.0, 1.0, start, self.at(1.0), tolerance, 0, &mut points);
+        points
+    }
+
+    /// table for moving along the curve at constant speed, more samples is more accurate
+    fn arc_length(&self, samples: u32) -> ArcLength {
+        ArcLength::new(self, samples)
+    }
+
+    /// approximate length of the curve
+    fn length(&self) -> f32 {
+        self.arc_length(64).total()
+    }
+
+    /// the point distance along the curve as measured by table
+    fn at_distance(&self, table: &ArcLength, distance: f32) -> Self::Point {
+        self.at(table.t_at_distance(distance))
+    }
+}
+
+/// ## QuadraticBezier
+/// curve from p0 to p2 pulled towards the control point p1
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadraticBezier<P: SplinePoint = Pos2D> {
+    p0: P,
+    p1: P,
+    p2: P,
+}
+
+/// ## CubicBezier
+/// curve from p0 to p3 pulled towards the control points p1 and p2
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CubicBezier<P: SplinePoint = Pos2D> {
+    p0: P,
+    p1: P,
+    p2: P,
+    p3: P,
+}
+
+/// ## CatmullRom
+/// uniform Catmull-Rom spline that passes through every one of its points
+///
+/// the end points are doubled up so the curve reaches them, t spreads evenly over the segments between points
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<P: SplinePoint = Pos2D> {
+    points: Vec<P>,
+}
+
+/// ## ArcLength
+/// cumulative length of a curve sampled at evenly spaced t, used to turn a distance into a t
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLength {
+    lengths: Vec<f32>,
+}
+
+fn lerp<P: SplinePoint>(a: P, b: P, t: f32) -> P {
+    P::affine(&[a, b], &[1.0 - t, t])
+}
+
+impl<P: SplinePoint> QuadraticBezier<P> {
+    pub fn new(p0: P, p1: P, p2: P) -> QuadraticBezier<P> {
+        QuadraticBezier { p0, p1, p2 }
+    }
+
+    pub fn p0(&self) -> P {
+        self.p0
+    }
+
+    pub fn p1(&self) -> P {
+        self.p1
+    }
+
+    pub fn p2(&self) -> P {
+        self.p2
+    }
+
+    /// splits the curve at t into the part before and the part after using de Casteljau
+    pub fn split(&self, t: f32) -> (QuadraticBezier<P>, QuadraticBezier<P>) {
+        let t = t.clamp(0.0, 1.0);
+        let a = lerp(self.p0, self.p1, t);
+        let b = lerp(self.p1, self.p2, t);
+        let mid = lerp(a, b, t);
+        (QuadraticBezier::new(self.p0, a, mid), QuadraticBezier::new(mid, b, self.p2))
+    }
+
+    /// the same curve as a cubic
+    pub fn to_cubic(&self) -> CubicBezier<P> {
+        CubicBezier::new(
+            self.p0,
+            lerp(self.p0, self.p1, 2.0 / 3.0),
+            lerp(self.p2, self.p1, 2.0 / 3.0),
+            self.p2,
+        )
+    }
+}
+
+impl<P: SplinePoint> Curve for QuadraticBezier<P> {
+    type Point = P;
+
+    fn at(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        P::affine(&[self.p0, self.p1, self.p2], &[u * u, 2.0 * u * t, t * t])
+    }
+
+    fn tangent(&self, t: f32) -> P::Vector {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        P::direction(&[self.p0, self.p1, self.p2], &[-2.0 * u, 2.0 * u - 2.0 * t, 2.0 * t])
+    }
+
+    fn bounds(&self) -> P::Bounds {
+        let mut points = vec![self.p0, self.p2];
+        for i in 0..P::AXES {
+            let (a, b, c) = (self.p0.axis(i), self.p1.axis(i), self.p2.axis(i));
+            // the derivative is linear so each axis turns around at most once
+            let denom = a - 2.0 * b + c;
+            if denom != 0.0 {
+                let t = (a - b) / denom;
+                if t > 0.0 && t < 1.0 {
+                    points.push(self.at(t));
+                }
+            }
+        }
+        P::bounds_of(&points)
+    }
+}
+
+impl<P: SplinePoint> CubicBezier<P> {
+    pub fn new(p0: P, p1: P, p2: P, p3: P) -> CubicBezier<P> {
+        CubicBezier { p0, p1, p2, p3 }
+    }
+
+    pub fn p0(&self) -> P {
+        self.p0
+    }
+
+    pub fn p1(&self) -> P {
+        self.p1
+    }
+
+    pub fn p2(&self) -> P {
+        self.p2
+    }
+
+    pub fn p3(&self) -> P {
+        self.p3
+    }
+
+    /// splits the curve at t into the part before and the part after using de Casteljau
+    pub fn split(&self, t: f32) -> (CubicBezier<P>, CubicBezier<P>) {
+        let t = t.clamp(0.0, 1.0);
+        let a = lerp(self.p0, self.p1, t);
+        let b = lerp(self.p1, self.p2, t);
+        let c = lerp(self.p2, self.p3, t);
+        let ab = lerp(a, b, t);
+        let bc = lerp(b, c, t);
+        let mid = lerp(ab, bc, t);
+        (CubicBezier::new(self.p0, a, ab, mid), CubicBezier::new(mid, bc, c, self.p3))
+    }
+
+    // the end points plus every point where an axis turns around, the bounds of these are the bounds of the curve
+    fn extremes(&self) -> Vec<P> {
+        let mut points = vec![self.p0, self.p3];
+        for i in 0..P::AXES {
+            // derivative per axis is 3(at^2 + bt + c)
+            let (p0, p1, p2, p3) = (self.p0.axis(i), self.p1.axis(i), self.p2.axis(i), self.p3.axis(i));
+            let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+            let b = 2.0 * (p0 - 2.0 * p1 + p2);
+            let c = p1 - p0;
+            for t in quadratic_roots(a, b, c).into_iter().flatten() {
+                if t > 0.0 && t < 1.0 {
+                    points.push(self.at(t));
+                }
+            }
+        }
+        points
+    }
+}
+
+impl<P: SplinePoint> Curve for CubicBezier<P> {
+    type Point = P;
+
+    fn at(&self, t: f32) -> P {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        P::affine(
+            &[self.p0, self.p1, self.p2, self.p3],
+            &[u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t],
+        )
+    }
+
+    fn tangent(&self, t: f32) -> P::Vector {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        P::direction(
+            &[self.p0, self.p1, self.p2, self.p3],
+            &[-3.0 * u * u, 3.0 * u * u - 6.0 * u * t, 6.0 * u * t - 3.0 * t * t, 3.0 * t * t],
+        )
+    }
+
+    fn bounds(&self) -> P::Bounds {
+        P::bounds_of(&self.extremes())
+    }
+}
+
+impl<P: SplinePoint> CatmullRom<P> {
+    /// None if there are less than two points to go between
+    pub fn new(points: Vec<P>) -> Option<CatmullRom<P>> {
+        if points.len() < 2 {
+            return None;
+        }
+        Some(CatmullRom { points })
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// adds a point to the end of the path
+    pub fn push(&mut self, p: P) {
+        self.points.push(p);
+    }
+
+    /// number of segments, one less than the number of points
+    pub fn segment_count(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    /// the segment from points[i] to points[i + 1] as a cubic bezier
+    pub fn segment(&self, i: usize) -> CubicBezier<P> {
+        let last = self.points.len() - 1;
+        let p0 = self.points[i.saturating_sub(1)];
+        let p1 = self.points[i];
+        let p2 = self.points[i + 1];
+        let p3 = self.points[(i + 2).min(last)];
+        // the bezier handles sit a sixth of the way along the neighbouring chords
+        CubicBezier::new(
+            p1,
+            P::affine(&[p0, p1, p2], &[-1.0 / 6.0, 1.0, 1.0 / 6.0]),
+            P::affine(&[p1, p2, p3], &[1.0 / 6.0, 1.0, -1.0 / 6.0]),
+            p2,
+        )
+    }
+
+    /// every segment as a cubic bezier
+    pub fn segments(&self) -> Vec<CubicBezier<P>> {
+        (0..self.segment_count()).map(|i| self.segment(i)).collect()
+    }
+
+    // which segment t lands in and how far along that segment it is
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let scaled = t.clamp(0.0, 1.0) * self.segment_count() as f32;
+        let i = (scaled as usize).min(self.segment_count() - 1);
+        (i, scaled - i as f32)
+    }
+}
+
+impl<P: SplinePoint> Curve for CatmullRom<P> {
+    type Point = P;
+
+    fn at(&self, t: f32) -> P {
+        let (i, local) = self.locate(t);
+        self.segment(i).at(local)
+    }
+
+    fn tangent(&self, t: f32) -> P::Vector {
+        let (i, local) = self.locate(t);
+        let tangent = self.segment(i).tangent(local);
+        // each segment only gets 1 / segment_count of t so it moves that much faster
+        tangent * self.segment_count() as f32
+    }
+
+    fn bounds(&self) -> P::Bounds {
+        let points: Vec<P> = self.segments().iter().flat_map(|s| s.extremes()).collect();
+        P::bounds_of(&points)
+    }
+
+    /// flattens each segment on its own so no bend between points gets skipped
+    fn flatten(&self, tolerance: f32) -> Vec<P> {
+        let mut points = vec![self.points[0]];
+        for segment in self.segments() {
+            points.extend(segment.flatten(tolerance).into_iter().skip(1));
+        }
+        points
+    }
+}
+
+impl ArcLength {
+    /// samples the curve at samples + 1 evenly spaced t, samples is at least 1
+    pub fn new<C: Curve + ?Sized>(curve: &C, samples: u32) -> ArcLength {
+        let samples = samples.max(1);
+        let mut lengths = Vec::with_capacity(samples as usize + 1);
+        let mut prev = curve.at(0.0);
+        let mut total = 0.0;
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let p = curve.at(i as f32 / samples as f32);
+            total += prev.distance(&p);
+            lengths.push(total);
+            prev = p;
+        }
+        ArcLength { lengths }
+    }
+
+    /// length of the whole curve
+    pub fn total(&self) -> f32 {
+        *self.lengths.last().unwrap()
+    }
+
+    /// the t that is distance along the curve, distance is clamped to the curve
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let total = self.total();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let distance = distance.clamp(0.0, total);
+        let samples = (self.lengths.len() - 1) as f32;
+        // first sample at or past distance, then lerp back into the gap before it
+        let i = self.lengths.partition_point(|&l| l < distance).max(1);
+        let (before, after) = (self.lengths[i - 1], self.lengths[i]);
+        let along = if after > before { (distance - before) / (after - before) } else { 0.0 };
+        ((i - 1) as f32 + along) / samples
+    }
+
+    /// the t that is fraction (0..1) of the way along the curve
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.total())
+    }
+}
+
+// recursive half of flatten, adds every point after start up to and including end
+#[allow(clippy::too_many_arguments)]
+fn flatten_range<C: Curve + ?Sized>(
+    curve: &C,
+    t0: f32,
+    t1: f32,
+    start: C::Point,
+    end: C::Point,
+    tolerance: f32,
+    depth: u32,
+    out: &mut Vec<C::Point>,
+) {
+    let t_mid = (t0 + t1) / 2.0;
+    let mid = curve.at(t_mid);
+    let flat = mid.distance_to_segment(&start, &end) <= tolerance;
+    if depth >= FLATTEN_MAX_DEPTH || (depth >= FLATTEN_MIN_DEPTH && flat) {
+        out.push(end);
+        return;
+    }
+    flatten_range(curve, t0, t_mid, start, mid, tolerance, depth + 1, out);
+    flatten_range(curve, t_mid, t1, mid, end, tolerance, depth + 1, out);
+}
+
+// real roots of at^2 + bt + c = 0, falls back to the linear case when a is 0
+fn quadratic_roots(a: f32, b: f32, c: f32) -> [Option<f32>; 2] {
+    if a.abs() <= f32::EPSILON {
+        if b.abs() <= f32::EPSILON {
+            return [None, None];
+        }
+        return [Some(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    [Some((-b - root) / (2.0 * a)), Some((-b + root) / (2.0 * a))]
+}
+
+impl<S: Space> SplinePoint for Pos2D<S> {
+    type Vector = Vec2D;
+    type Bounds = Aabb2D<S>;
+    const AXES: usize = 2;
+
+    fn axis(&self, i: usize) -> f32 {
+        if i == 0 { self.x() } else { self.y() }
+    }
+
+    fn affine(points: &[Pos2D<S>], weights: &[f32]) -> Pos2D<S> {
+        let origin = points[0];
+        origin + Pos2D::direction(points, weights)
+    }
+
+    fn direction(points: &[Pos2D<S>], weights: &[f32]) -> Vec2D {
+        // measured from the first point so positions never get added to each other
+        let origin = points[0];
+        points.iter().zip(weights).fold(Vec2D::ZERO, |v, (p, w)| v + (*p - origin) * *w)
+    }
+
+    fn distance(&self, other: &Pos2D<S>) -> f32 {
+        (*other - *self).length()
+    }
+
+    fn distance_to_segment(&self, a: &Pos2D<S>, b: &Pos2D<S>) -> f32 {
+        let ab = *b - *a;
+        let len = ab.length_squared();
+        let t = if len > 0.0 { ((*self - *a).dot(ab) / len).clamp(0.0, 1.0) } else { 0.0 };
+        self.distance(&(*a + ab * t))
+    }
+
+    fn bounds_of(points: &[Pos2D<S>]) -> Aabb2D<S> {
+        Aabb2D::from_points(points).expect("bounds of no points")
+    }
+}
+
+impl SplinePoint for Pos3D {
+    type Vector = Vec3D;
+    type Bounds = Aabb3D;
+    const AXES: usize = 3;
+
+    fn axis(&self, i: usize) -> f32 {
+        match i {
+            0 => self.x(),
+            1 => self.y(),
+            _ => self.z(),
+        }
+    }
+
+    fn affine(points: &[Pos3D], weights: &[f32]) -> Pos3D {
+        let origin = points[0];
+        origin + Pos3D::direction(points, weights)
+    }
+
+    fn direction(points: &[Pos3D], weights: &[f32]) -> Vec3D {
+        // measured from the first point so positions never get added to each other
+        let origin = points[0];
+        points.iter().zip(weights).fold(Vec3D::ZERO, |v, (p, w)| v + (*p - origin) * *w)
+    }
+
+    fn distance(&self, other: &Pos3D) -> f32 {
+        (*other - *self).length()
+    }
+
+    fn distance_to_segment(&self, a: &Pos3D, b: &Pos3D) -> f32 {
+        let ab = *b - *a;
+        let len = ab.length_squared();
+        let t = if len > 0.0 { ((*self - *a).dot(ab) / len).clamp(0.0, 1.0) } else { 0.0 };
+        self.distance(&(*a + ab * t))
+    }
+
+    fn bounds_of(points: &[Pos3D]) -> Aabb3D {
+        Aabb3D::from_points(points).expect("bounds of no points")
+    }
+}
+
+impl<P: SplinePoint + fmt::Display> fmt::Display for QuadraticBezier<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QuadraticBezier: {{ {}, {}, {} }}", self.p0, self.p1, self.p2)
+    }
+}
+
+impl<P: SplinePoint + fmt::Display> fmt::Display for CubicBezier<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CubicBezier: {{ {}, {}, {}, {} }}", self.p0, self.p1, self.p2, self.p3)
+    }
+}
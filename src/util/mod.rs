@@ -1,3 +1,8 @@
 pub mod linear;
 pub mod debug;
-pub mod color;
\ No newline at end of file
+pub mod color;
+pub mod easing;
+pub mod geometry;
+pub mod random;
+pub mod noise;
+pub mod integrate;
\ No newline at end of file
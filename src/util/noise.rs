@@ -0,0 +1,538 @@
+//! Noise
+//! Seeded coherent noise for backgrounds, heightmaps and anything else that should look random but change smoothly.
+//! Every generator takes its seed up front and gives the same value for the same point on every run,
+//! the permutation tables are shuffled with util::random so they match across machines too.
+//!
+//! Perlin, simplex and value noise come back in roughly -1..1, Worley in roughly 0..1.
+//! Fractal and Warp wrap any of them (or each other) and are noise themselves.
+
+use std::ops::Range;
+
+use crate::graphics::window_minifb::Framebuffer;
+use crate::util::color::Gradient;
+use crate::util::linear::{Pos2D, Pos3D, Screen, Space, Vec2D, Vec3D};
+use crate::util::random::Rng;
+
+// 3D gradients at the edge midpoints of a cube, from Ken Perlin's improved noise
+const GRAD3: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0], [-1.0, 1.0, 0.0], [1.0, -1.0, 0.0], [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [1.0, 0.0, -1.0], [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0], [0.0, -1.0, 1.0], [0.0, 1.0, -1.0], [0.0, -1.0, -1.0],
+];
+
+// 2D gradients, the four axes and four diagonals all unit length
+const GRAD2: [[f32; 2]; 8] = [
+    [1.0, 0.0], [-1.0, 0.0], [0.0, 1.0], [0.0, -1.0],
+    [std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2], [-std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2],
+    [std::f32::consts::FRAC_1_SQRT_2, -std::f32::consts::FRAC_1_SQRT_2], [-std::f32::consts::FRAC_1_SQRT_2, -std::f32::consts::FRAC_1_SQRT_2],
+];
+
+/// ## Noise
+/// anything that can be sampled for a smooth random value at a point
+pub trait Noise {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32;
+    fn sample_3d(&self, p: Pos3D) -> f32;
+}
+
+// the shuffled 0..256 table every generator hashes lattice points through, doubled so lookups never wrap
+#[derive(Debug, Clone, PartialEq)]
+struct Permutation([u8; 512]);
+
+impl Permutation {
+    fn new(seed: u64) -> Permutation {
+        let mut shuffled: Vec<u8> = (0..=255).collect();
+        Rng::new(seed).shuffle(&mut shuffled);
+        let mut table = [0; 512];
+        for (i, slot) in table.iter_mut().enumerate() {
+            *slot = shuffled[i & 255];
+        }
+        Permutation(table)
+    }
+
+    fn hash2(&self, x: i32, y: i32) -> usize {
+        self.0[self.0[(x & 255) as usize] as usize + (y & 255) as usize] as usize
+    }
+
+    fn hash3(&self, x: i32, y: i32, z: i32) -> usize {
+        self.0[self.hash2(x, y) + (z & 255) as usize] as usize
+    }
+}
+
+// Perlin's quintic, zero first and second derivatives at 0 and 1 so the cells join without creases
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn dot2(g: [f32; 2], x: f32, y: f32) -> f32 {
+    g[0] * x + g[1] * y
+}
+
+fn dot3(g: [f32; 3], x: f32, y: f32, z: f32) -> f32 {
+    g[0] * x + g[1] * y + g[2] * z
+}
+
+// lattice cell and the offset into it
+fn split(v: f32) -> (i32, f32) {
+    let cell = v.floor();
+    (cell as i32, v - cell)
+}
+
+/// ## Perlin
+/// Ken Perlin's improved gradient noise, 0 on every whole number coordinate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perlin {
+    perm: Permutation,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Perlin {
+        Perlin { perm: Permutation::new(seed) }
+    }
+}
+
+impl Noise for Perlin {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let corner = |dx: i32, dy: i32| dot2(GRAD2[self.perm.hash2(xi + dx, yi + dy) & 7], x - dx as f32, y - dy as f32);
+        let (u, v) = (fade(x), fade(y));
+        let bottom = lerp(corner(0, 0), corner(1, 0), u);
+        let top = lerp(corner(0, 1), corner(1, 1), u);
+        // unit gradients top out at sqrt(0.5), scale back up to -1..1
+        lerp(bottom, top, v) * std::f32::consts::SQRT_2
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let (zi, z) = split(p.z());
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            dot3(GRAD3[self.perm.hash3(xi + dx, yi + dy, zi + dz) % 12], x - dx as f32, y - dy as f32, z - dz as f32)
+        };
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let near = lerp(lerp(corner(0, 0, 0), corner(1, 0, 0), u), lerp(corner(0, 1, 0), corner(1, 1, 0), u), v);
+        let far = lerp(lerp(corner(0, 0, 1), corner(1, 0, 1), u), lerp(corner(0, 1, 1), corner(1, 1, 1), u), v);
+        lerp(near, far, w)
+    }
+}
+
+/// ## Simplex
+/// Perlin's simplex noise, cheaper than Perlin in 3D and without the grid lines
+///
+/// based on Stefan Gustavson's "Simplex noise demystified"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simplex {
+    perm: Permutation,
+}
+
+impl Simplex {
+    pub fn new(seed: u64) -> Simplex {
+        Simplex { perm: Permutation::new(seed) }
+    }
+}
+
+impl Noise for Simplex {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        // skew onto the grid of squares, each square is two triangles
+        let f2 = 0.5 * (3f32.sqrt() - 1.0);
+        let g2 = (3.0 - 3f32.sqrt()) / 6.0;
+        let skew = (p.x() + p.y()) * f2;
+        let i = (p.x() + skew).floor();
+        let j = (p.y() + skew).floor();
+        let unskew = (i + j) * g2;
+        let x0 = p.x() - (i - unskew);
+        let y0 = p.y() - (j - unskew);
+        // which of the two triangles we're in
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        let (i, j) = (i as i32, j as i32);
+
+        let corners = [
+            (x0, y0, 0, 0),
+            (x0 - i1 as f32 + g2, y0 - j1 as f32 + g2, i1, j1),
+            (x0 - 1.0 + 2.0 * g2, y0 - 1.0 + 2.0 * g2, 1, 1),
+        ];
+        let total: f32 = corners.iter().map(|&(x, y, di, dj)| {
+            let t = 0.5 - x * x - y * y;
+            if t <= 0.0 {
+                return 0.0;
+            }
+            let g = GRAD3[self.perm.hash2(i + di, j + dj) % 12];
+            t * t * t * t * dot2([g[0], g[1]], x, y)
+        }).sum();
+        // scales the peaks to -1..1
+        70.0 * total
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        let f3 = 1.0 / 3.0;
+        let g3 = 1.0 / 6.0;
+        let skew = (p.x() + p.y() + p.z()) * f3;
+        let i = (p.x() + skew).floor();
+        let j = (p.y() + skew).floor();
+        let k = (p.z() + skew).floor();
+        let unskew = (i + j + k) * g3;
+        let x0 = p.x() - (i - unskew);
+        let y0 = p.y() - (j - unskew);
+        let z0 = p.z() - (k - unskew);
+
+        // the cube splits into six tetrahedra, the order of the offsets picks ours
+        let (second, third) = if x0 >= y0 {
+            if y0 >= z0 {
+                ((1, 0, 0), (1, 1, 0))
+            } else if x0 >= z0 {
+                ((1, 0, 0), (1, 0, 1))
+            } else {
+                ((0, 0, 1), (1, 0, 1))
+            }
+        } else if y0 < z0 {
+            ((0, 0, 1), (0, 1, 1))
+        } else if x0 < z0 {
+            ((0, 1, 0), (0, 1, 1))
+        } else {
+            ((0, 1, 0), (1, 1, 0))
+        };
+        let (i, j, k) = (i as i32, j as i32, k as i32);
+
+        let total: f32 = [(0, 0, 0), second, third, (1, 1, 1)].iter().map(|&(di, dj, dk): &(i32, i32, i32)| {
+            let corner = (di + dj + dk) as f32 * g3;
+            let (x, y, z) = (x0 - di as f32 + corner, y0 - dj as f32 + corner, z0 - dk as f32 + corner);
+            let t = 0.6 - x * x - y * y - z * z;
+            if t <= 0.0 {
+                return 0.0;
+            }
+            let g = GRAD3[self.perm.hash3(i + di, j + dj, k + dk) % 12];
+            t * t * t * t * dot3(g, x, y, z)
+        }).sum();
+        32.0 * total
+    }
+}
+
+/// ## Value
+/// random values on the whole number grid blended smoothly, blockier than gradient noise but cheap
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    perm: Permutation,
+}
+
+impl Value {
+    pub fn new(seed: u64) -> Value {
+        Value { perm: Permutation::new(seed) }
+    }
+
+    // 0..255 hash to -1..1
+    fn value(hash: usize) -> f32 {
+        hash as f32 / 127.5 - 1.0
+    }
+}
+
+impl Noise for Value {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let corner = |dx: i32, dy: i32| Value::value(self.perm.hash2(xi + dx, yi + dy));
+        let (u, v) = (fade(x), fade(y));
+        lerp(lerp(corner(0, 0), corner(1, 0), u), lerp(corner(0, 1), corner(1, 1), u), v)
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let (zi, z) = split(p.z());
+        let corner = |dx: i32, dy: i32, dz: i32| Value::value(self.perm.hash3(xi + dx, yi + dy, zi + dz));
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let near = lerp(lerp(corner(0, 0, 0), corner(1, 0, 0), u), lerp(corner(0, 1, 0), corner(1, 1, 0), u), v);
+        let far = lerp(lerp(corner(0, 0, 1), corner(1, 0, 1), u), lerp(corner(0, 1, 1), corner(1, 1, 1), u), v);
+        lerp(near, far, w)
+    }
+}
+
+/// ## WorleyDistance
+/// what Worley noise reports about the feature points around a sample
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WorleyDistance {
+    /// distance to the closest point, round cells that are dark in the middle
+    F1,
+    /// distance to the second closest point
+    F2,
+    /// F2 - F1, 0 along the borders between cells so it draws the cell walls
+    F2MinusF1,
+}
+
+/// ## Worley
+/// cellular noise, one jittered feature point per grid cell and the distances to the nearest ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worley {
+    perm: Permutation,
+    distance: WorleyDistance,
+}
+
+impl Worley {
+    pub fn new(seed: u64, distance: WorleyDistance) -> Worley {
+        Worley { perm: Permutation::new(seed), distance }
+    }
+
+    pub fn distance(&self) -> WorleyDistance {
+        self.distance
+    }
+
+    // 0..1 from a hash
+    fn jitter(hash: usize) -> f32 {
+        hash as f32 / 255.0
+    }
+
+    fn pick(&self, f1: f32, f2: f32) -> f32 {
+        match self.distance {
+            WorleyDistance::F1 => f1,
+            WorleyDistance::F2 => f2,
+            WorleyDistance::F2MinusF1 => f2 - f1,
+        }
+    }
+}
+
+impl Noise for Worley {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let (mut f1, mut f2) = (f32::MAX, f32::MAX);
+        // the nearest two points are almost always in the 3x3 block around us
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let (cx, cy) = (xi + dx, yi + dy);
+                let feature = Vec2D::new(
+                    dx as f32 + Worley::jitter(self.perm.hash3(cx, cy, 0)) - x,
+                    dy as f32 + Worley::jitter(self.perm.hash3(cx, cy, 1)) - y,
+                );
+                let d = feature.length_squared();
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+        self.pick(f1.sqrt(), f2.sqrt())
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        let (xi, x) = split(p.x());
+        let (yi, y) = split(p.y());
+        let (zi, z) = split(p.z());
+        let (mut f1, mut f2) = (f32::MAX, f32::MAX);
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let (cx, cy, cz) = (xi + dx, yi + dy, zi + dz);
+                    // each axis hashes the cell with its own salt on x so the three jitters are unrelated
+                    let feature = Vec3D::new(
+                        dx as f32 + Worley::jitter(self.perm.hash3(cx, cy, cz)) - x,
+                        dy as f32 + Worley::jitter(self.perm.hash3(cx + 101, cy, cz)) - y,
+                        dz as f32 + Worley::jitter(self.perm.hash3(cx + 211, cy, cz)) - z,
+                    );
+                    let d = feature.length_squared();
+                    if d < f1 {
+                        f2 = f1;
+                        f1 = d;
+                    } else if d < f2 {
+                        f2 = d;
+                    }
+                }
+            }
+        }
+        self.pick(f1.sqrt(), f2.sqrt())
+    }
+}
+
+/// ## FractalKind
+/// how Fractal folds each octave in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FractalKind {
+    /// fractal Brownian motion, octaves summed as is. rolling hills and clouds, stays in the base noise's range
+    Fbm,
+    /// 1 - |n| squared per octave, sharp crests for mountain ridges. 0..1 for -1..1 noise
+    Ridged,
+    /// |n| per octave, billowy creases for fire and marble. 0..1 for -1..1 noise
+    Turbulence,
+}
+
+/// ## Fractal
+/// layers octaves of a noise at rising frequency and falling amplitude so there's detail at every scale
+///
+/// the sum is divided by the total amplitude so adding octaves never changes the output range
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fractal<N: Noise> {
+    noise: N,
+    kind: FractalKind,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+impl<N: Noise> Fractal<N> {
+    /// each octave doubles the frequency and halves the amplitude, change that with set_lacunarity and set_gain
+    pub fn new(noise: N, kind: FractalKind, octaves: u32) -> Fractal<N> {
+        Fractal { noise, kind, octaves: octaves.max(1), lacunarity: 2.0, gain: 0.5 }
+    }
+
+    pub fn fbm(noise: N, octaves: u32) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Fbm, octaves)
+    }
+
+    pub fn ridged(noise: N, octaves: u32) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Ridged, octaves)
+    }
+
+    pub fn turbulence(noise: N, octaves: u32) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Turbulence, octaves)
+    }
+
+    pub fn noise(&self) -> &N {
+        &self.noise
+    }
+
+    pub fn kind(&self) -> FractalKind {
+        self.kind
+    }
+
+    pub fn octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// frequency multiplier between octaves
+    pub fn lacunarity(&self) -> f32 {
+        self.lacunarity
+    }
+
+    /// amplitude multiplier between octaves
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    pub fn set_octaves(&mut self, octaves: u32) {
+        self.octaves = octaves.max(1);
+    }
+
+    pub fn set_lacunarity(&mut self, lacunarity: f32) {
+        self.lacunarity = lacunarity;
+    }
+
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    fn fold(&self, n: f32) -> f32 {
+        match self.kind {
+            FractalKind::Fbm => n,
+            FractalKind::Ridged => (1.0 - n.abs()) * (1.0 - n.abs()),
+            FractalKind::Turbulence => n.abs(),
+        }
+    }
+
+    // runs the octaves with sample taking (frequency, octave shift)
+    fn layer(&self, sample: impl Fn(f32, f32) -> f32) -> f32 {
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let (mut sum, mut total) = (0.0, 0.0);
+        for octave in 0..self.octaves {
+            // shift every octave so they don't all line up on the lattice at the origin
+            sum += self.fold(sample(frequency, octave as f32 * 19.19)) * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        sum / total
+    }
+}
+
+impl<N: Noise> Noise for Fractal<N> {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        self.layer(|frequency, shift| {
+            self.noise.sample_2d(Pos2D::<S>::new(p.x() * frequency + shift, p.y() * frequency + shift))
+        })
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        self.layer(|frequency, shift| {
+            self.noise.sample_3d(Pos3D::new(p.x() * frequency + shift, p.y() * frequency + shift, p.z() * frequency + shift))
+        })
+    }
+}
+
+/// ## Warp
+/// domain warping, samples noise at a point pushed around by a second noise for swirly, folded looking results
+///
+/// see https://iquilezles.org/articles/warp
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warp<N: Noise, W: Noise> {
+    noise: N,
+    warp: W,
+    strength: f32,
+}
+
+impl<N: Noise, W: Noise> Warp<N, W> {
+    /// strength is how far in noise units a point can be pushed
+    pub fn new(noise: N, warp: W, strength: f32) -> Warp<N, W> {
+        Warp { noise, warp, strength }
+    }
+
+    pub fn noise(&self) -> &N {
+        &self.noise
+    }
+
+    pub fn warp(&self) -> &W {
+        &self.warp
+    }
+
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength;
+    }
+
+    /// how far p gets pushed before sampling
+    pub fn offset_2d<S: Space>(&self, p: Pos2D<S>) -> Vec2D {
+        // every axis reads the warp noise somewhere far away from the others so they move independently
+        Vec2D::new(
+            self.warp.sample_2d(p),
+            self.warp.sample_2d(p + Vec2D::new(5.2, 1.3)),
+        ) * self.strength
+    }
+
+    /// how far p gets pushed before sampling
+    pub fn offset_3d(&self, p: Pos3D) -> Vec3D {
+        Vec3D::new(
+            self.warp.sample_3d(p),
+            self.warp.sample_3d(p + Vec3D::new(5.2, 1.3, 7.1)),
+            self.warp.sample_3d(p + Vec3D::new(1.7, 9.2, 3.4)),
+        ) * self.strength
+    }
+}
+
+impl<N: Noise, W: Noise> Noise for Warp<N, W> {
+    fn sample_2d<S: Space>(&self, p: Pos2D<S>) -> f32 {
+        self.noise.sample_2d(p + self.offset_2d(p))
+    }
+
+    fn sample_3d(&self, p: Pos3D) -> f32 {
+        self.noise.sample_3d(p + self.offset_3d(p))
+    }
+}
+
+/// colors the pixels in xs by ys from noise through gradient, the region is clipped to the framebuffer
+///
+/// pixel (x, y) samples the noise at (x, y) * frequency so smaller frequencies give bigger features
+pub fn fill<N: Noise>(framebuffer: &mut Framebuffer, xs: Range<usize>, ys: Range<usize>, noise: &N, frequency: f32, gradient: &Gradient) {
+    let xs = xs.start.min(framebuffer.width())..xs.end.min(framebuffer.width());
+    let ys = ys.start.min(framebuffer.height())..ys.end.min(framebuffer.height());
+    for y in ys {
+        for x in xs.clone() {
+            let p = Pos2D::<Screen>::new(x as f32 * frequency, y as f32 * frequency);
+            framebuffer.set_pixel(x, y, gradient.at(noise.sample_2d(p)).to_u32());
+        }
+    }
+}
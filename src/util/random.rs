@@ -0,0 +1,312 @@
+//! Random
+//! Seeded randomness for procedural content that has to come out the same on every run and every machine.
+//! The generator is PCG32 (https://www.pcg-random.org) and everything built on it sticks to +, -, *, / and sqrt,
+//! which are exact under IEEE 754, so the same seed gives the same level everywhere. sin, cos and friends are left out on purpose
+//! since their last bit can change between platforms.
+
+use std::ops::Range;
+
+use crate::components::shapes::{Rect, Triangle};
+use crate::util::linear::{Aabb2D, Pos2D, Pos3D, Space, Vec2D, Vec3D};
+
+const MULTIPLIER: u64 = 6364136223846793005;
+// the increment from the PCG reference code, streams become increments as stream * 2 + 1
+const DEFAULT_INCREMENT: u64 = 0xda3e39cb94b95bdb;
+// candidates tried around each point before poisson_disc gives up on it, Bridson's suggested value
+const POISSON_ATTEMPTS: u32 = 30;
+
+/// ## Rng
+/// small, fast and seedable. not for anything security related.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+    inc: u64,
+}
+
+/// ## Uniform
+/// number types Rng::range can pick from
+pub trait Uniform: Sized {
+    /// an evenly distributed value in start..end, panics on an empty range
+    fn sample(rng: &mut Rng, range: Range<Self>) -> Self;
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng::with_stream(seed, DEFAULT_INCREMENT >> 1)
+    }
+
+    /// different streams with the same seed give unrelated sequences, handy for one generator per system
+    pub fn with_stream(seed: u64, stream: u64) -> Rng {
+        let mut rng = Rng { state: 0, inc: (stream << 1) | 1 };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        // xorshift high, random rotation
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        (high << 32) | self.next_u32() as u64
+    }
+
+    /// 0..1 with all 24 bits of f32 precision
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// 0..1 with all 53 bits of f64 precision
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u32() >> 31 == 1
+    }
+
+    // 0..bound without the bias a plain % would have, bound can't be 0
+    fn below(&mut self, bound: u64) -> u64 {
+        // throw away the few values at the bottom that would make the low results more likely
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u64();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+
+    /// a value in the range, e.g. rng.range(0..10) or rng.range(-1.0..1.0)
+    pub fn range<T: Uniform>(&mut self, range: Range<T>) -> T {
+        T::sample(self, range)
+    }
+
+    /// true with probability p
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+
+    /// Fisher-Yates, every order is equally likely
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// None for an empty slice
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// index picked with probability proportional to its weight
+    ///
+    /// negative and non-finite weights count as 0, None if nothing has any weight
+    pub fn weighted_index(&mut self, weights: &[f32]) -> Option<usize> {
+        let weight = |w: f32| if w.is_finite() && w > 0.0 { w } else { 0.0 };
+        let total: f32 = weights.iter().map(|&w| weight(w)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = self.next_f32() * total;
+        let mut last = None;
+        for (i, &w) in weights.iter().enumerate() {
+            let w = weight(w);
+            if w == 0.0 {
+                continue;
+            }
+            if target < w {
+                return Some(i);
+            }
+            target -= w;
+            last = Some(i);
+        }
+        // rounding in the running total can leave target a hair past the end
+        last
+    }
+
+    /// item picked with probability proportional to weight(item), same rules as weighted_index
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [T], weight: impl Fn(&T) -> f32) -> Option<&'a T> {
+        let weights: Vec<f32> = items.iter().map(weight).collect();
+        self.weighted_index(&weights).map(|i| &items[i])
+    }
+
+    // a point in the unit disc, picked from the square until we land inside so there's no trig for platforms to disagree on
+    fn in_unit_disc(&mut self) -> Vec2D {
+        loop {
+            let v = Vec2D::new(self.range(-1.0f32..1.0), self.range(-1.0..1.0));
+            if v.length_squared() <= 1.0 {
+                return v;
+            }
+        }
+    }
+
+    fn in_unit_ball(&mut self) -> Vec3D {
+        loop {
+            let v = Vec3D::new(self.range(-1.0f32..1.0), self.range(-1.0..1.0), self.range(-1.0..1.0));
+            if v.length_squared() <= 1.0 {
+                return v;
+            }
+        }
+    }
+
+    /// random direction, every angle equally likely
+    pub fn unit_vec2d(&mut self) -> Vec2D {
+        loop {
+            let v = self.in_unit_disc();
+            let length_squared = v.length_squared();
+            // anything too close to the middle would blow up when normalized
+            if length_squared > 1e-6 {
+                return v / length_squared.sqrt();
+            }
+        }
+    }
+
+    /// random direction on the unit sphere
+    pub fn unit_vec3d(&mut self) -> Vec3D {
+        loop {
+            let v = self.in_unit_ball();
+            let length_squared = v.length_squared();
+            // anything too close to the middle would blow up when normalized
+            if length_squared > 1e-6 {
+                return v / length_squared.sqrt();
+            }
+        }
+    }
+
+    /// a point inside the box
+    pub fn pos_in_aabb<S: Space>(&mut self, aabb: &Aabb2D<S>) -> Pos2D<S> {
+        aabb.min() + aabb.size().mul_elem(Vec2D::new(self.next_f32(), self.next_f32()))
+    }
+
+    /// a point inside the circle, spread evenly over its area
+    pub fn pos_in_circle<S: Space>(&mut self, center: Pos2D<S>, radius: f32) -> Pos2D<S> {
+        center + self.in_unit_disc() * radius
+    }
+
+    /// a point inside the sphere, spread evenly over its volume
+    pub fn pos_in_sphere(&mut self, center: Pos3D, radius: f32) -> Pos3D {
+        center + self.in_unit_ball() * radius
+    }
+
+    /// a point inside the triangle, spread evenly over its area
+    pub fn pos_in_triangle<S: Space>(&mut self, triangle: &Triangle<S>) -> Pos2D<S> {
+        let (mut u, mut v) = (self.next_f32(), self.next_f32());
+        // points past the diagonal of the parallelogram get folded back into the triangle
+        if u + v > 1.0 {
+            u = 1.0 - u;
+            v = 1.0 - v;
+        }
+        let a = triangle.a();
+        a + (triangle.b() - a) * u + (triangle.c() - a) * v
+    }
+
+    /// a point inside the rect, spread evenly over its area
+    pub fn pos_in_rect<S: Space>(&mut self, rect: &Rect<S>) -> Pos2D<S> {
+        let a = rect.a();
+        a + (rect.b() - a) * self.next_f32() + (rect.d() - a) * self.next_f32()
+    }
+
+    /// evenly spread points inside the box with none closer than min_distance to another, Bridson's algorithm
+    ///
+    /// good for scattering trees and rocks without clumps, empty if min_distance isn't positive or the box has no area
+    pub fn poisson_disc<S: Space>(&mut self, bounds: &Aabb2D<S>, min_distance: f32) -> Vec<Pos2D<S>> {
+        if !(min_distance > 0.0 && min_distance.is_finite()) || bounds.width() <= 0.0 || bounds.height() <= 0.0 {
+            return Vec::new();
+        }
+        // cells small enough that each holds at most one point
+        let cell = min_distance / std::f32::consts::SQRT_2;
+        let columns = (bounds.width() / cell).ceil() as usize;
+        let rows = (bounds.height() / cell).ceil() as usize;
+        let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+        let cell_of = |p: Pos2D<S>| {
+            let offset = p - bounds.min();
+            let x = ((offset.dx() / cell) as usize).min(columns - 1);
+            let y = ((offset.dy() / cell) as usize).min(rows - 1);
+            (x, y)
+        };
+
+        let first = self.pos_in_aabb(bounds);
+        let (x, y) = cell_of(first);
+        grid[y * columns + x] = Some(0);
+        let mut points = vec![first];
+        let mut active = vec![0];
+
+        while !active.is_empty() {
+            let slot = self.below(active.len() as u64) as usize;
+            let around = points[active[slot]];
+            let mut found = false;
+            for _ in 0..POISSON_ATTEMPTS {
+                let candidate = around + self.unit_vec2d() * self.range(min_distance..min_distance * 2.0);
+                if !bounds.contains(&candidate) {
+                    continue;
+                }
+                let (x, y) = cell_of(candidate);
+                // anything too close has to be within two cells
+                let crowded = (y.saturating_sub(2)..(y + 3).min(rows)).any(|ny| {
+                    (x.saturating_sub(2)..(x + 3).min(columns)).any(|nx| {
+                        grid[ny * columns + nx].is_some_and(|i| (points[i] - candidate).length_squared() < min_distance * min_distance)
+                    })
+                });
+                if !crowded {
+                    grid[y * columns + x] = Some(points.len());
+                    active.push(points.len());
+                    points.push(candidate);
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                active.swap_remove(slot);
+            }
+        }
+        points
+    }
+}
+
+macro_rules! impl_uniform_int {
+    ($($t:ty => $unsigned:ty),*) => {$(
+        impl Uniform for $t {
+            fn sample(rng: &mut Rng, range: Range<$t>) -> $t {
+                assert!(range.start < range.end, "empty range {:?}", range);
+                // the span always fits the unsigned type even when it doesn't fit the signed one
+                let span = range.end.wrapping_sub(range.start) as $unsigned as u64;
+                range.start.wrapping_add(rng.below(span) as $t)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_uniform_float {
+    ($($t:ty => $next:ident),*) => {$(
+        impl Uniform for $t {
+            fn sample(rng: &mut Rng, range: Range<$t>) -> $t {
+                assert!(range.start < range.end, "empty range {:?}", range);
+                loop {
+                    let v = range.start + (range.end - range.start) * rng.$next();
+                    // rounding can land exactly on end, which is outside the range
+                    if v < range.end {
+                        return v;
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+impl_uniform_int!(i32 => u32, i64 => u64, u32 => u32, u64 => u64, usize => usize);
+impl_uniform_float!(f32 => next_f32, f64 => next_f64);